@@ -0,0 +1,192 @@
+// Tests
+mod test;
+
+// Structs
+use crate::config::load::include::{read_includes, resolve_includes};
+use crate::config::load::pipelines::pipeline_files;
+use crate::Config;
+use serde::{Deserialize, Serialize};
+// Filesystem
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+// Hash
+use sha2::{Digest, Sha256};
+use utils::teleport::PIPELINES_DIR;
+// Error Handling
+use log::{trace, warn};
+use miette::{IntoDiagnostic, Result};
+
+pub const CACHE_DIR: &str = ".pipelight/_internals/cache";
+
+/**
+The evaluated config stored on disk along with the key it was computed from.
+*/
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    config: Config,
+}
+
+/**
+A cache for the evaluated config file.
+
+The key is a hash of the config file, its transitive local imports,
+the arguments passed to the script and the version of this crate,
+so that any change to one of them invalidates the cached config.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cache {
+    pub key: String,
+    pub path: PathBuf,
+}
+
+impl Cache {
+    pub fn new(file_path: &str, args: &Option<Vec<String>>) -> Result<Cache> {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        for file in Cache::sources(file_path)? {
+            hasher.update([0]);
+            hasher.update(file.to_str().unwrap());
+            hasher.update([0]);
+            hasher.update(fs::read(&file).into_diagnostic()?);
+        }
+        for arg in args.clone().unwrap_or_default() {
+            hasher.update([0]);
+            hasher.update(arg);
+        }
+        Ok(Cache {
+            key: format!("{:x}", hasher.finalize()),
+            path: Path::new(CACHE_DIR).join("config.json"),
+        })
+    }
+    /**
+    Return the config file path followed by every local file it imports
+    or includes, directly or through another local file,
+    and the files of the pipelines directory.
+    */
+    fn sources(file_path: &str) -> Result<Vec<PathBuf>> {
+        let path = fs::canonicalize(file_path).into_diagnostic()?;
+        let mut files = if path.is_dir() {
+            pipeline_files(&path)?
+        } else {
+            let mut files = vec![path.clone()];
+            let dir = path.parent().unwrap().join(PIPELINES_DIR);
+            if dir.is_dir() {
+                files.extend(pipeline_files(&dir)?);
+            }
+            files
+        };
+        let mut visited: BTreeSet<PathBuf> = files.iter().cloned().collect();
+        let mut i = 0;
+        while i < files.len() {
+            let src = fs::read(&files[i]).into_diagnostic()?;
+            let dir = files[i].parent().unwrap().to_owned();
+            let mut paths: Vec<PathBuf> = local_imports(&String::from_utf8_lossy(&src))
+                .iter()
+                .map(|e| dir.join(e))
+                .collect();
+            if let Some(include) = read_includes(&files[i]) {
+                paths.extend(resolve_includes(&files[i], &include).unwrap_or_default());
+            }
+            for path in paths {
+                if let Ok(path) = fs::canonicalize(path) {
+                    if path.is_file() && visited.insert(path.clone()) {
+                        files.push(path);
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(files)
+    }
+    /// Return the cached config if it was stored with the same key.
+    pub fn get(&self) -> Option<Config> {
+        let json = fs::read_to_string(&self.path).ok()?;
+        let entry = serde_json::from_str::<CacheEntry>(&json).ok()?;
+        if entry.key == self.key {
+            trace!("Config cache hit");
+            Some(entry.config)
+        } else {
+            trace!("Config cache miss");
+            None
+        }
+    }
+    /**
+    Store the config under the cache key.
+    The file is written aside and then renamed so that concurrent
+    pipelight processes never read a partially written cache.
+    */
+    pub fn set(&self, config: &Config) -> Result<()> {
+        let entry = CacheEntry {
+            key: self.key.clone(),
+            config: config.clone(),
+        };
+        let json = serde_json::to_string(&entry).into_diagnostic()?;
+        fs::create_dir_all(self.path.parent().unwrap()).into_diagnostic()?;
+        let tmp = self
+            .path
+            .with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp, json).into_diagnostic()?;
+        fs::rename(&tmp, &self.path).into_diagnostic()?;
+        Ok(())
+    }
+}
+
+impl Config {
+    /**
+    Load the config file like Config::load,
+    but return the cached config when the file and its imports are unchanged.
+    */
+    pub fn load_cached(file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
+        Cache::new(file_path, &args)?.load(file_path, args)
+    }
+}
+
+impl Cache {
+    /**
+    Return the cached config, or load the config file and cache it.
+
+    Interpolations are checked again on a cache hit
+    because they depend on the environment, which is not part of the key.
+    */
+    pub fn load(&self, file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
+        if let Some(config) = self.get() {
+            let src = fs::read_to_string(file_path).unwrap_or_default();
+            config.check_interpolations(&src)?;
+            return Ok(config);
+        }
+        let config = Config::load(file_path, args)?;
+        if let Err(e) = self.set(&config) {
+            warn!("Could not cache the config: {}", e);
+        }
+        Ok(config)
+    }
+}
+
+/**
+Return the relative module specifiers of a script
+from its import and export statements and dynamic imports.
+*/
+pub fn local_imports(src: &str) -> Vec<String> {
+    let mut imports = vec![];
+    for keyword in ["from", "import"] {
+        for (i, _) in src.match_indices(keyword) {
+            let rest = src[i + keyword.len()..].trim_start();
+            let rest = rest.strip_prefix('(').unwrap_or(rest).trim_start();
+            let Some(quote) = rest.chars().next() else {
+                continue;
+            };
+            if !['"', '\'', '`'].contains(&quote) {
+                continue;
+            }
+            if let Some(end) = rest[1..].find(quote) {
+                let specifier = &rest[1..end + 1];
+                if specifier.starts_with("./") || specifier.starts_with("../") {
+                    imports.push(specifier.to_owned());
+                }
+            }
+        }
+    }
+    imports
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod cache {
+    use crate::config::cache::{local_imports, Cache};
+    use crate::Config;
+    use std::fs;
+
+    #[test]
+    fn find_local_imports() {
+        let src = r#"
+import { a } from "./a.ts";
+import type { B } from '../b.ts';
+export * from "./c.ts";
+import "https://deno.land/x/pipelight/mod.ts";
+const d = await import("./d.ts");
+"#;
+        let mut imports = local_imports(src);
+        imports.sort();
+        assert_eq!(imports, vec!["../b.ts", "./a.ts", "./c.ts", "./d.ts"]);
+    }
+    #[test]
+    fn invalidate_on_import_change() {
+        let dir = std::env::temp_dir().join(format!("pipelight-cache-key-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("pipelight.ts").display().to_string();
+        let import_path = dir.join("steps.ts");
+        fs::write(&config_path, "import { steps } from \"./steps.ts\";\n").unwrap();
+        fs::write(&import_path, "export const steps = [];\n").unwrap();
+
+        let cache = Cache::new(&config_path, &None).unwrap();
+        let same = Cache::new(&config_path, &None).unwrap();
+        // Args are part of the key
+        let with_args = Cache::new(&config_path, &Some(vec!["--host".to_owned()])).unwrap();
+        // So are the transitive imports
+        fs::write(&import_path, "export const steps = [1];\n").unwrap();
+        let changed = Cache::new(&config_path, &None).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(cache, same);
+        assert_ne!(cache.key, with_args.key);
+        assert_ne!(cache.key, changed.key);
+    }
+    #[test]
+    fn store_and_retrieve() {
+        let dir = std::env::temp_dir().join(format!("pipelight-cache-{}", std::process::id()));
+        let config = Config::load("./public/pipelight.yaml", None).unwrap();
+        let mut cache = Cache::new("./public/pipelight.yaml", &None).unwrap();
+        cache.path = dir.join("config.json");
+        cache.set(&config).unwrap();
+        let cached = cache.get();
+        // A different key misses
+        cache.key = "other".to_owned();
+        let missed = cache.get();
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&cached.unwrap()).unwrap(),
+            serde_json::to_string(&config).unwrap()
+        );
+        assert!(missed.is_none());
+    }
+    #[test]
+    fn check_interpolations_on_hit() {
+        let dir = std::env::temp_dir().join(format!("pipelight-cache-env-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("pipelight.yaml");
+        let config_path = config_path.to_str().unwrap();
+        let src = r#"
+pipelines:
+  - name: deploy
+    steps:
+      - name: release
+        commands: ["echo ${{ env.PIPELIGHT_CACHE_TEST_VAR }}"]
+"#;
+        fs::write(config_path, src).unwrap();
+        let config = serde_yaml::from_str::<Config>(src).unwrap();
+        let mut cache = Cache::new(config_path, &None).unwrap();
+        cache.path = dir.join("config.json");
+        cache.set(&config).unwrap();
+        assert!(cache.get().is_some());
+
+        // The cached config is still checked against the environment
+        let res = cache.load(config_path, None);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,631 @@
+// Tests
+mod test;
+
+pub(crate) mod node;
+
+// Structs
+use crate::config::load::include::resolve_includes;
+use crate::config::load::pipelines::pipeline_files;
+use crate::config::schema::flags;
+use crate::Config;
+use node::{Node, Value};
+use utils::dates::convert::str_to_std_duration;
+use utils::dates::Schedule;
+use utils::files::FileType;
+use utils::teleport::PIPELINES_DIR;
+// Traits
+use convert_case::{Case, Casing};
+// Globbing
+use glob::Pattern;
+// Filesystem
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+// Error Handling
+use crate::error::{CheckProblem, CheckReport};
+use miette::{Severity, SourceSpan};
+
+/**
+The step execution modes, as snake cased strings.
+*/
+pub(crate) const MODES: [&str; 3] = ["stop", "jump_next", "continue"];
+/**
+The scheduled triggers catch-up policies.
+*/
+pub(crate) const CATCH_UP: [&str; 3] = ["skip", "latest", "all"];
+/**
+The final pipeline statuses an "after" trigger can fire on.
+*/
+pub(crate) const STATUSES: [&str; 4] = ["succeeded", "failed", "aborted", "timed-out"];
+/**
+The pipeline parameter types.
+*/
+pub(crate) const PARAM_TYPES: [&str; 3] = ["string", "number", "boolean"];
+
+/**
+The fallback keys of pipelines, steps and parallel steps.
+*/
+const FALLBACKS: [&str; 5] = [
+    "on_started",
+    "on_failure",
+    "on_success",
+    "on_abortion",
+    "on_timeout",
+];
+
+/**
+A config file source and its raw values, for the problems to point at.
+*/
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub path: String,
+    pub src: String,
+    pub root: Node,
+    /// Whether the file holds a single pipeline, from the pipelines directory.
+    pub is_pipeline: bool,
+}
+
+impl Document {
+    /**
+    Parse a config file source without converting it into the config types,
+    so that a faulty value doesn't hide the other problems.
+    Scripts are evaluated, and their values have no spans.
+
+    Returns None when the file can't be parsed, for the config loading to report why.
+    */
+    pub fn new(
+        path: &str,
+        src: &str,
+        args: Option<Vec<String>>,
+        is_pipeline: bool,
+    ) -> Option<Document> {
+        let extension = Path::new(path).extension()?.to_str()?.to_owned();
+        let root = match FileType::from(&extension) {
+            FileType::Toml | FileType::Tml => Node::from_toml(src)?,
+            FileType::Yaml | FileType::Yml | FileType::Json => Node::from_yaml(src)?,
+            FileType::Json5 => Node::from_json(json5::from_str(src).ok()?),
+            FileType::TypeScript => {
+                Node::from_json(serde_json::from_str(&Config::ts_json(path, args).ok()?).ok()?)
+            }
+            FileType::JavaScript => {
+                Node::from_json(serde_json::from_str(&Config::js_json(path, args).ok()?).ok()?)
+            }
+        };
+        Some(Document {
+            path: path.to_owned(),
+            src: src.to_owned(),
+            root,
+            is_pipeline,
+        })
+    }
+    fn read(path: &Path, args: Option<Vec<String>>, is_pipeline: bool) -> Option<Document> {
+        let src = fs::read_to_string(path).ok()?;
+        Document::new(path.to_str()?, &src, args, is_pipeline)
+    }
+    /**
+    Return the config file, the files it includes recursively,
+    and the files of the pipelines directory next to it.
+    The file path can also be the pipelines directory itself.
+    */
+    pub fn collect(file_path: &str, args: Option<Vec<String>>) -> Vec<Document> {
+        let path = Path::new(file_path);
+        if path.is_dir() {
+            return Document::pipelines_dir(path, args);
+        }
+        let mut documents = vec![];
+        Document::collect_includes(path, args.clone(), &mut HashSet::new(), &mut documents);
+        let dir = path.parent().unwrap().join(PIPELINES_DIR);
+        if dir.is_dir() {
+            documents.extend(Document::pipelines_dir(&dir, args));
+        }
+        documents
+    }
+    fn collect_includes(
+        path: &Path,
+        args: Option<Vec<String>>,
+        visited: &mut HashSet<PathBuf>,
+        documents: &mut Vec<Document>,
+    ) {
+        let Ok(canonical) = fs::canonicalize(path) else {
+            return;
+        };
+        if !visited.insert(canonical.clone()) {
+            return;
+        }
+        let Some(document) = Document::read(path, args.clone(), false) else {
+            return;
+        };
+        let include: Vec<String> = document
+            .root
+            .get("include")
+            .map(Node::items)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|e| e.as_str().map(str::to_owned))
+            .collect();
+        documents.push(document);
+        for file in resolve_includes(&canonical, &include).unwrap_or_default() {
+            Document::collect_includes(&file, args.clone(), visited, documents);
+        }
+    }
+    fn pipelines_dir(dir: &Path, args: Option<Vec<String>>) -> Vec<Document> {
+        pipeline_files(dir)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|file| Document::read(file, args.clone(), true))
+            .collect()
+    }
+    /**
+    Return the pipelines declared in the file.
+    */
+    fn pipelines(&self) -> Vec<&Node> {
+        if self.is_pipeline {
+            vec![&self.root]
+        } else {
+            self.root
+                .get("pipelines")
+                .map(Node::items)
+                .unwrap_or_default()
+                .iter()
+                .collect()
+        }
+    }
+    fn templates(&self) -> &[Node] {
+        match self.is_pipeline {
+            true => &[],
+            false => self
+                .root
+                .get("templates")
+                .map(Node::items)
+                .unwrap_or_default(),
+        }
+    }
+    fn problem(&self, severity: Severity, message: &str, node: &Node) -> CheckProblem {
+        CheckProblem::new(severity, message, node.span(), &self.src)
+    }
+}
+
+impl Config {
+    /**
+    Report every problem found in the config files at once:
+    the config file, the files it includes and the pipelines directory files.
+
+    The checks run on the raw parsed values rather than on the config types,
+    so that they point at the faulty values in the file sources
+    and don't stop at the first value that fails to convert.
+
+    Enforces:
+    - unique pipeline names, without whitespaces (warning),
+    - known trigger actions (git hooks and special flags),
+    - valid glob patterns in trigger branches, tags and paths,
+    - valid trigger schedules and catch-up policies,
+    - known upstream pipelines (warning) and statuses of "after" triggers,
+    - known step and parallel execution modes,
+    - non empty step commands (warning),
+    - matrix axes with values,
+    - known step templates and extended pipelines, without loops,
+    - valid watch debounce duration,
+    - known parameter types and allowed default values.
+    */
+    pub fn validate(file_path: &str, args: Option<Vec<String>>) -> Result<(), CheckReport> {
+        validate(&Document::collect(file_path, args))
+    }
+}
+
+/**
+Report every problem found in the documents at once.
+Pipelines and templates are looked up across every document.
+*/
+pub fn validate(documents: &[Document]) -> Result<(), CheckReport> {
+    let mut checker = Checker {
+        pipelines: documents
+            .iter()
+            .flat_map(|e| e.pipelines())
+            .filter_map(|e| e.get("name")?.as_str())
+            .collect(),
+        templates: documents
+            .iter()
+            .flat_map(|e| e.templates())
+            .filter_map(|e| e.get("name")?.as_str())
+            .collect(),
+        extends: documents
+            .iter()
+            .flat_map(|e| e.pipelines())
+            .filter_map(|e| Some((e.get("name")?.as_str()?, e.get("extends")?.as_str()?)))
+            .collect(),
+        uses: documents
+            .iter()
+            .flat_map(|e| e.templates())
+            .filter_map(|e| Some((e.get("name")?.as_str()?, used_templates(e))))
+            .collect(),
+        origins: HashMap::new(),
+        problems: vec![],
+    };
+    for document in documents {
+        // Options are only read from config files
+        if let (false, Some(options)) = (document.is_pipeline, document.root.get("options")) {
+            checker.options(document, options);
+        }
+        for template in document.templates() {
+            checker.step(document, template);
+        }
+        for pipeline in document.pipelines() {
+            checker.pipeline(document, pipeline);
+        }
+    }
+    if checker.problems.is_empty() {
+        Ok(())
+    } else {
+        Err(CheckReport::new(checker.problems))
+    }
+}
+
+struct Checker<'a> {
+    pipelines: Vec<&'a str>,
+    templates: Vec<&'a str>,
+    // The pipeline every pipeline extends
+    extends: HashMap<&'a str, &'a str>,
+    // The templates every template uses in its fallbacks
+    uses: HashMap<&'a str, Vec<&'a str>>,
+    // The file defining every pipeline name
+    origins: HashMap<&'a str, &'a str>,
+    problems: Vec<CheckProblem>,
+}
+
+impl<'a> Checker<'a> {
+    fn options(&mut self, doc: &Document, options: &Node) {
+        if let Some(node) = options.get("watch_debounce") {
+            if let Some(debounce) = node.as_str() {
+                if str_to_std_duration(debounce).is_err() {
+                    let message = format!("Invalid watch debounce duration \"{}\"", debounce);
+                    let help = "Use an ISO8601 (PT1S) or a human readable (500ms) duration";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                }
+            }
+        }
+    }
+    fn pipeline(&mut self, doc: &'a Document, pipeline: &'a Node) {
+        if let Some(node) = pipeline.get("name") {
+            if let Some(name) = node.as_str() {
+                match self.origins.get(name) {
+                    Some(origin) if *origin == doc.path => {
+                        let message = format!("Duplicate pipeline name \"{}\"", name);
+                        let help =
+                            "Only the first pipeline with this name is kept, rename this one";
+                        self.problems
+                            .push(doc.problem(Severity::Error, &message, node).help(help));
+                    }
+                    Some(origin) => {
+                        let message =
+                            format!("The pipeline \"{}\" is already defined in {}", name, origin);
+                        let help = "A pipeline name can't be defined in two files, rename one";
+                        self.problems
+                            .push(doc.problem(Severity::Error, &message, node).help(help));
+                    }
+                    None => {
+                        self.origins.insert(name, &doc.path);
+                    }
+                }
+                if name.contains(char::is_whitespace) {
+                    let message = format!("The pipeline name \"{}\" contains whitespaces", name);
+                    self.problems
+                        .push(doc.problem(Severity::Warning, &message, node));
+                }
+            }
+        }
+        if let Some(node) = pipeline.get("extends") {
+            if let Some(extends) = node.as_str() {
+                let name = pipeline
+                    .get("name")
+                    .and_then(Node::as_str)
+                    .unwrap_or_default();
+                let references = |e: &str| self.extends.get(e).into_iter().copied().collect();
+                if !self.pipelines.contains(&extends) {
+                    let message = format!("Unknown extended pipeline \"{}\"", extends);
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node));
+                } else if let Some(chain) = find_loop(name, &references) {
+                    let message = format!("The pipeline inheritance loops: {}", chain.join(" -> "));
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node));
+                }
+            }
+        }
+        for trigger in items(pipeline, "triggers") {
+            self.trigger(doc, trigger);
+        }
+        // Only manual runs can be given parameters
+        let triggerable = items(pipeline, "triggers").iter().any(|trigger| {
+            let actions = items(trigger, "actions");
+            actions.is_empty() || actions.iter().any(|e| e.as_str() != Some("manual"))
+        });
+        for param in items(pipeline, "params") {
+            self.param(doc, param);
+            if triggerable && param.get("default").is_none() {
+                let node = param.get("name").unwrap_or(param);
+                let message = format!(
+                    "The parameter \"{}\" has no default value to trigger the pipeline with",
+                    node.as_str().unwrap_or_default()
+                );
+                let help = "Give it a default value, or only trigger the pipeline manually";
+                self.problems
+                    .push(doc.problem(Severity::Warning, &message, node).help(help));
+            }
+        }
+        if let Some(matrix) = pipeline.get("matrix") {
+            self.matrix(doc, matrix);
+        }
+        self.steps(doc, pipeline.get("steps"));
+        self.fallback(doc, pipeline);
+    }
+    fn trigger(&mut self, doc: &Document, trigger: &Node) {
+        for key in ["branches", "tags", "paths", "paths_ignore"] {
+            for node in items(trigger, key) {
+                let Some(pattern) = node.as_str() else {
+                    continue;
+                };
+                if let Err(e) = Pattern::new(pattern) {
+                    let message = format!("Invalid glob pattern \"{}\": {}", pattern, e.msg);
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node));
+                }
+            }
+        }
+        let schedule = trigger.get("schedule");
+        let after = trigger.get("after");
+        for node in items(trigger, "actions") {
+            let Some(action) = node.as_str() else {
+                continue;
+            };
+            if !is_known_flag(action) {
+                let message = format!("Unknown trigger action \"{}\"", action);
+                let help = "Use a git hook (pre-push, post-commit...) \
+                    or manual, watch, blank, schedule, webhook, after";
+                self.problems
+                    .push(doc.problem(Severity::Error, &message, node).help(help));
+            } else if schedule.is_none() && action.to_case(Case::Kebab) == "schedule" {
+                let message = "The schedule action needs a \"schedule\" cron expression";
+                let help = "Add a schedule to the trigger, ie: schedule: \"0 3 * * *\"";
+                self.problems
+                    .push(doc.problem(Severity::Error, message, node).help(help));
+            } else if after.is_none() && action.to_case(Case::Kebab) == "after" {
+                let message = "The after action needs an \"after\" upstream pipeline condition";
+                let help = "Add an upstream to the trigger, ie: after: { pipeline: build }";
+                self.problems
+                    .push(doc.problem(Severity::Error, message, node).help(help));
+            }
+        }
+        if let Some(node) = schedule {
+            if let Some(schedule) = node.as_str() {
+                if let Err(e) = Schedule::new(schedule) {
+                    let message = format!("Invalid schedule \"{}\": {}", schedule, e);
+                    let help = "Use a cron expression: minute hour day month weekday";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                }
+            }
+        }
+        if let Some(node) = trigger.get("catch_up") {
+            if let Some(catch_up) = node.as_str() {
+                if !CATCH_UP.contains(&catch_up.to_case(Case::Snake).as_str()) {
+                    let message = format!("Unknown catch-up policy \"{}\"", catch_up);
+                    let help = "Use one of skip, latest or all";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                }
+            }
+        }
+        if let Some(after) = after {
+            if let Some(node) = after.get("pipeline") {
+                if let Some(upstream) = node.as_str() {
+                    if !self.pipelines.contains(&upstream) {
+                        let message = format!("Unknown upstream pipeline \"{}\"", upstream);
+                        let help = "It must be declared in this file or in the global config";
+                        self.problems
+                            .push(doc.problem(Severity::Warning, &message, node).help(help));
+                    }
+                }
+            }
+            for node in items(after, "status") {
+                let Some(status) = node.as_str() else {
+                    continue;
+                };
+                if !STATUSES.contains(&status.to_case(Case::Kebab).as_str()) {
+                    let message = format!("Unknown upstream status \"{}\"", status);
+                    let help = "Use one of succeeded, failed, aborted or timed-out";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                }
+            }
+        }
+    }
+    fn param(&mut self, doc: &Document, param: &Node) {
+        if let Some(node) = param.get("type") {
+            if let Some(kind) = node.as_str() {
+                if !PARAM_TYPES.contains(&kind) {
+                    let message = format!("Unknown parameter type \"{}\"", kind);
+                    let help = "Use one of string, number or boolean";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                }
+            }
+        }
+        if let (Some(default), Some(values)) = (param.get("default"), param.get("values")) {
+            if !values.items().iter().any(|e| e.value == default.value) {
+                let name = param.get("name").and_then(Node::as_str).unwrap_or_default();
+                let message = format!(
+                    "The default value of the parameter \"{}\" is not allowed",
+                    name
+                );
+                self.problems
+                    .push(doc.problem(Severity::Error, &message, default));
+            }
+        }
+    }
+    fn matrix(&mut self, doc: &Document, matrix: &Node) {
+        for (axis, values) in matrix.entries() {
+            if matches!(&values.value, Value::Seq(e) if e.is_empty()) {
+                let name = axis.as_str().unwrap_or_default();
+                let message = format!("The matrix axis \"{}\" has no values", name);
+                let help = "Add values to the axis or remove it";
+                self.problems
+                    .push(doc.problem(Severity::Error, &message, axis).help(help));
+            }
+        }
+    }
+    fn steps(&mut self, doc: &Document, steps: Option<&Node>) {
+        for step in steps.map(Node::items).unwrap_or_default() {
+            self.step(doc, step);
+        }
+    }
+    fn step(&mut self, doc: &Document, step: &Node) {
+        if let Some(node) = step.get("use") {
+            if let Some(template) = node.as_str() {
+                let references = |e: &str| self.uses.get(e).cloned().unwrap_or_default();
+                if !self.templates.contains(&template) {
+                    let message = format!("Unknown step template \"{}\"", template);
+                    let help = "Declare it in the top level templates list";
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node).help(help));
+                } else if let Some(chain) = find_loop(template, &references) {
+                    let message = format!(
+                        "The step template \"{}\" uses itself: {}",
+                        template,
+                        chain.join(" -> ")
+                    );
+                    self.problems
+                        .push(doc.problem(Severity::Error, &message, node));
+                }
+            }
+        } else if let Some(parallel) = step.get("parallel") {
+            for step in parallel.items() {
+                self.step(doc, step);
+            }
+            if let Some(mode) = step.get("mode") {
+                self.mode(doc, mode);
+            }
+            self.fallback(doc, step);
+        } else {
+            if step.get("commands").map_or(true, |e| e.items().is_empty()) {
+                let name = step.get("name");
+                let message = format!(
+                    "The step \"{}\" has no commands",
+                    name.and_then(Node::as_str).unwrap_or_default()
+                );
+                self.problems
+                    .push(doc.problem(Severity::Warning, &message, name.unwrap_or(step)));
+            }
+            if let Some(mode) = step.get("options").and_then(|e| e.get("mode")) {
+                self.mode(doc, mode);
+            }
+            if let Some(matrix) = step.get("matrix") {
+                self.matrix(doc, matrix);
+            }
+            self.fallback(doc, step);
+        }
+    }
+    fn fallback(&mut self, doc: &Document, node: &Node) {
+        for key in FALLBACKS {
+            self.steps(doc, node.get(key));
+        }
+    }
+    fn mode(&mut self, doc: &Document, node: &Node) {
+        let Some(mode) = node.as_str() else {
+            return;
+        };
+        if !MODES.contains(&mode.to_case(Case::Snake).as_str()) {
+            let message = format!("Unknown execution mode \"{}\"", mode);
+            let help = "Use one of stop, jump_next or continue";
+            self.problems
+                .push(doc.problem(Severity::Error, &message, node).help(help));
+        }
+    }
+}
+
+/**
+Return the items of a sequence under a map key.
+*/
+fn items<'a>(node: &'a Node, key: &str) -> &'a [Node] {
+    node.get(key).map(Node::items).unwrap_or_default()
+}
+
+/**
+Return the span of the name of the first pipeline with this name
+in a markup file source (toml, yaml, json).
+*/
+pub(crate) fn pipeline_name_span(path: &Path, src: &str, name: &str) -> Option<SourceSpan> {
+    let extension = path.extension()?.to_str()?.to_owned();
+    let root = match FileType::from(&extension) {
+        FileType::Toml | FileType::Tml => Node::from_toml(src)?,
+        FileType::Yaml | FileType::Yml | FileType::Json => Node::from_yaml(src)?,
+        _ => return None,
+    };
+    // A config file or a single pipeline file
+    let pipelines = match root.get("pipelines") {
+        Some(pipelines) => pipelines.items(),
+        None => std::slice::from_ref(&root),
+    };
+    pipelines
+        .iter()
+        .filter_map(|e| e.get("name"))
+        .find(|e| e.as_str() == Some(name))?
+        .span()
+}
+
+/**
+Return the chain of names leading back to the start name,
+if following the references (extended pipelines, used templates) loops.
+*/
+pub(crate) fn find_loop<'a>(
+    start: &'a str,
+    references: &dyn Fn(&str) -> Vec<&'a str>,
+) -> Option<Vec<&'a str>> {
+    fn visit<'a>(
+        name: &str,
+        start: &str,
+        references: &dyn Fn(&str) -> Vec<&'a str>,
+        chain: &mut Vec<&'a str>,
+    ) -> bool {
+        for reference in references(name) {
+            if reference == start {
+                chain.push(reference);
+                return true;
+            }
+            if chain.contains(&reference) {
+                continue;
+            }
+            chain.push(reference);
+            if visit(reference, start, references, chain) {
+                return true;
+            }
+            chain.pop();
+        }
+        false
+    }
+    let mut chain = vec![start];
+    visit(start, start, references, &mut chain).then_some(chain)
+}
+/**
+Return the templates used by the steps under a node.
+*/
+fn used_templates(node: &Node) -> Vec<&str> {
+    let mut res = vec![];
+    for (key, value) in node.entries() {
+        match (key.as_str(), value.as_str()) {
+            (Some("use"), Some(template)) => res.push(template),
+            _ => res.extend(used_templates(value)),
+        }
+    }
+    for item in node.items() {
+        res.extend(used_templates(item));
+    }
+    res
+}
+
+/**
+Whether the action is a git hook or a special flag.
+*/
+fn is_known_flag(action: &str) -> bool {
+    flags().contains(&action.to_case(Case::Kebab))
+}
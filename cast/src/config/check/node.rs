@@ -0,0 +1,333 @@
+// Structs
+use serde_json::Value as Json;
+use toml::Spanned;
+// Serde
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+// Yaml
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, TScalarStyle};
+// Error Handling
+use miette::SourceSpan;
+use std::fmt;
+use std::ops::Range;
+
+/**
+A raw config file value, before its conversion into the config types,
+with the byte range of its text in the file source.
+
+The range is only known for the formats whose parser provides it (toml, yaml, json),
+not for the values returned by a script evaluation.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub value: Value,
+    pub span: Option<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Scalar(Json),
+    Seq(Vec<Node>),
+    Map(Vec<(Node, Node)>),
+}
+
+impl Node {
+    /// Return the value of a map key.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+    /// Return the key and value pairs of a map, none for other values.
+    pub fn entries(&self) -> &[(Node, Node)] {
+        match &self.value {
+            Value::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+    /// Return the items of a sequence, none for other values.
+    pub fn items(&self) -> &[Node] {
+        match &self.value {
+            Value::Seq(items) => items,
+            _ => &[],
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(Json::String(e)) => Some(e),
+            _ => None,
+        }
+    }
+    pub fn span(&self) -> Option<SourceSpan> {
+        self.span
+            .as_ref()
+            .map(|e| SourceSpan::new(e.start.into(), e.len().into()))
+    }
+    /**
+    Parse a toml source, with the spans of the values and keys.
+    */
+    pub fn from_toml(src: &str) -> Option<Node> {
+        let root = toml::from_str::<Spanned<TomlValue>>(src).ok()?;
+        let mut node = Node::from(root);
+        node.unquote(src);
+        Some(node)
+    }
+    /**
+    Parse a yaml source (or a json one, as yaml is a superset of json),
+    with the spans of the scalars.
+    */
+    pub fn from_yaml(src: &str) -> Option<Node> {
+        let mut receiver = YamlReceiver {
+            src,
+            offsets: src
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([src.len()])
+                .collect(),
+            stack: vec![],
+            root: None,
+        };
+        Parser::new(src.chars()).load(&mut receiver, false).ok()?;
+        let mut node = receiver.root?;
+        node.unquote(src);
+        Some(node)
+    }
+    /**
+    Convert a json value, without spans.
+    */
+    pub fn from_json(value: Json) -> Node {
+        let value = match value {
+            Json::Array(items) => Value::Seq(items.into_iter().map(Node::from_json).collect()),
+            Json::Object(entries) => Value::Map(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (Node::from_json(Json::String(k)), Node::from_json(v)))
+                    .collect(),
+            ),
+            scalar => Value::Scalar(scalar),
+        };
+        Node { value, span: None }
+    }
+    /**
+    Narrow the spans of quoted strings to their content.
+    */
+    fn unquote(&mut self, src: &str) {
+        if let Some(span) = &mut self.span {
+            let text = src.get(span.clone()).unwrap_or_default();
+            for quote in ["\"", "'"] {
+                if text.len() > 1 && text.starts_with(quote) && text.ends_with(quote) {
+                    *span = span.start + 1..span.end - 1;
+                }
+            }
+        }
+        match &mut self.value {
+            Value::Seq(items) => items.iter_mut().for_each(|e| e.unquote(src)),
+            Value::Map(entries) => {
+                for (key, value) in entries {
+                    key.unquote(src);
+                    value.unquote(src);
+                }
+            }
+            Value::Scalar(_) => {}
+        }
+    }
+}
+
+/**
+A toml value deserialized with the spans of its children.
+*/
+enum TomlValue {
+    Scalar(Json),
+    Seq(Vec<Spanned<TomlValue>>),
+    Map(Vec<(Spanned<String>, Spanned<TomlValue>)>),
+}
+impl From<Spanned<TomlValue>> for Node {
+    fn from(spanned: Spanned<TomlValue>) -> Node {
+        let span = spanned.span();
+        let value = match spanned.into_inner() {
+            TomlValue::Scalar(e) => Value::Scalar(e),
+            TomlValue::Seq(items) => Value::Seq(items.into_iter().map(Node::from).collect()),
+            TomlValue::Map(entries) => Value::Map(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| {
+                        let key = Node {
+                            span: Some(k.span()),
+                            value: Value::Scalar(Json::String(k.into_inner())),
+                        };
+                        (key, Node::from(v))
+                    })
+                    .collect(),
+            ),
+        };
+        Node {
+            value,
+            span: Some(span),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for TomlValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TomlVisitor)
+    }
+}
+struct TomlVisitor;
+impl<'de> Visitor<'de> for TomlVisitor {
+    type Value = TomlValue;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a toml value")
+    }
+    fn visit_bool<E>(self, v: bool) -> Result<TomlValue, E> {
+        Ok(TomlValue::Scalar(v.into()))
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<TomlValue, E> {
+        Ok(TomlValue::Scalar(v.into()))
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<TomlValue, E> {
+        Ok(TomlValue::Scalar(v.into()))
+    }
+    fn visit_f64<E>(self, v: f64) -> Result<TomlValue, E> {
+        Ok(TomlValue::Scalar(v.into()))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<TomlValue, E> {
+        Ok(TomlValue::Scalar(v.into()))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TomlValue, A::Error> {
+        let mut items = vec![];
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(TomlValue::Seq(items))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<TomlValue, A::Error> {
+        let mut entries = vec![];
+        while let Some(key) = map.next_key()? {
+            entries.push((key, map.next_value()?));
+        }
+        Ok(TomlValue::Map(entries))
+    }
+}
+
+/**
+The collections being built from the yaml events.
+*/
+enum Frame {
+    Seq(usize, Vec<Node>),
+    Map(usize, Vec<(Node, Node)>, Option<Node>),
+}
+struct YamlReceiver<'a> {
+    src: &'a str,
+    /// The byte offset of every char, as the yaml markers count chars.
+    offsets: Vec<usize>,
+    stack: Vec<Frame>,
+    root: Option<Node>,
+}
+impl YamlReceiver<'_> {
+    fn offset(&self, mark: &Marker) -> usize {
+        self.offsets
+            .get(mark.index())
+            .copied()
+            .unwrap_or(self.src.len())
+    }
+    /**
+    Return the span of a scalar text from its start.
+    */
+    fn scalar_span(&self, start: usize, value: &str, style: &TScalarStyle) -> Range<usize> {
+        let rest = &self.src[start..];
+        let end = match style {
+            TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => {
+                let quote = rest.chars().next().unwrap_or_default();
+                let mut chars = rest.char_indices().skip(1);
+                let mut end = rest.len();
+                while let Some((i, c)) = chars.next() {
+                    if c == '\\' && quote == '"' {
+                        chars.next();
+                    } else if c == quote {
+                        if quote == '\'' && rest[i + 1..].starts_with('\'') {
+                            chars.next();
+                        } else {
+                            end = i + 1;
+                            break;
+                        }
+                    }
+                }
+                end
+            }
+            _ if rest.starts_with(value) => value.len(),
+            // Multiline scalars: only the first line
+            _ => rest.find('\n').unwrap_or(rest.len()),
+        };
+        start..start + end
+    }
+    fn push(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            Some(Frame::Seq(_, items)) => items.push(node),
+            Some(Frame::Map(_, entries, key)) => match key.take() {
+                Some(key) => entries.push((key, node)),
+                None => *key = Some(node),
+            },
+            None => self.root = Some(node),
+        }
+    }
+}
+impl MarkedEventReceiver for YamlReceiver<'_> {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let offset = self.offset(&mark);
+        match event {
+            Event::Scalar(value, style, _, _) => {
+                let span = self.scalar_span(offset, &value, &style);
+                let scalar = match style {
+                    TScalarStyle::Plain => resolve_plain(&value),
+                    _ => Json::String(value),
+                };
+                self.push(Node {
+                    value: Value::Scalar(scalar),
+                    span: Some(span),
+                });
+            }
+            Event::Alias(_) => self.push(Node {
+                value: Value::Scalar(Json::Null),
+                span: None,
+            }),
+            Event::SequenceStart(_) => self.stack.push(Frame::Seq(offset, vec![])),
+            Event::MappingStart(_) => self.stack.push(Frame::Map(offset, vec![], None)),
+            Event::SequenceEnd | Event::MappingEnd => {
+                let (start, value) = match self.stack.pop() {
+                    Some(Frame::Seq(start, items)) => (start, Value::Seq(items)),
+                    Some(Frame::Map(start, entries, _)) => (start, Value::Map(entries)),
+                    None => return,
+                };
+                self.push(Node {
+                    value,
+                    span: Some(start..offset.max(start)),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/**
+Resolve a plain yaml scalar with the yaml core schema.
+*/
+fn resolve_plain(text: &str) -> Json {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Json::Null,
+        "true" | "True" | "TRUE" => Json::Bool(true),
+        "false" | "False" | "FALSE" => Json::Bool(false),
+        _ => {
+            if let Ok(number) = text.parse::<i64>() {
+                return number.into();
+            }
+            match text.parse::<f64>() {
+                Ok(number) if text.contains(|c: char| c.is_ascii_digit()) => {
+                    serde_json::Number::from_f64(number)
+                        .map(Json::Number)
+                        .unwrap_or_else(|| text.into())
+                }
+                _ => text.into(),
+            }
+        }
+    }
+}
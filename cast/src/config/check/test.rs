@@ -0,0 +1,299 @@
+#[cfg(test)]
+mod check {
+    use crate::config::check::{validate, Document};
+    use crate::error::CheckReport;
+    use miette::Severity;
+
+    fn check(src: &str) -> Result<(), CheckReport> {
+        validate(&[Document::new("pipelight.yaml", src, None, false).unwrap()])
+    }
+
+    #[test]
+    fn report_every_problem() {
+        let src = r#"
+pipelines:
+  - name: deploy
+    triggers:
+      - branches: ["feature/[oops"]
+        actions: ["pre-psuh"]
+        paths_ignore: ["docs/[oops"]
+    steps:
+      - name: build
+        commands: []
+        options:
+          mode: stopp
+  - name: deploy
+    steps:
+      - name: test
+        commands: ["cargo test"]
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 5);
+        assert_eq!(report.warnings, 1);
+
+        let problem = |text: &str| {
+            report
+                .problems
+                .iter()
+                .find(|e| e.message.contains(text))
+                .unwrap()
+        };
+        // Spans point at the faulty values
+        let span = problem("Duplicate").at.unwrap();
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "deploy");
+        assert!(span.offset() > src.find("deploy").unwrap());
+        let span = problem("pre-psuh").at.unwrap();
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "pre-psuh");
+        assert_eq!(problem("no commands").severity, Severity::Warning);
+        assert_eq!(problem("stopp").severity, Severity::Error);
+        assert!(problem("feature/[oops").message.contains("glob"));
+        assert!(problem("docs/[oops").message.contains("glob"));
+    }
+    #[test]
+    fn valid_config() {
+        let src = r#"
+pipelines:
+  - name: test
+    triggers:
+      - branches: ["main", "feature/*"]
+        actions: ["pre-push", "manual"]
+        paths: ["src/**"]
+        paths_ignore: ["docs/**", "*.md"]
+    steps:
+      - name: test
+        commands: ["cargo test"]
+        options:
+          mode: jump_next
+"#;
+        assert!(check(src).is_ok());
+    }
+    #[test]
+    fn unknown_templates() {
+        let src = r#"
+templates:
+  - name: setup
+    commands: ["rustup default stable"]
+pipelines:
+  - name: test
+    extends: base
+    steps:
+      - use: setup
+      - use: notify
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 2);
+        let messages: Vec<&str> = report.problems.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"Unknown extended pipeline \"base\""));
+        assert!(messages.contains(&"Unknown step template \"notify\""));
+    }
+    #[test]
+    fn reference_loops() {
+        let src = r#"
+templates:
+  - name: setup
+    commands: ["rustup default stable"]
+    on_failure:
+      - use: notify
+  - name: notify
+    commands: ["notify-send failure"]
+    on_failure:
+      - use: setup
+pipelines:
+  - name: a
+    extends: b
+    steps:
+      - use: setup
+  - name: b
+    extends: a
+    steps: []
+"#;
+        let report = check(src).unwrap_err();
+        let messages: Vec<&str> = report.problems.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"The pipeline inheritance loops: a -> b -> a"));
+        assert!(messages.contains(&"The pipeline inheritance loops: b -> a -> b"));
+        assert!(
+            messages.contains(&"The step template \"setup\" uses itself: setup -> notify -> setup")
+        );
+    }
+    #[test]
+    fn invalid_watch_debounce() {
+        let src = "options:\n  watch_debounce: soon\npipelines: []\n";
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 1);
+        let span = report.problems[0].at.unwrap();
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "soon");
+        assert!(check("options:\n  watch_debounce: 500ms\npipelines: []\n").is_ok());
+    }
+    #[test]
+    fn invalid_params() {
+        let src = r#"
+pipelines:
+  - name: deploy
+    params:
+      - name: replicas
+        type: integer
+      - name: env
+        default: dev
+        values: [staging, production]
+    steps: []
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 2);
+        let span = report.problems[0].at.unwrap();
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "integer");
+    }
+    #[test]
+    fn triggerable_required_params() {
+        let src = r#"
+pipelines:
+  - name: deploy
+    triggers:
+      - branches: [main]
+        actions: [pre-push]
+    params:
+      - name: env
+      - name: replicas
+        default: 1
+    steps: []
+  - name: release
+    triggers:
+      - actions: [manual]
+    params:
+      - name: version
+    steps: []
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 0);
+        assert_eq!(report.warnings, 1);
+        let span = report.problems[0].at.unwrap();
+        assert_eq!(span.offset(), src.find("env").unwrap());
+    }
+    #[test]
+    fn invalid_schedules() {
+        let src = r#"
+pipelines:
+  - name: audit
+    triggers:
+      - schedule: "0 25 * * *"
+        catch_up: sometimes
+      - actions: ["schedule"]
+    steps: []
+  - name: cleanup
+    triggers:
+      - schedule: "@hourly"
+        catch_up: latest
+    steps: []
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 3);
+        let messages: Vec<&str> = report.problems.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.iter().any(|e| e.contains("0 25 * * *")));
+        assert!(messages.iter().any(|e| e.contains("sometimes")));
+        assert!(messages.iter().any(|e| e.contains("needs a \"schedule\"")));
+    }
+    #[test]
+    fn invalid_afters() {
+        let src = r#"
+pipelines:
+  - name: build
+    steps: []
+  - name: deploy
+    triggers:
+      - branches: [main]
+        after:
+          pipeline: build
+          status: [succeeded]
+    steps: []
+  - name: notify
+    triggers:
+      - after:
+          pipeline: biuld
+          status: [failed, crashed]
+      - actions: [after]
+    steps: []
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.warnings, 1);
+        let messages: Vec<&str> = report.problems.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"Unknown upstream pipeline \"biuld\""));
+        assert!(messages.contains(&"Unknown upstream status \"crashed\""));
+        assert!(messages.iter().any(|e| e.contains("needs an \"after\"")));
+    }
+    #[test]
+    fn empty_matrix_axis() {
+        let src = r#"
+pipelines:
+  - name: ci
+    matrix:
+      os: []
+    steps:
+      - name: test
+        commands: ["cargo test"]
+        matrix:
+          toolchain: ["stable"]
+"#;
+        let report = check(src).unwrap_err();
+        assert_eq!(report.errors, 1);
+        assert!(report.problems[0].message.contains("\"os\" has no values"));
+    }
+    #[test]
+    fn spans_of_repeated_values() {
+        let src = r#"
+pipelines:
+  - name: failed
+    steps: []
+  - name: notify
+    triggers:
+      - after:
+          pipeline: failed
+          status: ["failed", "crashed"]
+    steps:
+      - name: crashed
+        commands: ["echo"]
+"#;
+        let report = check(src).unwrap_err();
+        let span = report.problems[0].at.unwrap();
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "crashed");
+        assert_eq!(span.offset(), src.find("\"crashed\"").unwrap() + 1);
+    }
+    #[test]
+    fn problems_before_conversion() {
+        // The retries can't be converted, the other problems are still reported
+        let src = r#"
+[[pipelines]]
+name = "test"
+[[pipelines.steps]]
+name = "build"
+commands = ["cargo build"]
+options = { mode = "stopp", retries = "twice" }
+[[pipelines.triggers]]
+actions = ["pre-psuh"]
+"#;
+        let document = Document::new("pipelight.toml", src, None, false).unwrap();
+        let report = validate(&[document]).unwrap_err();
+        assert_eq!(report.errors, 2);
+        for (problem, value) in report.problems.iter().zip(["pre-psuh", "stopp"]) {
+            let span = problem.at.unwrap();
+            assert_eq!(&src[span.offset()..span.offset() + span.len()], value);
+        }
+    }
+    #[test]
+    fn duplicates_across_files() {
+        let config = "pipelines:\n  - name: deploy\n    steps: []\n";
+        let pipeline = "name = \"deploy\"\nsteps = []\n";
+        let documents = [
+            Document::new("pipelight.yaml", config, None, false).unwrap(),
+            Document::new(".pipelight/pipelines/deploy.toml", pipeline, None, true).unwrap(),
+        ];
+        let report = validate(&documents).unwrap_err();
+        assert_eq!(report.errors, 1);
+        let problem = &report.problems[0];
+        assert!(problem
+            .message
+            .contains("already defined in pipelight.yaml"));
+        let span = problem.at.unwrap();
+        assert_eq!(span.offset(), pipeline.find("deploy").unwrap());
+        assert_eq!(problem.src, pipeline);
+    }
+}
@@ -0,0 +1,122 @@
+/**
+Step commands can embed `${{ variable }}` placeholders
+that are replaced with the run context right before execution.
+
+Values are never pasted into the command text:
+a placeholder is replaced with a reference to an environment variable
+holding the value (`${PIPELIGHT_VAR_BRANCH}`), expanded by the shell,
+so that values can't inject shell code.
+
+```text
+docker build -t registry/app:${{ tag }} .
+echo "${{ pipeline.name }} run ${{ pipeline.uuid }} on ${{ branch }}"
+echo ${{ env.HOME }}
+```
+
+Variables are:
+- `pipeline.name`, `pipeline.uuid`,
+- `branch`, `tag`, `commit`, `action` from the pipeline trigger,
+- `params.name` from the pipeline parameters,
+- `env.NAME` from the config env maps, the matrix axes and the pipelight environment.
+*/
+// Tests
+mod test;
+
+// Traits
+use convert_case::{Case, Casing};
+
+/**
+Variables that are always available.
+*/
+pub const VARIABLES: [&str; 6] = [
+    "pipeline.name",
+    "pipeline.uuid",
+    "branch",
+    "tag",
+    "commit",
+    "action",
+];
+
+/**
+A `${{ ... }}` occurrence in a string.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Placeholder {
+    // Byte offsets of the whole `${{ ... }}` in the string
+    pub start: usize,
+    pub end: usize,
+    // The trimmed variable name, like "env.HOME"
+    pub variable: String,
+}
+
+impl Placeholder {
+    /**
+    Returns the environment variable name if the variable is `env.NAME`.
+    */
+    pub fn env_name(&self) -> Option<&str> {
+        self.variable.strip_prefix("env.")
+    }
+}
+
+/**
+Returns every placeholder of a string.
+Unclosed `${{` are ignored.
+*/
+pub fn placeholders(string: &str) -> Vec<Placeholder> {
+    let mut res = vec![];
+    let mut offset = 0;
+    while let Some(start) = string[offset..].find("${{") {
+        let start = offset + start;
+        match string[start..].find("}}") {
+            Some(end) => {
+                let end = start + end + 2;
+                res.push(Placeholder {
+                    start,
+                    end,
+                    variable: string[start + 3..end - 2].trim().to_owned(),
+                });
+                offset = end;
+            }
+            None => break,
+        }
+    }
+    res
+}
+
+/**
+Replace every placeholder with the value returned by the resolver.
+*/
+pub fn interpolate<F>(string: &str, mut resolve: F) -> String
+where
+    F: FnMut(&Placeholder) -> String,
+{
+    let mut res = String::new();
+    let mut offset = 0;
+    for placeholder in placeholders(string) {
+        res.push_str(&string[offset..placeholder.start]);
+        res.push_str(&resolve(&placeholder));
+        offset = placeholder.end;
+    }
+    res.push_str(&string[offset..]);
+    res
+}
+
+/**
+Returns the environment variable that holds the value of an interpolated variable,
+"pipeline.name" is held by the "PIPELIGHT_VAR_PIPELINE_NAME" variable.
+*/
+pub fn interpolation_env_name(variable: &str) -> String {
+    let name: String = variable
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("PIPELIGHT_VAR_{}", name.to_uppercase())
+}
+
+/**
+Returns the environment variable name of a pipeline parameter,
+the "version" parameter is available as the "PARAM_VERSION" variable.
+*/
+pub fn param_env_name(name: &str) -> String {
+    format!("PARAM_{}", name.to_case(Case::UpperSnake))
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod interpolation {
+    use crate::interpolation::{interpolate, placeholders};
+    use crate::Config;
+
+    #[test]
+    fn find_placeholders() {
+        let res = placeholders("docker build -t app:${{ tag }} ${{env.HOME}} ${{ branch");
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].variable, "tag");
+        assert_eq!(res[1].env_name(), Some("HOME"));
+    }
+    #[test]
+    fn replace_placeholders() {
+        let res = interpolate("echo ${{ pipeline.name }}-${{ tag }}!", |e| {
+            e.variable.to_uppercase()
+        });
+        assert_eq!(res, "echo PIPELINE.NAME-TAG!");
+    }
+    #[test]
+    fn known_params() {
+        let src = r#"
+pipelines:
+  - name: deploy
+    params:
+      - name: version
+    steps:
+      - name: release
+        commands: ["echo ${{ params.version }} ${{ env.PARAM_VERSION }}"]
+"#;
+        let config = serde_yaml::from_str::<Config>(src).unwrap();
+        assert!(config.check_interpolations(src).is_ok());
+
+        let src = src.replace("params.version", "params.tag");
+        let config = serde_yaml::from_str::<Config>(&src).unwrap();
+        assert!(config.check_interpolations(&src).is_err());
+    }
+}
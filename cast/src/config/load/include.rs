@@ -0,0 +1,176 @@
+// Structs
+use crate::config::check::pipeline_name_span;
+use crate::{Config, Pipeline};
+use serde::Deserialize;
+// Filesystem
+use glob::glob;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+// Error Handling
+use crate::error::{DuplicatePipelineError, IncludeCycleError, IncludeError};
+use miette::{Error, IntoDiagnostic, Result};
+
+/**
+The state shared by the recursive loading of included files.
+*/
+#[derive(Debug, Default)]
+pub(super) struct Includes {
+    // The chain of files being loaded, to detect include cycles
+    stack: Vec<PathBuf>,
+    // Files already merged, when included more than once
+    loaded: HashSet<PathBuf>,
+    // The file defining every pipeline name
+    origins: HashMap<String, PathBuf>,
+}
+
+impl Includes {
+    /**
+    Record the file defining every pipeline.
+    Fails if a pipeline name is already defined in another file.
+    */
+    pub(super) fn register(
+        &mut self,
+        pipelines: &[Pipeline],
+        path: &Path,
+        src: &str,
+    ) -> Result<()> {
+        for pipeline in pipelines {
+            match self.origins.get(&pipeline.name) {
+                Some(other) if other != path => {
+                    let err = DuplicatePipelineError::new(
+                        &pipeline.name,
+                        &path.display().to_string(),
+                        &other.display().to_string(),
+                        pipeline_name_span(path, src, &pipeline.name),
+                        src,
+                    );
+                    return Err(err.into());
+                }
+                _ => {
+                    self.origins.insert(pipeline.name.clone(), path.to_owned());
+                }
+            }
+        }
+        Ok(())
+    }
+    /**
+    Returns the file defining the pipeline.
+    */
+    pub(super) fn origin(&self, name: &str) -> Option<&Path> {
+        self.origins.get(name).map(|e| e.as_path())
+    }
+}
+
+impl Config {
+    /**
+    Load a config file and merge the config files it includes, recursively.
+    Included files are resolved relative to the including file,
+    and a pipeline name can't be defined in two different files.
+    */
+    pub(super) fn load_file(
+        file_path: &str,
+        args: Option<Vec<String>>,
+        state: &mut Includes,
+    ) -> Result<Config> {
+        let path = fs::canonicalize(file_path).into_diagnostic()?;
+        if state.stack.contains(&path) {
+            let cycle = state
+                .stack
+                .iter()
+                .skip_while(|e| **e != path)
+                .chain([&path])
+                .map(|e| e.display().to_string())
+                .collect::<Vec<String>>()
+                .join(" -> ");
+            return Err(IncludeCycleError { cycle }.into());
+        }
+        if !state.loaded.insert(path.clone()) {
+            return Ok(Config::default());
+        }
+
+        let mut config = Config::parse(file_path, args.clone())?;
+        let src = fs::read_to_string(&path).into_diagnostic()?;
+        state.register(config.pipelines.as_deref().unwrap_or_default(), &path, &src)?;
+
+        if let Some(include) = &config.include {
+            state.stack.push(path.clone());
+            for file in resolve_includes(&path, include)? {
+                let file = file.display().to_string();
+                let included = Config::load_file(&file, args.clone(), state)
+                    .map_err(|e| IncludeError::new(&file, e))?;
+                config.merge(included);
+            }
+            state.stack.pop();
+        }
+        Ok(config)
+    }
+    /**
+    Append the other config pipelines.
+    The other config options are only used if this config has none.
+    */
+    pub(super) fn merge(&mut self, other: Config) {
+        if let Some(pipelines) = other.pipelines {
+            self.pipelines
+                .get_or_insert_with(Vec::new)
+                .extend(pipelines);
+        }
+        if let Some(templates) = other.templates {
+            self.templates
+                .get_or_insert_with(Vec::new)
+                .extend(templates);
+        }
+        if self.options.is_none() {
+            self.options = other.options;
+        }
+    }
+}
+
+/**
+Return the files matching the include paths and globs,
+relative to the including file directory.
+*/
+pub(crate) fn resolve_includes(file_path: &Path, include: &[String]) -> Result<Vec<PathBuf>> {
+    let dir = file_path.parent().unwrap();
+    let mut files = vec![];
+    for entry in include {
+        let pattern = dir.join(entry);
+        let mut matches: Vec<PathBuf> = glob(pattern.to_str().unwrap())
+            .into_diagnostic()?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.is_file())
+            .collect();
+        if matches.is_empty() && !entry.contains(['*', '?', '[']) {
+            let message = format!("The included file {} doesn't exist", pattern.display());
+            return Err(Error::msg(message));
+        }
+        matches.sort();
+        files.extend(matches);
+    }
+    Ok(files)
+}
+
+/**
+Only the include key of a config file.
+*/
+#[derive(Debug, Default, Deserialize)]
+struct IncludeKey {
+    include: Option<Vec<String>>,
+}
+
+/**
+Return the include list of a markup config file (toml, yaml, json)
+without loading the whole config.
+*/
+pub(crate) fn read_includes(file_path: &Path) -> Option<Vec<String>> {
+    let src = fs::read_to_string(file_path).ok()?;
+    let extension = file_path.extension()?.to_str()?;
+    let key: IncludeKey = match extension {
+        "toml" | "tml" => toml::from_str(&src).ok()?,
+        "yaml" | "yml" => serde_yaml::from_str(&src).ok()?,
+        "json" => serde_json::from_str(&src).ok()?,
+        "json5" => json5::from_str(&src).ok()?,
+        _ => return None,
+    };
+    key.include
+}
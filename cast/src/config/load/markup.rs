@@ -3,7 +3,7 @@ use crate::Config;
 // Filesystem - read file
 use std::fs;
 // Error Handling
-use crate::error::{TomlError, YamlError};
+use crate::error::{JsonError, TomlError, YamlError};
 use miette::{IntoDiagnostic, Result};
 
 impl Config {
@@ -35,4 +35,32 @@ impl Config {
             }
         }
     }
+    /**
+    Returns a Config struct from a provided json file path.
+    */
+    pub fn json(file_path: &str) -> Result<Config> {
+        let json = fs::read_to_string(file_path).into_diagnostic()?;
+        let res = serde_json::from_str::<Config>(&json);
+        match res {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                let err = JsonError::new(e, &json);
+                Err(err.into())
+            }
+        }
+    }
+    /**
+    Returns a Config struct from a provided json5 file path.
+    */
+    pub fn json5(file_path: &str) -> Result<Config> {
+        let json = fs::read_to_string(file_path).into_diagnostic()?;
+        let res = json5::from_str::<Config>(&json);
+        match res {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                let err = JsonError::from_json5(e, &json);
+                Err(err.into())
+            }
+        }
+    }
 }
@@ -1,16 +1,21 @@
 // Tests
 mod test;
 
+pub(crate) mod include;
 mod markup;
+pub(crate) mod pipelines;
 mod rules;
 mod typescript;
 // Structs
 use crate::Config;
+use include::Includes;
 // Filesystem
+use std::fs;
 use std::path::Path;
 use utils::files::FileType;
+use utils::teleport::PIPELINES_DIR;
 // Error Handling
-use miette::Result;
+use miette::{IntoDiagnostic, Result};
 
 impl Config {
     /**
@@ -23,8 +28,36 @@ impl Config {
 
     Languages coming next after v1.0.0:
       - Rust, Hcl, Kcl, Python...
+
+    Files listed in the config `include` key are loaded the same way and merged.
+    So are the single pipeline files of the pipelines directory next to the config file.
+    The file_path can also be the pipelines directory itself, when there is no config file.
     */
     pub fn load(file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
+        let mut state = Includes::default();
+        let path = Path::new(file_path);
+        if path.is_dir() {
+            let mut config = Config::default();
+            config.load_pipelines_dir(path, args, &mut state)?;
+            config.check_references()?;
+            config.check_sources_interpolations(&state, None)?;
+            return Ok(config);
+        }
+        let mut config = Config::load_file(file_path, args.clone(), &mut state)?;
+        let dir = path.parent().unwrap().join(PIPELINES_DIR);
+        if dir.is_dir() {
+            config.load_pipelines_dir(&dir, args, &mut state)?;
+        }
+        // References and inherited variables can cross files, so they are checked once merged
+        config.check_references()?;
+        let main = fs::canonicalize(path).into_diagnostic()?;
+        config.check_sources_interpolations(&state, Some(&main))?;
+        Ok(config)
+    }
+    /**
+    Load a single config file, without its includes.
+    */
+    fn parse(file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
         let extension = &Path::new(file_path)
             .extension()
             .unwrap()
@@ -34,10 +67,14 @@ impl Config {
 
         let file_type = FileType::from(extension);
         let mut config = match file_type {
-            FileType::TypeScript | FileType::JavaScript => Config::ts(file_path, args)?,
+            FileType::TypeScript => Config::ts(file_path, args)?,
+            FileType::JavaScript => Config::js(file_path, args)?,
             FileType::Toml | FileType::Tml => Config::tml(file_path)?,
             FileType::Yaml | FileType::Yml => Config::yml(file_path)?,
+            FileType::Json => Config::json(file_path)?,
+            FileType::Json5 => Config::json5(file_path)?,
         };
-        config.strict_check()
+        let config = config.strict_check()?;
+        Ok(config)
     }
 }
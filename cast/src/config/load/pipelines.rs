@@ -0,0 +1,92 @@
+// Structs
+use super::include::Includes;
+use super::typescript::from_json;
+use crate::{Config, Pipeline};
+use utils::files::FileType;
+// Filesystem
+use std::fs;
+use std::path::{Path, PathBuf};
+// Traits
+use strum::IntoEnumIterator;
+// Error Handling
+use crate::error::{IncludeError, JsonError, TomlError, YamlError};
+use miette::{IntoDiagnostic, Result};
+
+impl Config {
+    /**
+    Merge every single pipeline file of the pipelines directory.
+    Pipeline names must be unique across the config file and the directory files.
+    */
+    pub(super) fn load_pipelines_dir(
+        &mut self,
+        dir: &Path,
+        args: Option<Vec<String>>,
+        state: &mut Includes,
+    ) -> Result<()> {
+        for path in pipeline_files(dir)? {
+            let file = path.display().to_string();
+            let pipeline =
+                Pipeline::load(&file, args.clone()).map_err(|e| IncludeError::new(&file, e))?;
+            let src = fs::read_to_string(&path).into_diagnostic()?;
+            state.register(std::slice::from_ref(&pipeline), &path, &src)?;
+            self.pipelines.get_or_insert_with(Vec::new).push(pipeline);
+        }
+        Ok(())
+    }
+}
+
+impl Pipeline {
+    /**
+    Return a Pipeline struct from a single pipeline file,
+    according to the file extension like Config::load.
+    */
+    pub fn load(file_path: &str, args: Option<Vec<String>>) -> Result<Pipeline> {
+        let extension = &Path::new(file_path)
+            .extension()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let src = fs::read_to_string(file_path).into_diagnostic()?;
+        let pipeline: Pipeline = match FileType::from(extension) {
+            FileType::TypeScript => from_json(&Config::ts_json(file_path, args)?)?,
+            FileType::JavaScript => from_json(&Config::js_json(file_path, args)?)?,
+            FileType::Toml | FileType::Tml => {
+                toml::from_str(&src).map_err(|e| TomlError::new(e, &src))?
+            }
+            FileType::Yaml | FileType::Yml => {
+                serde_yaml::from_str(&src).map_err(|e| YamlError::new(e, &src))?
+            }
+            FileType::Json => serde_json::from_str(&src).map_err(|e| JsonError::new(e, &src))?,
+            FileType::Json5 => json5::from_str(&src).map_err(|e| JsonError::from_json5(e, &src))?,
+        };
+        // Reuse the config checks
+        let mut config = Config {
+            pipelines: Some(vec![pipeline]),
+            ..Config::default()
+        };
+        let config = config.strict_check()?;
+        Ok(config.pipelines.unwrap().remove(0))
+    }
+}
+
+/**
+Return the files of the pipelines directory with a supported extension,
+sorted by name.
+*/
+pub(crate) fn pipeline_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let extensions: Vec<String> = FileType::iter().map(|e| String::from(&e)).collect();
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_diagnostic()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|e| e.is_file())
+        .filter(|e| {
+            e.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e.to_owned()))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
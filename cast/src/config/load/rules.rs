@@ -1,8 +1,18 @@
-use crate::Config;
+use super::include::Includes;
+use crate::config::check::find_loop;
+use crate::interpolation::{param_env_name, placeholders, VARIABLES};
+use crate::{Config, Fallback, Pipeline, Step, StepOrParallel, UseStep};
+use convert_case::{Case, Casing};
+use std::collections::{HashMap, HashSet};
+use std::env;
+// Filesystem
+use std::fs;
+use std::path::Path;
 
 // Error Handling
+use crate::error::{IncludeError, InterpolationError};
 use log::warn;
-use miette::Result;
+use miette::{Error, Result};
 
 impl Config {
     /**
@@ -21,4 +31,341 @@ impl Config {
         }
         Ok(self.to_owned())
     }
+    /**
+    Raises an error.
+
+    Enforces that pipelines and step templates can be resolved:
+    - extended pipelines exist and the inheritance doesn't loop,
+    - used step templates exist and don't use themselves in their fallbacks.
+    */
+    pub fn check_references(&self) -> Result<()> {
+        let pipelines = self.pipelines.as_deref().unwrap_or_default();
+        let templates = self.templates.as_deref().unwrap_or_default();
+        let extends = |name: &str| {
+            pipelines
+                .iter()
+                .filter(|e| e.name == name)
+                .filter_map(|e| e.extends.as_deref())
+                .collect()
+        };
+        let uses = |name: &str| {
+            let mut res = vec![];
+            for template in templates.iter().filter(|e| e.name == name) {
+                if let Some(fallback) = &template.fallback {
+                    let mut used = vec![];
+                    fallback_uses(fallback, &mut used);
+                    res.extend(used.iter().map(|e| e.template.as_str()));
+                }
+            }
+            res
+        };
+        for pipeline in pipelines {
+            if let Some(parent) = &pipeline.extends {
+                if !pipelines.iter().any(|e| &e.name == parent) {
+                    let message = format!(
+                        "The pipeline \"{}\" extends the unknown pipeline \"{}\"",
+                        pipeline.name, parent
+                    );
+                    return Err(Error::msg(message));
+                }
+                if let Some(chain) = find_loop(&pipeline.name, &extends) {
+                    let message = format!("The pipeline inheritance loops: {}", chain.join(" -> "));
+                    return Err(Error::msg(message));
+                }
+            }
+            let mut used = vec![];
+            used_steps(&pipeline.steps, &mut used);
+            if let Some(fallback) = &pipeline.fallback {
+                fallback_uses(fallback, &mut used);
+            }
+            for template in used.iter().map(|e| e.template.as_str()) {
+                if !templates.iter().any(|e| e.name == template) {
+                    let message = format!(
+                        "The pipeline \"{}\" uses the unknown step template \"{}\"",
+                        pipeline.name, template
+                    );
+                    return Err(Error::msg(message));
+                }
+                if let Some(chain) = find_loop(template, &uses) {
+                    let message = format!(
+                        "The step template \"{}\" uses itself: {}",
+                        template,
+                        chain.join(" -> ")
+                    );
+                    return Err(Error::msg(message));
+                }
+            }
+        }
+        Ok(())
+    }
+    /**
+    Raises an error with a span on the config file source.
+
+    Enforces that every `${{ variable }}` in step commands is known:
+    - a context variable (pipeline.name, branch, tag...),
+    - a declared parameter (params.name), also available as the PARAM_NAME variable,
+    - an environment variable declared in the pipeline env maps or matrices,
+      in the env maps of the pipelines it extends,
+      or set in the pipelight environment.
+    */
+    pub fn check_interpolations(&self, src: &str) -> Result<()> {
+        // Commands are located in the source in order of declaration
+        let mut cursor = 0;
+        for pipeline in self.pipelines.iter().flatten() {
+            self.check_pipeline_interpolations(pipeline, src, &mut cursor)?;
+        }
+        Ok(())
+    }
+    /**
+    Check the interpolations of the merged config,
+    each pipeline against the source of the file that defines it.
+    Errors from other files than the main config file name the file.
+    */
+    pub(super) fn check_sources_interpolations(
+        &self,
+        state: &Includes,
+        main: Option<&Path>,
+    ) -> Result<()> {
+        // The source and cursor of every file
+        let mut sources: HashMap<&Path, (String, usize)> = HashMap::new();
+        for pipeline in self.pipelines.iter().flatten() {
+            let path = match state.origin(&pipeline.name) {
+                Some(path) => path,
+                None => continue,
+            };
+            let (src, cursor) = sources
+                .entry(path)
+                .or_insert_with(|| (fs::read_to_string(path).unwrap_or_default(), 0));
+            let res = self.check_pipeline_interpolations(pipeline, src, cursor);
+            if Some(path) != main {
+                res.map_err(|e| IncludeError::new(&path.display().to_string(), e))?;
+            } else {
+                res?;
+            }
+        }
+        Ok(())
+    }
+    /**
+    Check the interpolations of a pipeline commands,
+    located in the source from the cursor on.
+    */
+    fn check_pipeline_interpolations(
+        &self,
+        pipeline: &Pipeline,
+        src: &str,
+        cursor: &mut usize,
+    ) -> Result<()> {
+        let templates = self.templates.as_deref().unwrap_or_default();
+        let mut env_names = pipeline.env_names(templates);
+        let mut param_names = pipeline.param_names();
+        for parent in self.ancestors(pipeline) {
+            if let Some(env) = &parent.env {
+                env_names.extend(env.keys().cloned());
+            }
+            if pipeline.params.is_none() {
+                param_names.extend(parent.param_names());
+            }
+        }
+        env_names.extend(param_names.iter().map(|e| param_env_name(e)));
+        let mut steps = vec![];
+        collect_steps(&pipeline.steps, templates, &mut steps);
+        if let Some(fallback) = &pipeline.fallback {
+            collect_fallback_steps(fallback, templates, &mut steps);
+        }
+        for step in steps {
+            for command in &step.commands {
+                let offset = src[*cursor..]
+                    .find(command.as_str())
+                    .map(|e| e + *cursor)
+                    .or_else(|| src.find(command.as_str()));
+                if let Some(offset) = offset {
+                    *cursor = offset + command.len();
+                }
+                for placeholder in placeholders(command) {
+                    let known = match placeholder.env_name() {
+                        Some(name) => env_names.contains(name) || env::var_os(name).is_some(),
+                        None => match placeholder.variable.strip_prefix("params.") {
+                            Some(name) => param_names.contains(name),
+                            None => VARIABLES.contains(&placeholder.variable.as_str()),
+                        },
+                    };
+                    if !known {
+                        let text = &command[placeholder.start..placeholder.end];
+                        let at = match offset {
+                            Some(offset) => Some(offset + placeholder.start),
+                            None => src.find(text),
+                        };
+                        let err = InterpolationError::new(
+                            &placeholder.variable,
+                            &step.name,
+                            at,
+                            text.len(),
+                            src,
+                        );
+                        return Err(err.into());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /**
+    Returns the pipelines the pipeline extends, from the closest one.
+    Stops on unknown pipelines and inheritance cycles.
+    */
+    fn ancestors(&self, pipeline: &Pipeline) -> Vec<&Pipeline> {
+        let mut res: Vec<&Pipeline> = vec![];
+        let mut extends = pipeline.extends.as_ref();
+        while let Some(name) = extends {
+            let parent = self.pipelines.iter().flatten().find(|e| &e.name == name);
+            match parent {
+                Some(parent) if parent.name != pipeline.name && !res.contains(&parent) => {
+                    res.push(parent);
+                    extends = parent.extends.as_ref();
+                }
+                _ => break,
+            }
+        }
+        res
+    }
+}
+
+impl Pipeline {
+    /**
+    Returns every parameter name declared in the pipeline.
+    */
+    fn param_names(&self) -> HashSet<String> {
+        self.params
+            .iter()
+            .flatten()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+    /**
+    Returns every environment variable name declared in the pipeline,
+    and in the step templates it uses.
+    */
+    fn env_names(&self, templates: &[Step]) -> HashSet<String> {
+        let mut names = HashSet::new();
+        if let Some(env) = &self.env {
+            names.extend(env.keys().cloned());
+        }
+        if let Some(matrix) = &self.matrix {
+            names.extend(matrix.keys().map(|e| e.to_case(Case::UpperSnake)));
+        }
+        let mut steps = vec![];
+        collect_steps(&self.steps, templates, &mut steps);
+        if let Some(fallback) = &self.fallback {
+            collect_fallback_steps(fallback, templates, &mut steps);
+        }
+        for step in steps {
+            if let Some(env) = &step.env {
+                names.extend(env.keys().cloned());
+            }
+            if let Some(matrix) = &step.matrix {
+                names.extend(matrix.keys().map(|e| e.to_case(Case::UpperSnake)));
+            }
+        }
+        for step_or_parallel in &self.steps {
+            if let StepOrParallel::Parallel(parallel) = step_or_parallel {
+                if let Some(env) = &parallel.env {
+                    names.extend(env.keys().cloned());
+                }
+            }
+        }
+        let mut used = vec![];
+        used_steps(&self.steps, &mut used);
+        if let Some(fallback) = &self.fallback {
+            fallback_uses(fallback, &mut used);
+        }
+        for step in used {
+            if let Some(with) = &step.with {
+                names.extend(with.keys().map(|e| e.to_case(Case::UpperSnake)));
+            }
+        }
+        names
+    }
+}
+
+/**
+Flatten steps, parallel steps, the step templates they use and their fallbacks.
+Every template is only collected once, so that templates using themselves don't loop.
+*/
+fn collect_steps<'a>(steps: &'a [StepOrParallel], templates: &'a [Step], res: &mut Vec<&'a Step>) {
+    for step_or_parallel in steps {
+        match step_or_parallel {
+            StepOrParallel::Step(step) => collect_step(step, templates, res),
+            StepOrParallel::Parallel(parallel) => {
+                for step in &parallel.parallel {
+                    collect_step(step, templates, res);
+                }
+                if let Some(fallback) = &parallel.fallback {
+                    collect_fallback_steps(fallback, templates, res);
+                }
+            }
+            StepOrParallel::Use(step) => {
+                let template = templates.iter().find(|e| e.name == step.template);
+                if let Some(template) = template {
+                    if !res.iter().any(|e| std::ptr::eq(*e, template)) {
+                        collect_step(template, templates, res);
+                    }
+                }
+            }
+        }
+    }
+}
+fn collect_step<'a>(step: &'a Step, templates: &'a [Step], res: &mut Vec<&'a Step>) {
+    res.push(step);
+    if let Some(fallback) = &step.fallback {
+        collect_fallback_steps(fallback, templates, res);
+    }
+}
+fn collect_fallback_steps<'a>(
+    fallback: &'a Fallback,
+    templates: &'a [Step],
+    res: &mut Vec<&'a Step>,
+) {
+    for steps in fallback_steps(fallback) {
+        collect_steps(steps, templates, res);
+    }
+}
+fn fallback_steps(fallback: &Fallback) -> impl Iterator<Item = &Vec<StepOrParallel>> {
+    [
+        &fallback.on_started,
+        &fallback.on_failure,
+        &fallback.on_success,
+        &fallback.on_abortion,
+        &fallback.on_timeout,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/**
+Return the template steps among steps, parallel steps and their fallbacks.
+*/
+fn used_steps<'a>(steps: &'a [StepOrParallel], res: &mut Vec<&'a UseStep>) {
+    for step_or_parallel in steps {
+        match step_or_parallel {
+            StepOrParallel::Step(step) => {
+                if let Some(fallback) = &step.fallback {
+                    fallback_uses(fallback, res);
+                }
+            }
+            StepOrParallel::Parallel(parallel) => {
+                for fallback in parallel.parallel.iter().filter_map(|e| e.fallback.as_ref()) {
+                    fallback_uses(fallback, res);
+                }
+                if let Some(fallback) = &parallel.fallback {
+                    fallback_uses(fallback, res);
+                }
+            }
+            StepOrParallel::Use(step) => res.push(step),
+        }
+    }
+}
+fn fallback_uses<'a>(fallback: &'a Fallback, res: &mut Vec<&'a UseStep>) {
+    for steps in fallback_steps(fallback) {
+        used_steps(steps, res);
+    }
 }
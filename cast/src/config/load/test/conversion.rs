@@ -14,6 +14,11 @@ mod conversion {
             let trigger = Trigger::TriggerBranch(TriggerBranch {
                 branches: Some(vec!["master".to_owned()]),
                 actions: Some(vec!["pre-push".to_owned()]),
+                paths: None,
+                paths_ignore: None,
+                schedule: None,
+                catch_up: None,
+                after: None,
             });
             let res = serde_json::from_str::<Trigger>(&json).unwrap();
             assert_eq!(trigger == res, true);
@@ -28,6 +33,11 @@ mod conversion {
             let trigger = Trigger::TriggerBranch(TriggerBranch {
                 actions: Some(vec!["pre-push".to_owned()]),
                 branches: None,
+                paths: None,
+                paths_ignore: None,
+                schedule: None,
+                catch_up: None,
+                after: None,
             });
             let res = serde_json::from_str::<Trigger>(&json).unwrap();
             assert_eq!(trigger == res, true);
@@ -42,11 +52,37 @@ mod conversion {
             let trigger = Trigger::TriggerTag(TriggerTag {
                 actions: Some(vec!["pre-push".to_owned()]),
                 tags: None,
+                paths: None,
+                paths_ignore: None,
+                schedule: None,
+                catch_up: None,
+                after: None,
             });
             let res = serde_json::from_str::<Trigger>(&json).unwrap();
             assert_eq!(trigger == res, false);
         }
         #[test]
+        fn paths() {
+            let json = r#"
+        {
+            "branches": ["master"],
+            "paths": ["src/**"],
+            "paths_ignore": ["docs/**"]
+        }
+        "#;
+            let trigger = Trigger::TriggerBranch(TriggerBranch {
+                branches: Some(vec!["master".to_owned()]),
+                actions: None,
+                paths: Some(vec!["src/**".to_owned()]),
+                paths_ignore: Some(vec!["docs/**".to_owned()]),
+                schedule: None,
+                catch_up: None,
+                after: None,
+            });
+            let res = serde_json::from_str::<Trigger>(json).unwrap();
+            assert_eq!(trigger, res);
+        }
+        #[test]
         fn array() {
             let json = r#"
         [
@@ -62,14 +98,175 @@ mod conversion {
                 Trigger::TriggerBranch(TriggerBranch {
                     branches: Some(vec!["master".to_owned()]),
                     actions: None,
+                    paths: None,
+                    paths_ignore: None,
+                    schedule: None,
+                    catch_up: None,
+                    after: None,
                 }),
                 Trigger::TriggerBranch(TriggerBranch {
                     branches: None,
                     actions: Some(vec!["manual".to_owned(), "watch".to_owned()]),
+                    paths: None,
+                    paths_ignore: None,
+                    schedule: None,
+                    catch_up: None,
+                    after: None,
                 }),
             ];
             let res = serde_json::from_str::<Vec<Trigger>>(&json).unwrap();
             assert_eq!(triggers == res, true);
         }
     }
+    mod env {
+        use crate::{Pipeline, StepOrParallel};
+
+        #[test]
+        fn pipeline_and_step() {
+            let toml = r#"
+        name = "deploy"
+        env = { RUST_LOG = "info" }
+
+        [[steps]]
+        name = "migrate"
+        commands = ["diesel migration run"]
+        env = { DATABASE_URL = "postgres://localhost/db" }
+        "#;
+            let res = toml::from_str::<Pipeline>(toml).unwrap();
+            let env = res.env.unwrap();
+            assert_eq!(env.get("RUST_LOG").map(|e| e.as_str()), Some("info"));
+            match &res.steps[0] {
+                StepOrParallel::Step(step) => {
+                    let env = step.env.clone().unwrap();
+                    assert!(env.contains_key("DATABASE_URL"));
+                }
+                _ => panic!(),
+            }
+        }
+    }
+    mod retries {
+        use crate::{RetryDelay, RetryStrategy, StepOpts};
+
+        #[test]
+        fn retry_delay() {
+            let yaml = "retries: 3\nretry_delay: 5s\n";
+            let res = serde_yaml::from_str::<StepOpts>(yaml).unwrap();
+            assert_eq!(res.retry_delay, Some(RetryDelay::Fixed("5s".to_owned())));
+
+            let yaml = "retries: 3\nretry_delay:\n  exponential: 1s\n";
+            let res = serde_yaml::from_str::<StepOpts>(yaml).unwrap();
+            assert_eq!(
+                res.retry_delay,
+                Some(RetryDelay::Strategy(RetryStrategy::Exponential(
+                    "1s".to_owned()
+                )))
+            );
+        }
+    }
+    mod matrix {
+        use crate::{Pipeline, StepOrParallel};
+
+        #[test]
+        fn step_matrix() {
+            let toml = r#"
+        name = "test"
+
+        [[steps]]
+        name = "test"
+        commands = ["cargo +$TOOLCHAIN test --features $FEATURES"]
+        matrix = { features = ["default", "full"], toolchain = ["stable", "nightly"] }
+        "#;
+            let res = toml::from_str::<Pipeline>(toml).unwrap();
+            match &res.steps[0] {
+                StepOrParallel::Step(step) => {
+                    let matrix = step.matrix.clone().unwrap();
+                    assert_eq!(matrix.get("toolchain").unwrap().len(), 2);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+    mod interpolation {
+        use crate::error::InterpolationError;
+        use crate::Config;
+
+        #[test]
+        fn unknown_variable() {
+            let toml = r#"
+        [[pipelines]]
+        name = "release"
+        env = { REGISTRY = "docker.io" }
+
+        [[pipelines.steps]]
+        name = "build"
+        commands = ["docker build -t ${{ env.REGISTRY }}/app:${{ tag }} ."]
+        "#;
+            let res = toml::from_str::<Config>(toml).unwrap();
+            assert!(res.check_interpolations(toml).is_ok());
+
+            let toml = toml.replace("${{ tag }}", "${{ version }}");
+            let res = toml::from_str::<Config>(&toml).unwrap();
+            let err = res.check_interpolations(&toml).unwrap_err();
+            assert!(err.to_string().contains("version"));
+        }
+        #[test]
+        fn unknown_template_variable() {
+            let yaml = r#"
+templates:
+  - name: setup
+    commands: ["rustup default ${{ env.TOOLCHAIN }}"]
+pipelines:
+  - name: test
+    steps:
+      - use: setup
+"#;
+            let res = serde_yaml::from_str::<Config>(yaml).unwrap();
+            let err = res.check_interpolations(yaml).unwrap_err();
+            assert!(err.to_string().contains("TOOLCHAIN"));
+
+            let yaml = yaml.replace(
+                "- use: setup",
+                "- use: setup\n        with: { toolchain: stable }",
+            );
+            let res = serde_yaml::from_str::<Config>(&yaml).unwrap();
+            assert!(res.check_interpolations(&yaml).is_ok());
+        }
+        #[test]
+        fn unknown_references() {
+            let yaml = r#"
+pipelines:
+  - name: test
+    steps:
+      - use: setup
+"#;
+            let res = serde_yaml::from_str::<Config>(yaml).unwrap();
+            let err = res.check_references().unwrap_err();
+            assert!(err.to_string().contains("unknown step template \"setup\""));
+
+            let yaml = "pipelines:\n  - name: a\n    extends: a\n    steps: []\n";
+            let res = serde_yaml::from_str::<Config>(yaml).unwrap();
+            let err = res.check_references().unwrap_err();
+            assert_eq!(err.to_string(), "The pipeline inheritance loops: a -> a");
+        }
+        #[test]
+        fn unknown_variable_span() {
+            let yaml = r#"
+pipelines:
+  - name: known
+    env: { TARGET: prod }
+    steps:
+      - name: deploy
+        commands: ["echo ${{ env.TARGET }}"]
+  - name: unknown
+    steps:
+      - name: deploy
+        commands: ["echo ${{ env.TARGET }}"]
+"#;
+            let res = serde_yaml::from_str::<Config>(yaml).unwrap();
+            let err = res.check_interpolations(yaml).unwrap_err();
+            let err = err.downcast_ref::<InterpolationError>().unwrap();
+            // The span points at the failing placeholder, not the first one
+            assert_eq!(err.at.offset(), yaml.rfind("${{ env.TARGET }}").unwrap());
+        }
+    }
 }
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod include {
+    use super::super::write_files;
+    use crate::error::IncludeError;
+    use crate::Config;
+    use std::fs;
+
+    #[test]
+    fn merge_included_files() {
+        let dir = write_files(
+            "merge",
+            &[
+                (
+                    "pipelight.yaml",
+                    "include: [\"pipelines/*.yaml\", \"more.toml\"]\npipelines:\n  - name: root\n    steps: []\n",
+                ),
+                ("pipelines/a.yaml", "pipelines:\n  - name: a\n    steps: []\n"),
+                ("pipelines/b.yaml", "include: [\"../more.toml\"]\npipelines:\n  - name: b\n    steps: []\n"),
+                ("more.toml", "[[pipelines]]\nname = \"more\"\nsteps = []\n"),
+            ],
+        );
+        let config = Config::load(&format!("{}/pipelight.yaml", dir), None).unwrap();
+        let names: Vec<String> = config
+            .pipelines
+            .unwrap()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect();
+        // Files included twice are only merged once
+        assert_eq!(names, vec!["root", "a", "b", "more"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn detect_cycles() {
+        let dir = write_files(
+            "cycle",
+            &[
+                ("a.yaml", "include: [\"b.yaml\"]\n"),
+                ("b.yaml", "include: [\"a.yaml\"]\n"),
+            ],
+        );
+        let err = Config::load(&format!("{}/a.yaml", dir), None).unwrap_err();
+        let err = err.downcast_ref::<IncludeError>().unwrap();
+        // In b.yaml -> In a.yaml -> cycle
+        assert!(err.file.ends_with("b.yaml"));
+        let origin = err.origin.diagnostic_source().unwrap();
+        assert!(origin.to_string().starts_with("Include cycle"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn duplicate_across_files() {
+        let dir = write_files(
+            "duplicate",
+            &[
+                (
+                    "a.yaml",
+                    "include: [\"b.yaml\"]\npipelines:\n  - name: test\n    steps: []\n",
+                ),
+                ("b.yaml", "pipelines:\n  - name: test\n    steps: []\n"),
+            ],
+        );
+        let err = Config::load(&format!("{}/a.yaml", dir), None).unwrap_err();
+        let err = err.downcast_ref::<IncludeError>().unwrap();
+        assert!(err.origin.to_string().contains("already defined"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
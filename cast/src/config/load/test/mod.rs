@@ -1,8 +1,34 @@
 // Serde conversion test
 mod conversion;
+mod include;
+mod pipelines;
+
+/**
+Write the files in a fresh temporary directory and return its path.
+The directory is unique to the test process and call,
+so that tests running concurrently never share files.
+*/
+#[cfg(test)]
+pub(super) fn write_files(name: &str, files: &[(&str, &str)]) -> String {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "pipelight.{}.{}.{}",
+        name,
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    for (file, content) in files {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir.to_str().unwrap().to_owned()
+}
 
 #[cfg(test)]
 mod cast {
+    use crate::error::JsonError;
     use crate::{Config, Logs};
     #[test]
     fn toml() {
@@ -15,6 +41,22 @@ mod cast {
         assert!(res.is_ok());
     }
     #[test]
+    fn json() {
+        let res = Config::load("./public/pipelight.json", None);
+        assert!(res.is_ok());
+    }
+    #[test]
+    fn json5() {
+        let res = Config::load("./public/pipelight.json5", None);
+        assert!(res.is_ok());
+    }
+    #[test]
+    fn json5_error_span() {
+        let src = "{\n  pipelines: [\n    { name: \"test\", steps: [] }\n  ],\n  unknown: true,\n}\n";
+        let err = JsonError::from_json5(json5::from_str::<Config>(src).unwrap_err(), src);
+        assert_ne!(err.at.offset(), 0);
+    }
+    #[test]
     fn javascript() {
         let res = Config::load("./public/pipelight.js", None);
         println!("{:?}",res);
@@ -27,6 +69,20 @@ mod cast {
         assert!(res.is_ok());
     }
     #[test]
+    #[cfg(feature = "quickjs")]
+    fn javascript_runtime_error() {
+        let dir = super::write_files(
+            "error",
+            &[(
+                "pipelight.js",
+                "const config = {};\nundefinedFunction();\nexport default config;\n",
+            )],
+        );
+        let res = Config::load(&format!("{}/pipelight.js", dir), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(res.unwrap_err().to_string().contains("undefinedFunction"));
+    }
+    #[test]
     fn logs() {
         let res = Logs::read("./public");
         assert!(res.is_ok());
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod pipelines_dir {
+    use super::super::write_files;
+    use crate::error::{DuplicatePipelineError, IncludeError, InterpolationError};
+    use crate::Config;
+    use std::fs;
+
+    fn names(config: Config) -> Vec<String> {
+        config
+            .pipelines
+            .unwrap()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn merge_pipelines_dir() {
+        let dir = write_files(
+            "merge",
+            &[
+                (
+                    "pipelight.yaml",
+                    "options:\n  log_level: warn\npipelines:\n  - name: root\n    steps: []\n",
+                ),
+                (".pipelight/pipelines/b.toml", "name = \"b\"\nsteps = []\n"),
+                (".pipelight/pipelines/a.yaml", "name: a\nsteps: []\n"),
+                (
+                    ".pipelight/pipelines/c.json",
+                    "{ \"name\": \"c\", \"steps\": [] }",
+                ),
+                (".pipelight/pipelines/README.md", "Not a pipeline"),
+            ],
+        );
+        let config = Config::load(&format!("{}/pipelight.yaml", dir), None).unwrap();
+        // Options come from the main file
+        assert!(config.options.is_some());
+        assert_eq!(names(config), vec!["root", "a", "b", "c"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn pipelines_dir_without_config() {
+        let dir = write_files(
+            "alone",
+            &[(".pipelight/pipelines/a.yaml", "name: a\nsteps: []\n")],
+        );
+        let config = Config::load(&format!("{}/.pipelight/pipelines", dir), None).unwrap();
+        assert!(config.options.is_none());
+        assert_eq!(names(config), vec!["a"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn duplicate_with_config() {
+        const PIPELINE: &str = "env:\n  path: a\nname: a\nsteps: []\n";
+        let dir = write_files(
+            "duplicate",
+            &[
+                ("pipelight.yaml", "pipelines:\n  - name: a\n    steps: []\n"),
+                (".pipelight/pipelines/a.yaml", PIPELINE),
+            ],
+        );
+        let err = Config::load(&format!("{}/pipelight.yaml", dir), None).unwrap_err();
+        // Points at the name key, not at the first "a" of the file
+        let span = err
+            .downcast_ref::<DuplicatePipelineError>()
+            .unwrap()
+            .at
+            .unwrap();
+        assert_eq!(span.offset(), PIPELINE.find("name: a").unwrap() + 6);
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn inherited_variables_across_files() {
+        const CHILD: &str = "name: child\nextends: base\nsteps:\n  - name: deploy\n    commands: [\"echo ${{ env.TARGET }}\"]\n";
+        let dir = write_files(
+            "inherited",
+            &[
+                (
+                    "pipelight.yaml",
+                    "pipelines:\n  - name: base\n    env:\n      TARGET: prod\n    steps: []\n",
+                ),
+                (".pipelight/pipelines/child.yaml", CHILD),
+            ],
+        );
+        let file = format!("{}/pipelight.yaml", dir);
+        let res = Config::load(&file, None);
+        assert!(res.is_ok());
+
+        // Unknown variables point at the file defining the pipeline
+        let child = CHILD.replace("env.TARGET", "env.PIPELIGHT_UNKNOWN_TARGET");
+        fs::write(format!("{}/.pipelight/pipelines/child.yaml", dir), &child).unwrap();
+        let err = Config::load(&file, None).unwrap_err();
+        fs::remove_dir_all(dir).unwrap();
+        let err = err.downcast_ref::<IncludeError>().unwrap();
+        assert!(err.file.ends_with("child.yaml"));
+        let origin = err.origin.downcast_ref::<InterpolationError>().unwrap();
+        assert_eq!(origin.at.offset(), child.find("${{").unwrap());
+    }
+}
@@ -1,18 +1,54 @@
 // Error Handling
 use crate::error::JsonError;
 use miette::{Error, Result};
+// Serde
+use serde::de::DeserializeOwned;
 
 // Exec
 use exec::Process;
 
+// Tests
+mod test;
+
+#[cfg(feature = "quickjs")]
+mod quickjs;
 mod script;
+#[cfg(feature = "quickjs")]
+mod strip;
 use script::import_script;
 
 use crate::Config;
 
 impl Config {
+    /// Return a Config struct from a provided javascript file path.
+    /// The file is evaluated in-process when the "quickjs" feature is enabled,
+    /// and by deno otherwise.
+    pub fn js(file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
+        from_json(&Config::js_json(file_path, args)?)
+    }
     /// Return a Config struct from a provided typescript file path
     pub fn ts(file_path: &str, args: Option<Vec<String>>) -> Result<Config> {
+        from_json(&Config::ts_json(file_path, args)?)
+    }
+    /// Evaluate a javascript file and return its default export as json.
+    pub(crate) fn js_json(file_path: &str, args: Option<Vec<String>>) -> Result<String> {
+        #[cfg(feature = "quickjs")]
+        return quickjs::eval(file_path, args);
+        #[cfg(not(feature = "quickjs"))]
+        return Config::deno_json(file_path, args);
+    }
+    /// Evaluate a typescript file and return its default export as json.
+    /// With the "quickjs" feature, the types are erased and the file evaluated in-process,
+    /// unless it imports remote modules at runtime, that only deno can fetch.
+    pub(crate) fn ts_json(file_path: &str, args: Option<Vec<String>>) -> Result<String> {
+        #[cfg(feature = "quickjs")]
+        if !quickjs::imports_remote_modules(file_path)? {
+            return quickjs::eval(file_path, args);
+        }
+        Config::deno_json(file_path, args)
+    }
+    /// Evaluate a script file with deno and return its default export as json.
+    fn deno_json(file_path: &str, args: Option<Vec<String>>) -> Result<String> {
         // Fail safe guards
         Config::lint(file_path)?;
         Config::check(file_path, args.clone())?;
@@ -26,15 +62,7 @@ impl Config {
         };
         let mut p = Process::new(&command);
         p.run_piped()?;
-        let json = p.io.stdout.unwrap();
-        let res = serde_json::from_str::<Config>(&json);
-        match res {
-            Ok(res) => Ok(res),
-            Err(e) => {
-                let err = JsonError::new(e, &json);
-                Err(err.into())
-            }
-        }
+        Ok(p.io.stdout.unwrap())
     }
     /// Check if the deno script contains syntax errors
     fn lint(file: &str) -> Result<()> {
@@ -83,3 +111,13 @@ impl Config {
         }
     }
 }
+
+/**
+Parse the json returned by a script evaluation.
+*/
+pub(super) fn from_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    match serde_json::from_str::<T>(json) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(JsonError::new(e, json).into()),
+    }
+}
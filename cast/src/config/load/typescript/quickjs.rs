@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+// Filesystem
+use std::fs;
+use std::path::{Path, PathBuf};
+// Javascript runtime
+use rquickjs::loader::{Loader, Resolver};
+use rquickjs::{CaughtError, Context, Ctx, Function, Module, Object, Runtime, Value};
+// Typescript
+use super::strip::strip_types;
+// Error Handling
+use crate::error::JsError;
+use miette::{Error, IntoDiagnostic, Result};
+
+/**
+Console methods that are muted while the config file is imported,
+like the deno import script does.
+*/
+const CONSOLE_METHODS: [&str; 6] = ["log", "error", "warn", "info", "debug", "trace"];
+
+/**
+Module schemes that only deno can fetch.
+*/
+const REMOTE_SCHEMES: [&str; 4] = ["http:", "https:", "npm:", "jsr:"];
+
+/**
+Evaluate a javascript or typescript config file with the embedded QuickJS runtime
+and return its default export as json.
+
+Only local imports are supported.
+Arguments are available to the script as `Deno.args`.
+*/
+pub fn eval(file_path: &str, args: Option<Vec<String>>) -> Result<String> {
+    let name = Path::new(file_path)
+        .canonicalize()
+        .into_diagnostic()?
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let runtime = Runtime::new().into_diagnostic()?;
+    runtime.set_loader(PathResolver, ModuleLoader);
+    let source = read_module(&name).into_diagnostic()?;
+    let context = Context::full(&runtime).into_diagnostic()?;
+    context.with(|ctx: Ctx| {
+        set_globals(&ctx, args.unwrap_or_default()).into_diagnostic()?;
+        // Declare the module from its source rather than with `Module::import`
+        // whose asynchronous loading reads an already freed base module name.
+        let res = Module::declare(ctx.clone(), name.clone(), source)
+            .and_then(|module| module.eval())
+            .and_then(|(module, promise)| {
+                promise.finish::<()>()?;
+                module.get::<_, Value>("default")
+            })
+            .and_then(|config| ctx.json_stringify(config));
+        match res {
+            Ok(Some(json)) => json.to_string().into_diagnostic(),
+            Ok(None) => Err(Error::msg(format!(
+                "The config file {:?} has no default export",
+                file_path
+            ))),
+            Err(e) => Err(js_error(CaughtError::from_error(&ctx, e), file_path).into()),
+        }
+    })
+}
+
+/**
+Mute the console and expose the script arguments.
+*/
+fn set_globals(ctx: &Ctx, args: Vec<String>) -> rquickjs::Result<()> {
+    let console = Object::new(ctx.clone())?;
+    for method in CONSOLE_METHODS {
+        console.set(method, Function::new(ctx.clone(), || {})?)?;
+    }
+    ctx.globals().set("console", console)?;
+    let deno = Object::new(ctx.clone())?;
+    deno.set("args", args)?;
+    ctx.globals().set("Deno", deno)?;
+    Ok(())
+}
+
+/**
+Whether the config file, or any local module it imports,
+imports modules at runtime that can't be read from disk.
+*/
+pub fn imports_remote_modules(file_path: &str) -> Result<bool> {
+    let path = Path::new(file_path).canonicalize().into_diagnostic()?;
+    find_remote_import(&path, &mut HashSet::new())
+}
+
+/**
+Walk the local imports of a module until a remote import is found.
+Already visited modules are skipped so that import cycles terminate.
+*/
+fn find_remote_import(path: &Path, visited: &mut HashSet<PathBuf>) -> Result<bool> {
+    if !visited.insert(path.to_owned()) {
+        return Ok(false);
+    }
+    let source = fs::read_to_string(path).into_diagnostic()?;
+    for import in strip_types(&source).imports {
+        if REMOTE_SCHEMES
+            .iter()
+            .any(|scheme| import.starts_with(scheme))
+        {
+            return Ok(true);
+        }
+        if import.starts_with('.') {
+            let base = path.to_str().unwrap();
+            let module = PathResolver.resolve_path(base, &import);
+            // Missing modules are reported by the loader
+            if module.is_file() && find_remote_import(&module, visited)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/**
+Read a module source, with the types erased from typescript modules.
+*/
+fn read_module(path: &str) -> std::io::Result<String> {
+    let source = fs::read_to_string(path)?;
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("ts" | "mts") => Ok(strip_types(&source).js),
+        _ => Ok(source),
+    }
+}
+
+/**
+Load javascript and typescript modules from disk.
+*/
+struct ModuleLoader;
+impl Loader for ModuleLoader {
+    fn load<'js>(&mut self, ctx: &Ctx<'js>, path: &str) -> rquickjs::Result<Module<'js>> {
+        Module::declare(ctx.clone(), path, read_module(path)?)
+    }
+}
+
+/**
+Resolve relative imports against the importing module path.
+*/
+struct PathResolver;
+impl Resolver for PathResolver {
+    fn resolve(&mut self, _ctx: &Ctx, base: &str, name: &str) -> rquickjs::Result<String> {
+        if !name.starts_with('.') {
+            return Ok(name.to_owned());
+        }
+        Ok(self.resolve_path(base, name).to_str().unwrap().to_owned())
+    }
+}
+impl PathResolver {
+    /**
+    Join a relative import to the directory of the importing module.
+    */
+    fn resolve_path(&self, base: &str, name: &str) -> PathBuf {
+        let mut path = PathBuf::from(base);
+        path.pop();
+        for component in Path::new(name).components() {
+            match component.as_os_str().to_str().unwrap() {
+                "." => {}
+                ".." => {
+                    path.pop();
+                }
+                component => path.push(component),
+            }
+        }
+        path
+    }
+}
+
+/**
+Convert a javascript exception into a diagnostic
+pointing at the throwing line of the faulty module.
+*/
+fn js_error(e: CaughtError, file_path: &str) -> JsError {
+    match e {
+        CaughtError::Exception(exception) => {
+            let message = exception.message().unwrap_or_default();
+            // The first stack frame is "at <function> (<file>:<line>:<column>)"
+            let frame = exception.stack().and_then(|stack| {
+                let frame = stack
+                    .lines()
+                    .next()?
+                    .rsplit_once('(')?
+                    .1
+                    .trim_end_matches(')');
+                let mut parts = frame.rsplitn(3, ':');
+                let column = parts.next()?.parse::<usize>().ok()?;
+                let line = parts.next()?.parse::<usize>().ok()?;
+                Some((parts.next()?.to_owned(), line, column))
+            });
+            let (file, line, column) = match frame {
+                Some(frame) => frame,
+                None => (
+                    file_path.to_owned(),
+                    exception.line().unwrap_or(0).max(0) as usize,
+                    exception.column().unwrap_or(0).max(0) as usize,
+                ),
+            };
+            let src = fs::read_to_string(file)
+                .or_else(|_| fs::read_to_string(file_path))
+                .unwrap_or_default();
+            JsError::new(&message, &src, line, column)
+        }
+        e => {
+            let src = fs::read_to_string(file_path).unwrap_or_default();
+            JsError::new(&e.to_string(), &src, 0, 0)
+        }
+    }
+}
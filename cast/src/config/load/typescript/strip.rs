@@ -0,0 +1,708 @@
+/**
+Erase the typescript type syntax from a module source
+so that it can be evaluated by a plain javascript runtime.
+
+Types are replaced with whitespace rather than removed,
+so that the lines and columns of javascript errors
+still point at the typescript source.
+
+The supported syntax is the erasable one config files use:
+- type only imports and exports,
+- type aliases and interfaces,
+- annotations of variables, parameters and return types,
+- generic parameters of function declarations,
+- `as` and `satisfies` expressions and non-null assertions.
+*/
+pub fn strip_types(source: &str) -> Stripped {
+    let mut stripper = Stripper {
+        src: source.as_bytes(),
+        blanks: vec![],
+        imports: vec![],
+    };
+    stripper.strip();
+    let mut js = String::with_capacity(source.len());
+    for (i, c) in source.char_indices() {
+        if c != '\n' && stripper.blank_end(i).is_some() {
+            js.push(' ');
+        } else {
+            js.push(c);
+        }
+    }
+    Stripped {
+        js,
+        imports: stripper.imports,
+    }
+}
+
+/**
+A typescript module source turned into javascript.
+*/
+pub struct Stripped {
+    pub js: String,
+    /// The modules imported at runtime (type only imports excluded).
+    pub imports: Vec<String>,
+}
+
+/**
+Words that can't end an expression,
+after which a parenthesis or a slash opens something new.
+*/
+const KEYWORDS: [&str; 24] = [
+    "return",
+    "typeof",
+    "case",
+    "do",
+    "else",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "instanceof",
+    "yield",
+    "await",
+    "async",
+    "if",
+    "for",
+    "while",
+    "switch",
+    "with",
+    "catch",
+    "function",
+    "export",
+    "default",
+];
+
+struct Stripper<'a> {
+    src: &'a [u8],
+    /// Byte ranges to replace with whitespace.
+    blanks: Vec<(usize, usize)>,
+    imports: Vec<String>,
+}
+
+impl Stripper<'_> {
+    fn strip(&mut self) {
+        let mut pos = 0;
+        // Whether the previous token ends an expression.
+        let mut value = false;
+        // Whether the previous token is a member access dot.
+        let mut dot = false;
+        loop {
+            pos = self.skip_trivia(pos);
+            if let Some(end) = self.blank_end(pos) {
+                pos = end;
+                value = true;
+                continue;
+            }
+            let Some(end) = self.token_end(pos, value) else {
+                break;
+            };
+            let token = &self.src[pos..end];
+            let (mut next, mut next_value) = (end, true);
+            if is_ident_start(token[0]) {
+                let word = std::str::from_utf8(token).unwrap_or_default();
+                next_value = dot || !KEYWORDS.contains(&word);
+                if !dot {
+                    if let Some(end) = self.word(pos, end, word, value) {
+                        // Only type expressions end in the middle of an expression
+                        next_value = matches!(word, "as" | "satisfies");
+                        next = end;
+                    }
+                }
+            } else {
+                match token[0] {
+                    b'(' if !value => self.arrow(pos),
+                    // Non-null assertions stick to their operand
+                    b'!' if value
+                        && !self.src[pos - 1].is_ascii_whitespace()
+                        && self.byte(end) != Some(b'=') =>
+                    {
+                        self.blanks.push((pos, end));
+                    }
+                    b')' | b']' | b'}' => {}
+                    b'"' | b'\'' | b'`' | b'0'..=b'9' => {}
+                    _ => next_value = false,
+                }
+            }
+            dot = token == b".";
+            value = next_value;
+            pos = next;
+        }
+    }
+
+    /**
+    Handle a word that may start type syntax.
+    Returns the position to resume from when the word starts a whole statement.
+    */
+    fn word(&mut self, start: usize, end: usize, word: &str, value: bool) -> Option<usize> {
+        let next = self.skip_trivia(end);
+        match word {
+            "import" if !matches!(self.byte(next), Some(b'(' | b'.')) => {
+                return Some(self.import(start, next));
+            }
+            "export" => {
+                let after = self.word_at(next).map(|w| self.skip_trivia(next + w.len()));
+                match (self.word_at(next), after.and_then(|a| self.byte(a))) {
+                    (Some("type"), Some(b'{' | b'*')) => {
+                        let end = self.statement_end(after.unwrap());
+                        self.blanks.push((start, end));
+                        return Some(end);
+                    }
+                    (Some("type" | "interface"), _) => {
+                        let end = self.declaration(next)?;
+                        self.blanks.push((start, end));
+                        return Some(end);
+                    }
+                    _ if self.byte(next) == Some(b'{') => {
+                        return Some(self.specifiers(next));
+                    }
+                    _ => {}
+                }
+            }
+            "type" | "interface" => {
+                let end = self.declaration(start)?;
+                self.blanks.push((start, end));
+                return Some(end);
+            }
+            "const" | "let" | "var" => self.variable(next),
+            "function" => {
+                let mut pos = next;
+                if self.byte(pos) == Some(b'*') {
+                    pos = self.skip_trivia(pos + 1);
+                }
+                if let Some(name) = self.word_at(pos) {
+                    pos = self.skip_trivia(pos + name.len());
+                }
+                return self.function(pos);
+            }
+            "as" | "satisfies" if value => {
+                let end = self.skip_type(next)?;
+                self.blanks.push((start, end));
+                return Some(end);
+            }
+            "catch" if self.byte(next) == Some(b'(') => self.parameters(next),
+            _ if !value && !KEYWORDS.contains(&word) => {
+                // Method definitions: "name(params): type {"
+                if matches!(self.byte(next), Some(b'(' | b'<')) {
+                    let close = self.skip_balanced(next)?;
+                    let close = self.skip_trivia(close);
+                    if self.byte(next) == Some(b'<') && self.byte(close) != Some(b'(') {
+                        return None;
+                    }
+                    let params = if self.byte(next) == Some(b'<') {
+                        close
+                    } else {
+                        next
+                    };
+                    let after = self.skip_trivia(self.skip_balanced(params)?);
+                    match self.byte(after) {
+                        Some(b'{') => {}
+                        Some(b':') => {
+                            let end = self.skip_type(after + 1)?;
+                            if self.byte(self.skip_trivia(end)) != Some(b'{') {
+                                return None;
+                            }
+                        }
+                        _ => return None,
+                    }
+                    return self.function(next);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /**
+    Blank the generic parameters, parameter and return types of a function
+    whose generics or parameters start at pos.
+    Returns the position right after the opening parenthesis.
+    */
+    fn function(&mut self, mut pos: usize) -> Option<usize> {
+        if self.byte(pos) == Some(b'<') {
+            let end = self.skip_balanced(pos)?;
+            self.blanks.push((pos, end));
+            pos = self.skip_trivia(end);
+        }
+        if self.byte(pos) != Some(b'(') {
+            return None;
+        }
+        self.parameters(pos);
+        let close = self.skip_balanced(pos)?;
+        self.return_type(close);
+        Some(pos + 1)
+    }
+
+    /**
+    Blank the parameter and return types of an arrow function,
+    if the parenthesis at pos opens one.
+    */
+    fn arrow(&mut self, pos: usize) {
+        let Some(close) = self.skip_balanced(pos) else {
+            return;
+        };
+        let after = self.skip_trivia(close);
+        let end = match self.byte(after) {
+            Some(b':') => match self.skip_type(after + 1) {
+                Some(end) => end,
+                None => return,
+            },
+            _ => after,
+        };
+        if self.src[self.skip_trivia(end)..].starts_with(b"=>") {
+            self.parameters(pos);
+            self.return_type(close);
+        }
+    }
+
+    /**
+    Blank the return type following the parameters closing at pos.
+    */
+    fn return_type(&mut self, close: usize) {
+        let after = self.skip_trivia(close);
+        if self.byte(after) == Some(b':') {
+            if let Some(end) = self.skip_type(after + 1) {
+                self.blanks.push((after, end));
+            }
+        }
+    }
+
+    /**
+    Blank the optional marks and the types of the parameters
+    in the parenthesis opening at pos.
+    */
+    fn parameters(&mut self, open: usize) {
+        let mut pos = open + 1;
+        loop {
+            pos = self.skip_trivia(pos);
+            if self.src[pos..].starts_with(b"...") {
+                pos = self.skip_trivia(pos + 3);
+            }
+            // The binding, a name or a destructuring pattern
+            pos = match self.byte(pos) {
+                Some(b'{' | b'[') => match self.skip_balanced(pos) {
+                    Some(end) => end,
+                    None => return,
+                },
+                _ => match self.word_at(pos) {
+                    Some(word) => pos + word.len(),
+                    None => return,
+                },
+            };
+            pos = self.skip_trivia(pos);
+            if self.byte(pos) == Some(b'?') {
+                self.blanks.push((pos, pos + 1));
+                pos = self.skip_trivia(pos + 1);
+            }
+            if self.byte(pos) == Some(b':') {
+                match self.skip_type(pos + 1) {
+                    Some(end) => {
+                        self.blanks.push((pos, end));
+                        pos = end;
+                    }
+                    None => return,
+                }
+            }
+            // Skip the default value up to the next parameter
+            let mut value = false;
+            loop {
+                pos = self.skip_trivia(pos);
+                match self.byte(pos) {
+                    Some(b',') => {
+                        pos += 1;
+                        break;
+                    }
+                    Some(b')') | None => return,
+                    Some(b'(' | b'[' | b'{') => match self.skip_balanced(pos) {
+                        Some(end) => pos = end,
+                        None => return,
+                    },
+                    Some(_) => match self.token_end(pos, value) {
+                        Some(end) => {
+                            value = is_ident_start(self.src[pos]);
+                            pos = end;
+                        }
+                        None => return,
+                    },
+                }
+            }
+        }
+    }
+
+    /**
+    Blank the type of a variable declaration whose binding starts at pos.
+    */
+    fn variable(&mut self, pos: usize) {
+        let end = match self.byte(pos) {
+            Some(b'{' | b'[') => self.skip_balanced(pos),
+            _ => self.word_at(pos).map(|word| pos + word.len()),
+        };
+        let Some(end) = end else {
+            return;
+        };
+        let mut colon = self.skip_trivia(end);
+        // Definite assignment assertion
+        if self.byte(colon) == Some(b'!') {
+            self.blanks.push((colon, colon + 1));
+            colon = self.skip_trivia(colon + 1);
+        }
+        if self.byte(colon) == Some(b':') {
+            if let Some(end) = self.skip_type(colon + 1) {
+                self.blanks.push((colon, end));
+            }
+        }
+    }
+
+    /**
+    Return the end of a type alias or an interface declaration
+    whose keyword starts at pos.
+    */
+    fn declaration(&self, pos: usize) -> Option<usize> {
+        let keyword = self.word_at(pos)?;
+        let pos = self.skip_trivia(pos + keyword.len());
+        let name = self.word_at(pos)?;
+        let mut pos = self.skip_trivia(pos + name.len());
+        if self.byte(pos) == Some(b'<') {
+            pos = self.skip_trivia(self.skip_balanced(pos)?);
+        }
+        let end = if keyword == "type" {
+            if self.byte(pos) != Some(b'=') {
+                return None;
+            }
+            self.skip_type(pos + 1)?
+        } else {
+            // Skip the extended interfaces up to the body
+            while self.byte(pos)? != b'{' {
+                pos = self.skip_trivia(self.token_end(pos, false)?);
+            }
+            self.skip_balanced(pos)?
+        };
+        let after = self.skip_trivia(end);
+        match self.byte(after) {
+            Some(b';') => Some(after + 1),
+            _ => Some(end),
+        }
+    }
+
+    /**
+    Handle an import statement whose clause starts at pos.
+    Type only imports are blanked, runtime imports are recorded.
+    Returns the end of the statement.
+    */
+    fn import(&mut self, start: usize, pos: usize) -> usize {
+        let end = self.statement_end(pos);
+        if self.word_at(pos) == Some("type") {
+            let after = self.skip_trivia(pos + 4);
+            if self.word_at(after) != Some("from") && self.byte(after) != Some(b',') {
+                self.blanks.push((start, end));
+                return end;
+            }
+        }
+        if let Some(open) = (pos..end).find(|i| self.byte(*i) == Some(b'{')) {
+            self.specifiers(open);
+        }
+        if let Some(specifier) = self.specifier(pos, end) {
+            self.imports.push(specifier);
+        }
+        end
+    }
+
+    /**
+    Blank the type only specifiers of an import or export clause
+    opening at pos.
+    Returns the end of the clause.
+    */
+    fn specifiers(&mut self, open: usize) -> usize {
+        let Some(close) = self.skip_balanced(open) else {
+            return open + 1;
+        };
+        let mut pos = self.skip_trivia(open + 1);
+        while pos < close {
+            let mut end = pos;
+            while end < close && self.byte(end) != Some(b',') {
+                end = self.skip_trivia(self.token_end(end, false).unwrap_or(close));
+            }
+            let after = self.word_at(pos).map(|w| self.skip_trivia(pos + w.len()));
+            if self.word_at(pos) == Some("type")
+                && after.is_some_and(|a| a < end && self.word_at(a) != Some("as"))
+            {
+                self.blanks.push((pos, (end + 1).min(close)));
+            }
+            pos = self.skip_trivia(end + 1);
+        }
+        close
+    }
+
+    /**
+    Return the module specifier of an import or export statement.
+    */
+    fn specifier(&self, start: usize, end: usize) -> Option<String> {
+        let mut pos = start;
+        while pos < end {
+            if matches!(self.byte(pos), Some(b'"' | b'\'')) {
+                let close = self.token_end(pos, false)?;
+                let specifier = &self.src[pos + 1..close - 1];
+                return Some(String::from_utf8_lossy(specifier).into_owned());
+            }
+            pos = self.skip_trivia(self.token_end(pos, false)?);
+        }
+        None
+    }
+
+    /**
+    Return the end of an import or export statement, after its module specifier.
+    */
+    fn statement_end(&self, mut pos: usize) -> usize {
+        while let Some(byte) = self.byte(pos) {
+            match byte {
+                b';' => return pos + 1,
+                b'"' | b'\'' => {
+                    let end = self.token_end(pos, false).unwrap_or(self.src.len());
+                    let after = self.skip_trivia(end);
+                    if self.byte(after) == Some(b';') {
+                        return after + 1;
+                    }
+                    return end;
+                }
+                b'{' => pos = self.skip_balanced(pos).unwrap_or(self.src.len()),
+                _ => pos = self.token_end(pos, false).unwrap_or(self.src.len()),
+            }
+            pos = self.skip_trivia(pos);
+        }
+        pos
+    }
+
+    /**
+    Return the end of the type starting at pos.
+    */
+    fn skip_type(&self, pos: usize) -> Option<usize> {
+        let mut pos = self.skip_trivia(pos);
+        // Leading union or intersection operator
+        if matches!(self.byte(pos), Some(b'|' | b'&')) {
+            pos = self.skip_trivia(pos + 1);
+        }
+        loop {
+            let mut end = self.skip_type_operand(pos)?;
+            // Array types and indexed accesses
+            while self.byte(self.skip_trivia(end)) == Some(b'[') {
+                end = self.skip_balanced(self.skip_trivia(end))?;
+            }
+            let next = self.skip_trivia(end);
+            let operator = &self.src[next..];
+            if (operator.starts_with(b"|") && !operator.starts_with(b"||"))
+                || (operator.starts_with(b"&") && !operator.starts_with(b"&&"))
+            {
+                pos = self.skip_trivia(next + 1);
+            } else {
+                return Some(end);
+            }
+        }
+    }
+
+    fn skip_type_operand(&self, pos: usize) -> Option<usize> {
+        match self.byte(pos)? {
+            b'{' | b'[' => self.skip_balanced(pos),
+            b'(' | b'<' => {
+                // Parenthesized and function types
+                let mut end = self.skip_balanced(pos)?;
+                if self.byte(pos) == Some(b'<') {
+                    end = self.skip_balanced(self.skip_trivia(end))?;
+                }
+                let next = self.skip_trivia(end);
+                if self.src[next..].starts_with(b"=>") {
+                    self.skip_type(next + 2)
+                } else {
+                    Some(end)
+                }
+            }
+            b'"' | b'\'' | b'`' | b'0'..=b'9' => self.token_end(pos, false),
+            b'-' => self.token_end(self.skip_trivia(pos + 1), false),
+            byte if is_ident_start(byte) => {
+                let word = self.word_at(pos)?;
+                let mut end = pos + word.len();
+                if matches!(word, "typeof" | "keyof" | "readonly" | "unique") {
+                    return self.skip_type_operand(self.skip_trivia(end));
+                }
+                // Qualified names and generic arguments
+                loop {
+                    let next = self.skip_trivia(end);
+                    match self.byte(next) {
+                        Some(b'.') => {
+                            let name = self.skip_trivia(next + 1);
+                            end = name + self.word_at(name)?.len();
+                        }
+                        Some(b'<') => end = self.skip_balanced(next)?,
+                        _ => return Some(end),
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /**
+    Return the position after the bracket matching the one at pos.
+    */
+    fn skip_balanced(&self, pos: usize) -> Option<usize> {
+        let open = self.byte(pos)?;
+        let close = match open {
+            b'(' => b')',
+            b'[' => b']',
+            b'{' => b'}',
+            b'<' => b'>',
+            _ => return None,
+        };
+        let mut depth = 0;
+        let mut value = false;
+        let mut pos = pos;
+        loop {
+            pos = self.skip_trivia(pos);
+            let end = self.token_end(pos, value)?;
+            let token = &self.src[pos..end];
+            if token == [open] {
+                depth += 1;
+            } else if token == [close] {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(end);
+                }
+            }
+            value = is_ident_start(token[0]) || matches!(token[0], b')' | b']' | b'}');
+            pos = end;
+        }
+    }
+
+    /**
+    Return the end of the token starting at pos.
+    A slash opens a regular expression unless it follows a value.
+    */
+    fn token_end(&self, pos: usize, value: bool) -> Option<usize> {
+        let byte = self.byte(pos)?;
+        let end = match byte {
+            b'"' | b'\'' => {
+                let mut i = pos + 1;
+                while self.byte(i)? != byte {
+                    i += if self.byte(i)? == b'\\' { 2 } else { 1 };
+                }
+                i + 1
+            }
+            b'`' => self.template_end(pos)?,
+            b'/' if !value => {
+                let mut i = pos + 1;
+                let mut class = false;
+                loop {
+                    match self.byte(i)? {
+                        b'\\' => i += 1,
+                        b'[' => class = true,
+                        b']' => class = false,
+                        b'/' if !class => break,
+                        b'\n' => return Some(pos + 1),
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                self.ident_end(i + 1)
+            }
+            b'=' if self.src[pos..].starts_with(b"=>") => pos + 2,
+            b'.' if self.src[pos..].starts_with(b"...") => pos + 3,
+            b'0'..=b'9' => self.ident_end(pos),
+            _ if is_ident_start(byte) => self.ident_end(pos),
+            _ => pos + 1,
+        };
+        Some(end)
+    }
+
+    /**
+    Return the end of the template literal starting at pos,
+    skipping over its substitutions.
+    */
+    fn template_end(&self, pos: usize) -> Option<usize> {
+        let mut i = pos + 1;
+        loop {
+            match self.byte(i)? {
+                b'\\' => i += 2,
+                b'`' => return Some(i + 1),
+                b'$' if self.byte(i + 1) == Some(b'{') => {
+                    let mut depth = 0;
+                    let mut value = false;
+                    i += 1;
+                    loop {
+                        i = self.skip_trivia(i);
+                        let end = self.token_end(i, value)?;
+                        match self.src[i] {
+                            b'{' => depth += 1,
+                            b'}' => depth -= 1,
+                            _ => {}
+                        }
+                        value = is_ident_start(self.src[i]);
+                        i = end;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    /**
+    Return the position of the next token, after whitespaces and comments.
+    */
+    fn skip_trivia(&self, mut pos: usize) -> usize {
+        loop {
+            match self.byte(pos) {
+                Some(byte) if byte.is_ascii_whitespace() => pos += 1,
+                Some(b'/') if self.byte(pos + 1) == Some(b'/') => {
+                    while self.byte(pos).is_some_and(|b| b != b'\n') {
+                        pos += 1;
+                    }
+                }
+                Some(b'/') if self.byte(pos + 1) == Some(b'*') => {
+                    pos += 2;
+                    while self.byte(pos).is_some() && !self.src[pos..].starts_with(b"*/") {
+                        pos += 1;
+                    }
+                    pos = (pos + 2).min(self.src.len());
+                }
+                _ => return pos,
+            }
+        }
+    }
+
+    fn ident_end(&self, mut pos: usize) -> usize {
+        while self.byte(pos).is_some_and(is_ident_part) {
+            pos += 1;
+        }
+        pos
+    }
+
+    fn word_at(&self, pos: usize) -> Option<&str> {
+        if !self.byte(pos).is_some_and(is_ident_start) {
+            return None;
+        }
+        std::str::from_utf8(&self.src[pos..self.ident_end(pos)]).ok()
+    }
+
+    fn byte(&self, pos: usize) -> Option<u8> {
+        self.src.get(pos).copied()
+    }
+
+    /**
+    Return the end of the blanked range containing pos.
+    */
+    fn blank_end(&self, pos: usize) -> Option<usize> {
+        self.blanks
+            .iter()
+            .find(|(start, end)| (*start..*end).contains(&pos))
+            .map(|(_, end)| *end)
+    }
+}
+
+fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || matches!(byte, b'_' | b'$') || byte >= 0x80
+}
+fn is_ident_part(byte: u8) -> bool {
+    is_ident_start(byte) || byte.is_ascii_digit()
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+#[cfg(feature = "quickjs")]
+mod strip {
+    use super::super::quickjs::imports_remote_modules;
+    use super::super::strip::strip_types;
+    use crate::config::load::test::write_files;
+    use crate::Config;
+    use std::fs;
+
+    const CONFIG: &str = r#"import type { Config, Pipeline } from "https://deno.land/x/pipelight/mod.ts";
+import { type Step, helper } from "./helper.ts";
+
+interface Options extends Record<string, unknown> {
+  host?: string;
+}
+type Mode = "stop" | "continue";
+
+const mode: Mode = "continue" as Mode;
+const options: Options = { host: "localhost" };
+
+function step<T extends string>(name: T, commands?: string[]): Step {
+  return { name, commands: commands ?? ["ls"], options: { mode } };
+}
+const pipeline = (name: string, ...steps: Step[]): Pipeline => ({ name, steps });
+
+const config = {
+  pipelines: [pipeline(helper(options.host!), step(`ping ${options.host}`))],
+} satisfies Config;
+export default config;
+"#;
+
+    #[test]
+    fn keep_positions() {
+        let stripped = strip_types(CONFIG);
+        assert_eq!(stripped.imports, ["./helper.ts"]);
+        for (js, ts) in stripped.js.lines().zip(CONFIG.lines()) {
+            assert_eq!(js.len(), ts.len());
+        }
+        assert!(stripped.js.starts_with(&" ".repeat(76)));
+        assert!(stripped
+            .js
+            .contains("function step                  (name   , commands           )       {"));
+        assert!(stripped
+            .js
+            .contains("const mode       = \"continue\"        ;"));
+        assert!(stripped.js.contains("helper(options.host )"));
+    }
+    #[test]
+    fn typescript_in_process() {
+        let dir = std::env::temp_dir().join(format!("pipelight-ts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("helper.ts"),
+            "export type Step = { name: string };\nexport const helper = (host: string): string => `deploy_${host}`;\n",
+        )
+        .unwrap();
+        fs::write(dir.join("pipelight.ts"), CONFIG).unwrap();
+
+        let res = Config::ts(dir.join("pipelight.ts").to_str().unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+        let config = res.unwrap();
+        let pipeline = &config.pipelines.unwrap()[0];
+        assert_eq!(pipeline.name, "deploy_localhost");
+    }
+    #[test]
+    fn runtime_remote_imports() {
+        let stripped =
+            strip_types("import { parse } from \"https://deno.land/std/flags/mod.ts\";\n");
+        assert_eq!(stripped.imports, ["https://deno.land/std/flags/mod.ts"]);
+        let stripped =
+            strip_types("import type { Config } from \"https://deno.land/x/pipelight/mod.ts\";\n");
+        assert!(stripped.imports.is_empty());
+    }
+    #[test]
+    fn nested_remote_imports() {
+        let dir = write_files(
+            "remote",
+            &[
+                (
+                    "pipelight.ts",
+                    "import helper from \"./lib/helper.ts\";\nexport default { pipelines: [helper] };\n",
+                ),
+                (
+                    "lib/helper.ts",
+                    "import { parse } from \"https://deno.land/std/flags/mod.ts\";\nimport config from \"../pipelight.ts\";\nexport default parse([]);\n",
+                ),
+            ],
+        );
+        let res = imports_remote_modules(&format!("{}/pipelight.ts", dir));
+        let local = imports_remote_modules(&format!("{}/lib/helper.ts", dir));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(res.unwrap());
+        assert!(local.unwrap());
+    }
+}
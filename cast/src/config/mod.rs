@@ -1,2 +1,6 @@
+pub mod cache;
+mod check;
+mod schema;
+pub mod interpolation;
 mod load;
 pub mod types;
@@ -0,0 +1,86 @@
+// Tests
+mod test;
+
+// Structs
+use crate::config::check::{CATCH_UP, MODES, PARAM_TYPES, STATUSES};
+use crate::Config;
+use utils::git::{Hook, Special};
+// Schema
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{ArrayValidation, InstanceType, RootSchema, Schema, SchemaObject};
+use schemars::schema_for;
+// Traits
+use strum::IntoEnumIterator;
+// Error Handling
+use miette::{IntoDiagnostic, Result};
+
+impl Config {
+    /**
+    Return the JSON Schema of the config file format,
+    to be used by editors for completion and validation.
+    */
+    pub fn schema() -> RootSchema {
+        schema_for!(Config)
+    }
+    pub fn schema_to_string() -> Result<String> {
+        serde_json::to_string_pretty(&Config::schema()).into_diagnostic()
+    }
+}
+
+/**
+Return every flag that can trigger a pipeline,
+special flags (manual, watch...) and git hooks.
+*/
+pub(crate) fn flags() -> Vec<String> {
+    Special::iter()
+        .map(|e| String::from(&e))
+        .chain(Hook::iter().map(|e| String::from(&e)))
+        .collect()
+}
+
+/// Schema of the step and parallel execution mode strings.
+pub(crate) fn mode(_: &mut SchemaGenerator) -> Schema {
+    enumeration(MODES.iter().map(|e| e.to_string()).collect())
+}
+
+/// Schema of the pipeline parameter types.
+pub(crate) fn param_type(_: &mut SchemaGenerator) -> Schema {
+    enumeration(PARAM_TYPES.iter().map(|e| e.to_string()).collect())
+}
+
+/// Schema of the scheduled triggers catch-up policies.
+pub(crate) fn catch_up(_: &mut SchemaGenerator) -> Schema {
+    enumeration(CATCH_UP.iter().map(|e| e.to_string()).collect())
+}
+
+/// Schema of the upstream statuses list of "after" triggers.
+pub(crate) fn statuses(_: &mut SchemaGenerator) -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Array.into()),
+        array: Some(Box::new(ArrayValidation {
+            items: Some(enumeration(STATUSES.iter().map(|e| e.to_string()).collect()).into()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    })
+}
+
+/// Schema of the trigger actions list.
+pub(crate) fn actions(_: &mut SchemaGenerator) -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Array.into()),
+        array: Some(Box::new(ArrayValidation {
+            items: Some(enumeration(flags()).into()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    })
+}
+
+fn enumeration(values: Vec<String>) -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(values.into_iter().map(|e| e.into()).collect()),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod schema {
+    use crate::Config;
+    use serde_json::Value;
+
+    #[test]
+    fn enumerate_modes_and_flags() {
+        let json = Config::schema_to_string().unwrap();
+        let schema: Value = serde_json::from_str(&json).unwrap();
+        let definitions = &schema["definitions"];
+        for name in ["Pipeline", "Step", "Parallel", "TriggerBranch", "StepOpts"] {
+            assert!(definitions.get(name).is_some(), "missing {}", name);
+        }
+        // Fallbacks are flattened into steps
+        assert!(definitions["Step"]["properties"]
+            .get("on_failure")
+            .is_some());
+        let modes = &definitions["StepOpts"]["properties"]["mode"]["enum"];
+        assert_eq!(modes, &serde_json::json!(["stop", "jump_next", "continue"]));
+        let actions = definitions["TriggerBranch"]["properties"]["actions"]["items"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(actions.contains(&"pre-push".into()));
+        assert!(actions.contains(&"manual".into()));
+    }
+}
@@ -2,18 +2,23 @@
 // Serde is the rust crate to that implements the logic
 // to parse structs from filepaths.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /**
 Options to tweak global pipelines behavior
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ConfigOpts {
     // Wheteher every pipelines should be attached or detached from the standard I/O
     // when triggered by a git hook.
     pub attach: Option<bool>,
     pub log_level: Option<String>,
+    // Quiet period that coalesces a burst of file changes into one watch trigger,
+    // ISO8601 ("PT0.5S") or human readable ("500ms")
+    pub watch_debounce: Option<String>,
 }
 
 /**
@@ -23,13 +28,23 @@ Yes, we could have load the pipeline list directly without the burden of encapsu
 But the config file isn't meant to stay as is.
 
 It is as is to let room for top level configuration that will come after **v1.0.1**.
-- boolean to declare global user pipelines accessible from everywhere in the fs.
 - eventually other optional things like credentials, daemon config...
 
+Global user pipelines, accessible from everywhere in the fs,
+are declared in a config file of the user config directory
+(~/.config/pipelight/pipelight.toml) and merged with the project ones.
+
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
+    // JSON Schema reference for editors, ignored by pipelight
+    #[serde(rename = "$schema")]
+    pub schema: Option<String>,
+    // Paths or globs of config files to merge, relative to this file
+    pub include: Option<Vec<String>>,
+    // Reusable steps, referenced by name with { use: <name> } in pipelines
+    pub templates: Option<Vec<Step>>,
     pub pipelines: Option<Vec<Pipeline>>,
     pub options: Option<ConfigOpts>,
 }
@@ -37,46 +52,116 @@ pub struct Config {
 /**
 Options to tweak pipelines behavior
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct PipelineOpts {
     // Wheteher the pipeline should be attached or detached from the standard I/O
     // when triggered by a git hook.
     pub attach: Option<bool>,
     pub log_level: Option<String>,
+    // Maximum pipeline duration, ISO8601 ("PT10M") or human readable ("10m")
+    pub timeout: Option<String>,
+    // Maximum number of steps running at once when steps have "needs"
+    pub concurrency: Option<usize>,
 }
 
 /**
 Pipelines are a named list of steps and parallel steps.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Pipeline {
     pub name: String,
+    // Name of the pipeline to inherit triggers, env, options and fallbacks from
+    pub extends: Option<String>,
     pub triggers: Option<Vec<Trigger>>,
+    // Environment variables passed to every step
+    pub env: Option<HashMap<String, String>>,
+    // Axis names and values, the pipeline is duplicated for every combination
+    pub matrix: Option<BTreeMap<String, Vec<String>>>,
+    // Parameters set on manual runs with "--param <name>=<value>"
+    pub params: Option<Vec<Param>>,
     pub steps: Vec<StepOrParallel>,
     #[serde(flatten)]
     pub fallback: Option<Fallback>,
     pub options: Option<PipelineOpts>,
 }
 
+/**
+A typed pipeline parameter.
+Parameters without a default value are required.
+*/
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Param {
+    pub name: String,
+    // One of "string" (default), "number" or "boolean"
+    #[serde(rename = "type")]
+    #[schemars(schema_with = "crate::config::schema::param_type")]
+    pub kind: Option<String>,
+    pub default: Option<serde_json::Value>,
+    // Allowed values
+    pub values: Option<Vec<serde_json::Value>>,
+    pub description: Option<String>,
+}
+
 /**
 Options to tweak step behavior and command execution
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 pub struct StepOpts {
     // The step's command execution behavior
+    #[schemars(schema_with = "crate::config::schema::mode")]
     pub mode: Option<String>,
+    // The step's working directory, relative to the config file directory
+    pub cwd: Option<String>,
+    // Maximum step duration, ISO8601 ("PT10M") or human readable ("10m")
+    pub timeout: Option<String>,
+    // How many times a failed command is re-run
+    pub retries: Option<u32>,
+    // Time to wait before re-running a failed command
+    pub retry_delay: Option<RetryDelay>,
+}
+
+/**
+The delay between command retries.
+Either a plain duration string for a fixed delay ("5s"),
+or a strategy object ({ exponential: "1s" }).
+*/
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum RetryDelay {
+    Fixed(String),
+    Strategy(RetryStrategy),
+}
+
+/**
+With an exponential strategy, the delay doubles on every attempt.
+*/
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryStrategy {
+    Fixed(String),
+    Exponential(String),
 }
 
 /**
 Steps are a named list of Commands.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Step {
     pub name: String,
     pub commands: Vec<String>,
+    // Names of the steps that must succeed before this one starts
+    pub needs: Option<Vec<String>>,
+    // Condition expression, the step is skipped when false
+    #[serde(rename = "if")]
+    pub condition: Option<String>,
+    // Environment variables, override the pipeline ones
+    pub env: Option<HashMap<String, String>>,
+    // Axis names and values, the step is duplicated for every combination
+    pub matrix: Option<BTreeMap<String, Vec<String>>>,
     pub options: Option<StepOpts>,
     #[serde(flatten)]
     pub fallback: Option<Fallback>,
@@ -85,16 +170,39 @@ pub struct Step {
 /**
 Parallel are unnamed list of steps.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Parallel {
     pub parallel: Vec<Step>,
+    // Condition expression, the parallel group is skipped when false
+    #[serde(rename = "if")]
+    pub condition: Option<String>,
     // pub options: Option<StepOpts>,
+    #[schemars(schema_with = "crate::config::schema::mode")]
     pub mode: Option<String>,
+    // Maximum parallel group duration
+    pub timeout: Option<String>,
+    // Environment variables passed to every parallel step
+    pub env: Option<HashMap<String, String>>,
     #[serde(flatten)]
     pub fallback: Option<Fallback>,
 }
 
+/**
+A step defined by a step template.
+The "with" parameters are passed to the step as environment variables,
+the "toolchain" parameter is available as the "TOOLCHAIN" variable.
+*/
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UseStep {
+    #[serde(rename = "use")]
+    pub template: String,
+    // Overrides the template name
+    pub name: Option<String>,
+    pub with: Option<BTreeMap<String, String>>,
+}
+
 /**
 The StepOrParallel enum is a conveninent enum designed for
 a pipeline to accept either steps and parallel steps.
@@ -102,12 +210,13 @@ As a  developer, I find it a bit of an overhead to use.
 But it is the simplest way I have found to make
 a usable **Union** (Step must be This type OR This type).
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(untagged)]
 #[serde(deny_unknown_fields)]
 pub enum StepOrParallel {
     Step(Step),
     Parallel(Parallel),
+    Use(UseStep),
 }
 
 /**
@@ -116,19 +225,20 @@ They are steps to be triggered on specific events.
 For example if a pipeline fails and if its on_failure fallback is defined
 the on_failure fallback is triggered.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Fallback {
     pub on_started: Option<Vec<StepOrParallel>>,
     pub on_failure: Option<Vec<StepOrParallel>>,
     pub on_success: Option<Vec<StepOrParallel>>,
     pub on_abortion: Option<Vec<StepOrParallel>>,
+    pub on_timeout: Option<Vec<StepOrParallel>>,
 }
 
 /**
 Triggers are casted into multiple types.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(untagged)]
 #[serde(deny_unknown_fields)]
 pub enum Trigger {
@@ -139,19 +249,56 @@ pub enum Trigger {
 /**
 A trigger that is a combination of actions over a git branch.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct TriggerBranch {
     pub branches: Option<Vec<String>>,
+    #[schemars(schema_with = "crate::config::schema::actions")]
     pub actions: Option<Vec<String>>,
+    // Only trigger when a changed file matches one of these globs
+    pub paths: Option<Vec<String>>,
+    // Do not trigger when every changed file matches these globs
+    pub paths_ignore: Option<Vec<String>>,
+    // Cron expression fired by the scheduler ("0 3 * * *")
+    pub schedule: Option<String>,
+    // What to do with the runs missed while the scheduler was down
+    #[schemars(schema_with = "crate::config::schema::catch_up")]
+    pub catch_up: Option<String>,
+    // Trigger on the completion of another pipeline
+    pub after: Option<TriggerAfter>,
 }
 
 /**
 A trigger that is a combination of actions over a git tag.
 */
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct TriggerTag {
     pub tags: Option<Vec<String>>,
+    #[schemars(schema_with = "crate::config::schema::actions")]
     pub actions: Option<Vec<String>>,
+    // Only trigger when a changed file matches one of these globs
+    pub paths: Option<Vec<String>>,
+    // Do not trigger when every changed file matches these globs
+    pub paths_ignore: Option<Vec<String>>,
+    // Cron expression fired by the scheduler ("0 3 * * *")
+    pub schedule: Option<String>,
+    // What to do with the runs missed while the scheduler was down
+    #[schemars(schema_with = "crate::config::schema::catch_up")]
+    pub catch_up: Option<String>,
+    // Trigger on the completion of another pipeline
+    pub after: Option<TriggerAfter>,
+}
+
+/**
+A trigger that fires when an upstream pipeline run ends.
+- pipeline: the upstream pipeline name, any pipeline if omitted,
+- status: the upstream statuses to fire on, any final status if omitted.
+*/
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TriggerAfter {
+    pub pipeline: Option<String>,
+    #[schemars(schema_with = "crate::config::schema::statuses")]
+    pub status: Option<Vec<String>>,
 }
@@ -1,7 +1,10 @@
 // Tests
 mod test;
 // Error Handling
-use miette::{Diagnostic, SourceOffset, SourceSpan};
+use miette::{
+    Diagnostic, LabeledSpan, NamedSource, Severity, SourceCode, SourceOffset, SourceSpan,
+};
+use std::fmt;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -15,6 +18,24 @@ pub enum CastError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     TomlError(#[from] TomlError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InterpolationError(#[from] InterpolationError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    JsError(#[from] JsError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    CheckReport(#[from] CheckReport),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    IncludeError(#[from] IncludeError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    IncludeCycleError(#[from] IncludeCycleError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    DuplicatePipelineError(#[from] DuplicatePipelineError),
 }
 
 /**
@@ -25,7 +46,7 @@ A json report type with hint, colors and code span for better pipeline debugging
 #[error("Serde: Could not convert Json into Rust types")]
 pub struct JsonError {
     #[source]
-    pub origin: serde_json::Error,
+    pub origin: Box<dyn std::error::Error + Send + Sync>,
     #[label("here")]
     pub at: SourceSpan,
     #[source_code]
@@ -44,7 +65,22 @@ impl JsonError {
                 1.into(),
             ),
             src: src.to_owned(),
-            origin: e,
+            origin: Box::new(e),
+        }
+    }
+    pub fn from_json5(e: json5::Error, src: &str) -> Self {
+        let json5::Error::Message { location, .. } = &e;
+        let at = match location {
+            Some(location) => SourceSpan::new(
+                SourceOffset::from_location(src, location.line, location.column),
+                1.into(),
+            ),
+            None => SourceSpan::new(0.into(), 0.into()),
+        };
+        JsonError {
+            at,
+            src: src.to_owned(),
+            origin: Box::new(e),
         }
     }
 }
@@ -128,3 +164,225 @@ impl TomlError {
         }
     }
 }
+
+/**
+An interpolation report type with a code span on the unknown variable
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(
+    code(cast::interpolation),
+    help("Use one of pipeline.name, pipeline.uuid, branch, tag, commit, action or env.NAME")
+)]
+#[error("Unknown variable \"{variable}\" in step \"{step}\"")]
+pub struct InterpolationError {
+    pub variable: String,
+    pub step: String,
+    #[label("here")]
+    pub at: SourceSpan,
+    #[source_code]
+    pub src: String,
+}
+impl InterpolationError {
+    /**
+    Point at the placeholder, at the given offset in the config file source.
+    */
+    pub fn new(variable: &str, step: &str, offset: Option<usize>, len: usize, src: &str) -> Self {
+        let at = match offset {
+            Some(offset) => SourceSpan::new(offset.into(), len.into()),
+            None => SourceSpan::new(0.into(), 0.into()),
+        };
+        InterpolationError {
+            variable: variable.to_owned(),
+            step: step.to_owned(),
+            at,
+            src: src.to_owned(),
+        }
+    }
+}
+
+/**
+A javascript runtime report type with a code span on the throwing line
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(code(cast::javascript))]
+#[error("Javascript: {message}")]
+pub struct JsError {
+    pub message: String,
+    #[label("here")]
+    pub at: SourceSpan,
+    #[source_code]
+    pub src: String,
+}
+#[cfg(feature = "quickjs")]
+impl JsError {
+    pub fn new(message: &str, src: &str, line: usize, column: usize) -> Self {
+        let at = if line > 0 && column > 0 {
+            SourceSpan::new(SourceOffset::from_location(src, line, column), 1.into())
+        } else {
+            SourceSpan::new(0.into(), 0.into())
+        };
+        JsError {
+            message: message.to_owned(),
+            at,
+            src: src.to_owned(),
+        }
+    }
+}
+
+/**
+A problem found by the config validation,
+with a severity and a code span on the faulty value
+*/
+#[derive(Error, Debug)]
+#[error("{message}")]
+pub struct CheckProblem {
+    pub message: String,
+    pub help: Option<String>,
+    pub severity: Severity,
+    pub at: Option<SourceSpan>,
+    pub src: String,
+}
+impl CheckProblem {
+    /**
+    Point at the span of the faulty value in the config file source, when known.
+    */
+    pub fn new(severity: Severity, message: &str, at: Option<SourceSpan>, src: &str) -> Self {
+        CheckProblem {
+            message: message.to_owned(),
+            help: None,
+            severity,
+            at,
+            src: src.to_owned(),
+        }
+    }
+    pub fn help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_owned());
+        self
+    }
+}
+impl Diagnostic for CheckProblem {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new("cast::check"))
+    }
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|e| Box::new(e) as Box<dyn fmt::Display + 'a>)
+    }
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.src)
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let at = self.at?;
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            Some("here".to_owned()),
+            at,
+        ))))
+    }
+}
+
+/**
+A report type that gathers every problem found by the config validation
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(code(cast::check))]
+#[error("Found {errors} error(s) and {warnings} warning(s) in the config file")]
+pub struct CheckReport {
+    pub errors: usize,
+    pub warnings: usize,
+    #[related]
+    pub problems: Vec<CheckProblem>,
+}
+impl CheckReport {
+    pub fn new(problems: Vec<CheckProblem>) -> Self {
+        let errors = problems
+            .iter()
+            .filter(|e| e.severity == Severity::Error)
+            .count();
+        CheckReport {
+            errors,
+            warnings: problems.len() - errors,
+            problems,
+        }
+    }
+}
+
+/**
+An include report type that names the included file the inner error comes from
+*/
+#[derive(Error, Debug)]
+#[error("In included file {file}")]
+pub struct IncludeError {
+    pub file: String,
+    pub origin: miette::Report,
+}
+impl IncludeError {
+    pub fn new(file: &str, origin: miette::Report) -> Self {
+        IncludeError {
+            file: file.to_owned(),
+            origin,
+        }
+    }
+}
+impl Diagnostic for IncludeError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new("cast::include"))
+    }
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        Some(&*self.origin)
+    }
+}
+
+/**
+An include report type for files that include themselves
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(
+    code(cast::include),
+    help("Remove one of the includes to break the cycle")
+)]
+#[error("Include cycle: {cycle}")]
+pub struct IncludeCycleError {
+    pub cycle: String,
+}
+
+/**
+A report type for a pipeline name defined in two config files
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(
+    code(cast::include),
+    help("Pipeline names must be unique across included files")
+)]
+#[error("The pipeline \"{name}\" is already defined in {other_file}")]
+pub struct DuplicatePipelineError {
+    pub name: String,
+    pub other_file: String,
+    #[label("duplicate")]
+    pub at: Option<SourceSpan>,
+    #[source_code]
+    pub src: NamedSource,
+}
+impl DuplicatePipelineError {
+    /**
+    Point at the name of the duplicate pipeline in the file source,
+    when the file format provides spans.
+    */
+    pub fn new(
+        name: &str,
+        file: &str,
+        other_file: &str,
+        at: Option<SourceSpan>,
+        src: &str,
+    ) -> Self {
+        DuplicatePipelineError {
+            name: name.to_owned(),
+            other_file: other_file.to_owned(),
+            at,
+            src: NamedSource::new(file, src.to_owned()),
+        }
+    }
+}
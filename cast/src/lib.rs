@@ -7,6 +7,9 @@ mod error;
 mod logs;
 
 // Re-export
+pub use config::cache::Cache;
+pub use error::{CheckProblem, CheckReport};
+pub use config::interpolation;
 pub use config::types::*;
 pub use logs::Logs;
 pub use logs::*;
@@ -0,0 +1,41 @@
+// Structs
+use cast::Config;
+use utils::error::LibError;
+use utils::teleport::Portal;
+// Error Handling
+use miette::{Report, Result};
+// Global vars
+use crate::globals::CLI;
+
+/**
+Load the config file and report every problem found in it.
+Only errors make the command fail, warnings are printed to stderr.
+*/
+pub fn launch() -> Result<()> {
+    let args = CLI.lock().unwrap().clone();
+    let seed = args.config.unwrap_or("pipelight".to_owned());
+    let mut portal = Portal::new()?;
+    portal.seed(&seed);
+    let portal = match portal.search() {
+        Ok(portal) => portal,
+        Err(e) => {
+            let message = "Could not find a configuration file";
+            let help = "Create a default configuration file: \"pipelight init --help\"";
+            return Err(LibError::new(message, help, e).into());
+        }
+    };
+    let file_path = portal.target.file_path.unwrap();
+    // Check the raw values first, for every problem to be reported at once
+    let warnings = match Config::validate(&file_path, args.raw.clone()) {
+        Ok(()) => None,
+        Err(report) if report.errors > 0 => return Err(report.into()),
+        Err(report) => Some(report),
+    };
+    // Then the conversion into the config types and the interpolations
+    Config::load(&file_path, args.raw)?;
+    match warnings {
+        Some(report) => eprintln!("{:?}", Report::new(report)),
+        None => println!("No problems found in {}", file_path),
+    }
+    Ok(())
+}
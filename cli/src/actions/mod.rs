@@ -1,10 +1,14 @@
 // Test
 // Actions
+pub mod check;
 pub mod logs;
 pub mod pipeline;
 pub mod run;
+pub mod schedule;
+pub mod secrets;
 pub mod stop;
 pub mod trigger;
 pub mod watch;
+pub mod webhook;
 // Utils
 pub mod prompt;
@@ -81,6 +81,10 @@ pub fn default() -> Result<()> {
     }
     // Retrieve pipelines defined in config and run logs.
     for pipeline in &config.pipelines.unwrap() {
+        let name = match pipeline.global {
+            true => format!("{} (global)", pipeline.name),
+            false => pipeline.name.to_owned(),
+        };
         let mut date = "".to_owned();
         let mut status = "".to_owned();
         let mut action = "".to_owned();
@@ -107,17 +111,17 @@ pub fn default() -> Result<()> {
             LevelFilter::Warn => {
                 warn!(target: "pipelines_nude",
                     "{:<15} {:<25} {:<40}\n",
-                    status, date, pipeline.name);
+                    status, date, name);
             }
             LevelFilter::Error => {
                 error!(target: "pipelines_nude",
                     "{:<40}\n",
-                     pipeline.name);
+                     name);
             }
             _ => {
                 info!(target: "pipelines_nude",
                     "{:<15} {:<15} {:<15} {:<25} {:<40}\n",
-                    status, action, branch, date, pipeline.name
+                    status, action, branch, date, name
                 );
             }
         }
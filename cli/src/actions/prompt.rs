@@ -1,8 +1,8 @@
 // Struct
 use exec::Status;
-use workflow::{pipeline::Filters, Getters, Logs, Pipeline};
+use workflow::{pipeline::Filters, Getters, Logs, ParamType, Pipeline};
 // Prompt
-use dialoguer::{console::Term, Select};
+use dialoguer::{console::Term, Input, Select};
 // Error Handling
 use miette::{Error, IntoDiagnostic, Result};
 
@@ -12,7 +12,13 @@ Displays a selet prompt and add the selected pipeline name to the global CLI.
 pub fn pipeline() -> Result<String> {
     // Get pipelines names
     let pipelines = Pipeline::get()?;
-    let items = pipelines.iter().map(|e| &e.name).collect::<Vec<&String>>();
+    let items = pipelines
+        .iter()
+        .map(|e| match e.global {
+            true => format!("{} (global)", e.name),
+            false => e.name.to_owned(),
+        })
+        .collect::<Vec<String>>();
 
     // Displays a select prompt with pipeline names.
     let selection = Select::new()
@@ -64,3 +70,49 @@ pub fn running_pipeline() -> Result<String> {
         }
     }
 }
+/**
+Displays a prompt for every required param of the pipeline
+that is missing from the run arguments.
+Returns the prompted params as "name=value" arguments.
+*/
+pub fn params(name: &str, args: &[String]) -> Result<Vec<String>> {
+    let pipeline = Pipeline::get_by_name(name)?;
+    let mut res = vec![];
+    for param in pipeline.missing_params(args) {
+        let prompt = match &param.description {
+            Some(description) => format!("{} ({})", param.name, description),
+            None => param.name.to_owned(),
+        };
+        let values = match (&param.values, &param.kind) {
+            (Some(values), _) => Some(values.to_owned()),
+            (None, ParamType::Boolean) => Some(vec!["true".to_owned(), "false".to_owned()]),
+            (None, _) => None,
+        };
+        let value = match values {
+            // Displays a select prompt with the allowed values.
+            Some(values) => {
+                let selection = Select::new()
+                    .with_prompt(&prompt)
+                    .items(&values)
+                    .default(0)
+                    .interact_on_opt(&Term::stderr())
+                    .into_diagnostic()?;
+                match selection {
+                    Some(index) => values[index].to_owned(),
+                    None => {
+                        let message = "User did not select anything";
+                        return Err(Error::msg(message));
+                    }
+                }
+            }
+            // Displays a text prompt, checking the value type.
+            None => Input::<String>::new()
+                .with_prompt(&prompt)
+                .validate_with(|input: &String| param.validate(input).map_err(|e| e.to_string()))
+                .interact_text_on(&Term::stderr())
+                .into_diagnostic()?,
+        };
+        res.push(format!("{}={}", param.name, value));
+    }
+    Ok(res)
+}
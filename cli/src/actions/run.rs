@@ -1,18 +1,30 @@
 // Struct
+use crate::services::{Action, FgBg, Service};
+use crate::types::{Commands, DetachableCommands, PostCommands};
 use exec::Status;
+use utils::git::{Flag, Special};
 use workflow::{Getters, Node, Pipeline};
+// Globals
+use crate::globals::CLI;
 // Error Handling
+use log::error;
 use miette::{Error, Result};
 use workflow::error::IsError;
 
-pub fn launch(name: &str) -> Result<()> {
+pub fn launch(name: &str, params: &[String]) -> Result<()> {
     // Guard
     let mut pipeline = Pipeline::get_by_name(name)?;
+    let values = pipeline.parse_params(params)?;
+    pipeline.set_params(values);
     if pipeline.is_triggerable()? {
         // Action
         pipeline.run()?;
         // Return pipeline log
         println!("{}", Node::from(&pipeline));
+        // The run status is reported even if the chained runs can't be launched
+        if let Err(e) = launch_downstream(&pipeline) {
+            error!("{}", e);
+        }
 
         match pipeline.status {
             Some(Status::Succeeded) => Ok(()),
@@ -20,6 +32,10 @@ pub fn launch(name: &str) -> Result<()> {
                 let message = "Pipeline status: Failed";
                 Err(Error::msg(message))
             }
+            Some(Status::TimedOut) => {
+                let message = "Pipeline status: Timed out";
+                Err(Error::msg(message))
+            }
             _ => Ok(()),
         }
     } else {
@@ -38,3 +54,32 @@ pub fn launch(name: &str) -> Result<()> {
         Err(IsError::new(message, &hint)?.into())
     }
 }
+
+/**
+Detach the runs of the pipelines chained to the ended pipeline run.
+They inherit the triggering env arguments and get the upstream run uuid.
+*/
+pub fn launch_downstream(pipeline: &Pipeline) -> Result<()> {
+    for downstream in pipeline.get_downstream()? {
+        let mut args = CLI.lock().unwrap().clone();
+        args.attach = false;
+        let mut trigger = match args.commands {
+            Commands::PostCommands(PostCommands::DetachableCommands(DetachableCommands::Run(
+                e,
+            ))) => e.trigger,
+            _ => crate::types::Pipeline::default().trigger,
+        };
+        trigger.flag = Some(String::from(&Flag::Special(Special::After)));
+        trigger.scheduled_at = None;
+        trigger.upstream = Some(pipeline.uuid.to_string());
+        args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
+            DetachableCommands::Run(crate::types::Pipeline {
+                name: Some(downstream.name),
+                trigger,
+                params: vec![],
+            }),
+        ));
+        Service::new(Action::Run, Some(args))?.should_detach()?;
+    }
+    Ok(())
+}
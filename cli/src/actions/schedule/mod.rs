@@ -0,0 +1,149 @@
+// Test
+mod test;
+// Structs
+use crate::services::{Action, FgBg, Service};
+use crate::types::{Commands, DetachableCommands, Pipeline, PostCommands, Trigger};
+use utils::dates::truncate;
+use utils::git::{Flag, Special};
+use workflow::Getters;
+// Dates
+use chrono::{DateTime, Duration, Local};
+// Filesystem
+use std::fs;
+use std::path::Path;
+use std::thread;
+// Env
+use std::env;
+// Process finder
+use exec::processes::Finder;
+// Globals
+use crate::globals::CLI;
+// Error handling
+use log::{info, trace};
+use miette::{IntoDiagnostic, Result};
+
+/**
+The file that stores the date of the latest scheduler tick,
+to catch up with the runs missed while the scheduler was down.
+*/
+pub const SCHEDULER_STATE: &str = ".pipelight/_internals/scheduler";
+
+#[derive(Debug)]
+pub struct Scheduler;
+
+impl Scheduler {
+    pub fn kill() -> Result<()> {
+        Scheduler::kill_homologous()?;
+        Ok(())
+    }
+    /**
+    Catch up with the runs missed since the latest tick,
+    then fire the scheduled triggers every minute a schedule is due.
+
+    Schedules are read from the config loaded on start,
+    the scheduler is to be restarted on config changes.
+    */
+    pub fn start() -> Result<()> {
+        let state = Path::new(SCHEDULER_STATE);
+        let now = Local::now();
+        if let Some(since) = Scheduler::get_latest_tick(state)? {
+            Scheduler::catch_up(&since, &now)?;
+        }
+        Scheduler::set_latest_tick(state, &truncate(&now))?;
+        loop {
+            // Wait for the next minute
+            let next = truncate(&Local::now()) + Duration::minutes(1);
+            if let Ok(delay) = (next - Local::now()).to_std() {
+                thread::sleep(delay);
+            }
+            Scheduler::tick(&next)?;
+            Scheduler::set_latest_tick(state, &next)?;
+        }
+    }
+    /**
+    Set the schedule flag and date to the triggering env and try to trigger pipelines.
+    */
+    fn tick(date: &DateTime<Local>) -> Result<()> {
+        trace!("scheduler tick at {}", date);
+        let mut is_due = false;
+        for pipeline in workflow::Pipeline::get()? {
+            is_due |= pipeline.is_scheduled_at(date)?;
+        }
+        if !is_due {
+            return Ok(());
+        }
+        let mut args = CLI.lock().unwrap().clone();
+        args.attach = false;
+        args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
+            DetachableCommands::Trigger(Trigger {
+                flag: Some(String::from(&Flag::Special(Special::Schedule))),
+                paths: vec![],
+                scheduled_at: Some(date.to_string()),
+                upstream: None,
+                branch: None,
+                tag: None,
+                commit: None,
+            }),
+        ));
+        Service::new(Action::Trigger, Some(args))?.should_detach()?;
+        Ok(())
+    }
+    /**
+    Run the pipelines scheduled between the latest tick and now,
+    according to their triggers catch-up policy.
+    */
+    fn catch_up(since: &DateTime<Local>, until: &DateTime<Local>) -> Result<()> {
+        for pipeline in workflow::Pipeline::get()? {
+            for date in pipeline.get_missed_schedules(since, until)? {
+                info!("catching up with {} scheduled at {}", pipeline.name, date);
+                let mut args = CLI.lock().unwrap().clone();
+                args.attach = false;
+                args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
+                    DetachableCommands::Run(Pipeline {
+                        name: Some(pipeline.name.clone()),
+                        trigger: Trigger {
+                            flag: Some(String::from(&Flag::Special(Special::Schedule))),
+                            paths: vec![],
+                            scheduled_at: Some(date.to_string()),
+                            upstream: None,
+                            branch: None,
+                            tag: None,
+                            commit: None,
+                        },
+                        params: vec![],
+                    }),
+                ));
+                Service::new(Action::Run, Some(args))?.should_detach()?;
+            }
+        }
+        Ok(())
+    }
+    fn get_latest_tick(path: &Path) -> Result<Option<DateTime<Local>>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let date = fs::read_to_string(path).into_diagnostic()?;
+        Ok(date.trim().parse::<DateTime<Local>>().ok())
+    }
+    fn set_latest_tick(path: &Path, date: &DateTime<Local>) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        fs::write(path, date.to_string()).into_diagnostic()?;
+        Ok(())
+    }
+    /**
+    Kill the schedulers already running on the current working directory.
+    Must not be called from a scheduler process,
+    its parent shell would match and the whole process group be killed.
+    */
+    pub fn kill_homologous() -> Result<()> {
+        Finder::new()
+            .cwd(env::current_dir().into_diagnostic()?.to_str().unwrap())
+            .seed("pipelight")
+            .seed("scheduler")
+            .search()?
+            .kill()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(test)]
+mod scheduler {
+    use crate::actions::schedule::Scheduler;
+    use chrono::Local;
+    use std::{env, fs, process};
+    use utils::dates::truncate;
+    // Error handling
+    use miette::{IntoDiagnostic, Result};
+
+    #[test]
+    fn latest_tick() -> Result<()> {
+        let dir = env::temp_dir().join(format!("pipelight-scheduler-{}", process::id()));
+        let path = dir.join("_internals/scheduler");
+        let now = truncate(&Local::now());
+        Scheduler::set_latest_tick(&path, &now)?;
+        assert_eq!(Scheduler::get_latest_tick(&path)?, Some(now));
+        fs::remove_dir_all(&dir).into_diagnostic()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+// Types
+use utils::secrets::Secrets;
+// Standard I/O
+use std::io::{stdin, BufRead};
+// Error Handling
+use miette::{Error, IntoDiagnostic, Result};
+
+/**
+Add or replace a project secret.
+The value is read from the standard input when not provided,
+to keep it out of the shell history.
+*/
+pub fn set(name: &str, value: Option<String>) -> Result<()> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            let mut line = String::new();
+            stdin().lock().read_line(&mut line).into_diagnostic()?;
+            line.trim_end_matches(['\n', '\r']).to_owned()
+        }
+    };
+    let mut secrets = Secrets::load()?;
+    secrets.set(name, &value);
+    secrets.save()?;
+    Ok(())
+}
+
+/**
+Print a project secret value.
+*/
+pub fn get(name: &str) -> Result<()> {
+    let secrets = Secrets::load()?;
+    match secrets.get(name) {
+        Some(value) => {
+            println!("{}", value);
+            Ok(())
+        }
+        None => Err(Error::msg(format!("Couldn't find a secret named {:?}", name))),
+    }
+}
+
+/**
+Remove a project secret.
+*/
+pub fn rm(name: &str) -> Result<()> {
+    let mut secrets = Secrets::load()?;
+    match secrets.remove(name) {
+        Some(_) => secrets.save(),
+        None => Err(Error::msg(format!("Couldn't find a secret named {:?}", name))),
+    }
+}
+
+/**
+List the project secret names.
+*/
+pub fn ls() -> Result<()> {
+    let secrets = Secrets::load()?;
+    for name in secrets.names() {
+        println!("{}", name);
+    }
+    Ok(())
+}
@@ -1,15 +1,27 @@
+// Struct
+use crate::actions::run;
+use exec::Status;
 use workflow::Logs;
 // Error Handling
+use log::error;
 use miette::Result;
 
 /**
 Stop the pipeline and its attached subprocesses.
+The stopped runs can't chain their downstream pipelines anymore,
+they are launched from here.
 */
 pub fn launch(pipeline_name: &str) -> Result<()> {
     // Get pipelines from the provided name.
     let pipelines = Logs::get_many_by_name(pipeline_name)?;
     for mut pipeline in pipelines {
+        let is_running = pipeline.event.is_some() && pipeline.status == Some(Status::Running);
         pipeline.stop()?;
+        if is_running {
+            if let Err(e) = run::launch_downstream(&pipeline) {
+                error!("{}", e);
+            }
+        }
     }
     Ok(())
 }
@@ -3,6 +3,7 @@ use crate::services::types::{Action, Service};
 use crate::types::{Commands, DetachableCommands, Pipeline, PostCommands, Trigger};
 use crate::verbosity::external::level_value;
 use crate::verbosity::external::Verbosity;
+use utils::git::{Flag, Git, Special};
 
 use workflow;
 // Traits
@@ -18,6 +19,24 @@ use miette::Result;
 pub fn launch(trigger: &Trigger) -> Result<()> {
     let mut pipelines = workflow::Pipeline::get()?;
     let config = workflow::Config::get()?;
+    let env = workflow::Trigger::get()?;
+    let is_watch = env.get_action()? == Some(Flag::Special(Special::Watch));
+
+    // The changed files may not be computable by the runs (pre-push hook stdin),
+    // pass them on as absolute paths.
+    let mut trigger = trigger.to_owned();
+    if let Some(changed) = env.get_changed()? {
+        trigger.paths = changed.iter().map(|e| Git::new().get_absolute(e)).collect();
+    }
+
+    // Restart the pipelines on new file changes
+    if is_watch {
+        for pipeline in &pipelines {
+            if pipeline.is_triggerable_strict()? {
+                pipeline.stop_watch_runs()?;
+            }
+        }
+    }
 
     pipelines.par_iter_mut().for_each(|pipeline| {
         // Guard
@@ -51,8 +70,9 @@ pub fn launch(trigger: &Trigger) -> Result<()> {
             }
             args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
                 DetachableCommands::Run(Pipeline {
-                    trigger: trigger.to_owned(),
+                    trigger: trigger.clone(),
                     name: Some(pipeline.name.clone()),
+                    params: vec![],
                 }),
             ));
             Service::new(Action::Run, Some(args))
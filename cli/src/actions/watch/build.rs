@@ -51,6 +51,10 @@ pub async fn build() -> Result<(Arc<Watchexec>, RuntimeConfig)> {
   runtime.filterer(Arc::new(filterer));
   // Watch cwd only
   runtime.pathset(vec![env::current_dir().unwrap()]);
+  // Coalesce a burst of file changes into a single trigger
+  if let Some(debounce) = workflow::Config::get()?.get_watch_debounce()? {
+    runtime.action_throttle(debounce);
+  }
 
   // Create WE instance
   let watchexec = Watchexec::new(init, runtime.clone()).unwrap();
@@ -65,14 +69,14 @@ pub async fn build() -> Result<(Arc<Watchexec>, RuntimeConfig)> {
       // Self reconfigure on ignore file change
       reconfigure(&w_clone, &r_clone, &action, ignore_path).await.unwrap();
       // Pipeline execution
-      watch_trigger().unwrap();
+      watch_trigger(&action).unwrap();
       // Handle Stop signals
 			let sigs = action
 				.events
 				.iter()
 				.flat_map(Event::signals)
 				.collect::<Vec<_>>();
-      if sigs.iter().any(|sig| sig == &Signal::Interrupt) {
+      if sigs.iter().any(|sig| sig == &Signal::Interrupt || sig == &Signal::Terminate) {
         action.outcome(Outcome::Exit);
       } else{
         action.outcome(Outcome::if_running(
@@ -125,13 +129,37 @@ pub async fn filter_configuration(path: &str)-> Result<IgnoreFilterer> {
 }
 
 /**
-Set the watch flag to the triggering env and try to trigger pipelines.
+Set the watch flag and the changed paths to the triggering env
+and try to trigger pipelines.
 */
-pub fn watch_trigger() -> Result<()> {
+pub fn watch_trigger(action: &Action) -> Result<()> {
   let flag = Some(String::from(&Flag::Special(Special::Watch)));
+  // Changed paths relative to the working directory
+  let cwd = env::current_dir().into_diagnostic()?;
+  let mut paths: Vec<String> = vec![];
+  for (path, _) in action.events.iter().flat_map(|e| e.paths()) {
+    let path = path.strip_prefix(&cwd).unwrap_or(path).to_string_lossy().to_string();
+    if !paths.contains(&path) {
+      paths.push(path);
+    }
+  }
+  // Signals carry no file change
+  if paths.is_empty() {
+    return Ok(());
+  }
   let mut args = CLI.lock().unwrap().clone();
+  // Never block the watcher, newer changes restart running pipelines
+  args.attach = false;
   args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
-      DetachableCommands::Trigger(Trigger { flag }),
+      DetachableCommands::Trigger(Trigger {
+          flag,
+          paths,
+          scheduled_at: None,
+          upstream: None,
+          branch: None,
+          tag: None,
+          commit: None,
+      }),
   ));
   Service::new(services::Action::Trigger, Some(args))?.should_detach()?;
   Ok(())
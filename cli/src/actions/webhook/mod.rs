@@ -0,0 +1,386 @@
+/**
+A minimal webhook receiver that triggers pipelines on forge push events.
+
+Accepts the GitHub and Gitea/Forgejo/Gogs push payloads (branches and tags),
+signed with HMAC-SHA256 and the project secret given to the server.
+
+```sh
+pipelight secrets set WEBHOOK_SECRET
+pipelight serve-webhooks --listen 127.0.0.1:8080
+```
+*/
+// Test
+mod test;
+// Structs
+use crate::actions::trigger;
+use crate::types::Trigger;
+use std::collections::BTreeMap;
+use utils::git::{Flag, Git, Special};
+use utils::secrets::Secrets;
+// Network
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+// Signature
+use sha2::{Digest, Sha256};
+// Serde
+use serde_json::Value;
+// Error handling
+use log::{info, warn};
+use miette::{Diagnostic, Error, IntoDiagnostic, Result};
+use thiserror::Error;
+
+/**
+The default project secret that holds the webhook signing key.
+*/
+pub const SECRET: &str = "WEBHOOK_SECRET";
+
+// Requests bigger than this are rejected.
+const MAX_REQUEST_SIZE: u64 = 10 * 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/**
+The headers that carry the signature, ordered by forge.
+GitHub prefixes the hexadecimal signature with "sha256=".
+*/
+const SIGNATURE_HEADERS: [&str; 4] = [
+    "x-hub-signature-256",
+    "x-gitea-signature",
+    "x-forgejo-signature",
+    "x-gogs-signature",
+];
+const EVENT_HEADERS: [&str; 4] = [
+    "x-github-event",
+    "x-gitea-event",
+    "x-forgejo-event",
+    "x-gogs-event",
+];
+
+#[derive(Debug)]
+pub struct Server {
+    pub listen: String,
+    secret: String,
+}
+
+impl Server {
+    /**
+    Create a server that verifies the payloads with the given project secret.
+    */
+    pub fn new(listen: &str, secret: &str) -> Result<Server> {
+        let value = Secrets::load()?.get(secret).ok_or_else(|| {
+            let message = format!(
+                "The webhook secret {} is not set, add it with \"pipelight secrets set {}\"",
+                secret, secret
+            );
+            Error::msg(message)
+        })?;
+        Ok(Server {
+            listen: listen.to_owned(),
+            secret: value,
+        })
+    }
+    /**
+    Listen for webhooks and handle them one at a time.
+    A bad request is answered and logged but never stops the server.
+    */
+    pub fn start(&self) -> Result<()> {
+        let listener = TcpListener::bind(&self.listen).into_diagnostic()?;
+        info!(
+            "Listening for webhooks on http://{}",
+            listener.local_addr().into_diagnostic()?
+        );
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("{}", e);
+                    continue;
+                }
+            };
+            let (status, body) = match self.handle(&stream) {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("{}", e);
+                    (400, e.to_string())
+                }
+            };
+            if let Err(e) = respond(&mut stream, status, &body) {
+                warn!("{}", e);
+            }
+        }
+        Ok(())
+    }
+    /**
+    Read a request, check its signature and trigger pipelines on push events.
+    Returns the response status and body.
+    */
+    fn handle(&self, stream: &TcpStream) -> Result<(u16, String)> {
+        stream.set_read_timeout(Some(TIMEOUT)).into_diagnostic()?;
+        stream.set_write_timeout(Some(TIMEOUT)).into_diagnostic()?;
+        let mut reader = BufReader::new(stream.take(MAX_REQUEST_SIZE));
+        let request = match Request::read(&mut reader) {
+            Err(e) if e.downcast_ref::<PayloadTooLarge>().is_some() => {
+                warn!("{}", e);
+                return Ok((413, e.to_string()));
+            }
+            request => request?,
+        };
+
+        if request.method != "POST" {
+            return Ok((405, "Only POST requests are accepted".to_owned()));
+        }
+        if !request.verify_signature(&self.secret) {
+            warn!("Rejected a webhook with a missing or bad signature");
+            return Ok((401, "Bad signature".to_owned()));
+        }
+        match request.event().as_deref() {
+            Some("ping") => Ok((200, "pong".to_owned())),
+            Some("push") => match Push::from_payload(&request.body)? {
+                Some(push) => {
+                    info!("Received a push on {}", push);
+                    push.launch()?;
+                    Ok((202, format!("Triggered pipelines on {}", push)))
+                }
+                None => Ok((200, "Ignored ref".to_owned())),
+            },
+            _ => Ok((200, "Ignored event".to_owned())),
+        }
+    }
+}
+
+/**
+The parts of an HTTP request a webhook needs.
+Header names are lowercased.
+*/
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/**
+A request announcing a body bigger than the accepted size.
+*/
+#[derive(Debug, Error, Diagnostic)]
+#[error("The request body of {length} bytes is bigger than the {MAX_REQUEST_SIZE} bytes accepted")]
+pub struct PayloadTooLarge {
+    pub length: u64,
+}
+
+impl Request {
+    /**
+    Read the request line, the headers and the body.
+    Bodies bigger than the accepted size are rejected before being read.
+    */
+    pub fn read<R: BufRead>(reader: &mut R) -> Result<Request> {
+        let mut line = String::new();
+        reader.read_line(&mut line).into_diagnostic()?;
+        let method = match line.split_whitespace().next() {
+            Some(method) => method.to_owned(),
+            None => return Err(Error::msg("Empty request")),
+        };
+        let mut headers = BTreeMap::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).into_diagnostic()? == 0 {
+                break;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+            }
+        }
+        let length = match headers.get("content-length") {
+            Some(length) => length.parse::<u64>().into_diagnostic()?,
+            None => 0,
+        };
+        // Don't allocate for bodies the reader would truncate anyway
+        if length > MAX_REQUEST_SIZE {
+            return Err(PayloadTooLarge { length }.into());
+        }
+        let mut body = vec![0; length as usize];
+        reader.read_exact(&mut body).into_diagnostic()?;
+        Ok(Request {
+            method,
+            headers,
+            body,
+        })
+    }
+    /**
+    Returns the forge event name (push, ping...).
+    */
+    pub fn event(&self) -> Option<String> {
+        EVENT_HEADERS
+            .iter()
+            .find_map(|header| self.headers.get(*header))
+            .map(|e| e.to_lowercase())
+    }
+    /**
+    Compare the body HMAC-SHA256 with the signature header in constant time.
+    */
+    pub fn verify_signature(&self, secret: &str) -> bool {
+        let signature = match SIGNATURE_HEADERS
+            .iter()
+            .find_map(|header| self.headers.get(*header))
+        {
+            Some(signature) => signature,
+            None => return false,
+        };
+        let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+        let signature = match hex::decode(signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        let expected = hmac_sha256(secret.as_bytes(), &self.body);
+        signature.len() == expected.len()
+            && signature
+                .iter()
+                .zip(expected.iter())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+/**
+A pushed branch or tag.
+*/
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Push {
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+    pub commit: Option<String>,
+    // The files changed by the pushed commits
+    pub paths: Vec<String>,
+}
+
+impl std::fmt::Display for Push {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.branch, &self.tag) {
+            (_, Some(tag)) => write!(f, "tag {}", tag),
+            (Some(branch), _) => write!(f, "branch {}", branch),
+            _ => write!(f, "unknown ref"),
+        }
+    }
+}
+
+impl Push {
+    /**
+    Read a push payload.
+    Returns None on ref deletions and on refs that are neither branches nor tags.
+    */
+    pub fn from_payload(body: &[u8]) -> Result<Option<Push>> {
+        let payload: Value = serde_json::from_slice(body).into_diagnostic()?;
+        let git_ref = match payload["ref"].as_str() {
+            Some(git_ref) => git_ref,
+            None => return Err(Error::msg("The push payload has no ref")),
+        };
+        // A deleted ref points to the null commit
+        let commit = payload["after"]
+            .as_str()
+            .filter(|e| !e.chars().all(|c| c == '0'))
+            .map(str::to_owned);
+        if commit.is_none() || payload["deleted"].as_bool() == Some(true) {
+            return Ok(None);
+        }
+        let mut push = Push {
+            commit,
+            ..Push::default()
+        };
+        if let Some(branch) = git_ref.strip_prefix("refs/heads/") {
+            push.branch = Some(branch.to_owned());
+        } else if let Some(tag) = git_ref.strip_prefix("refs/tags/") {
+            push.tag = Some(tag.to_owned());
+        } else {
+            return Ok(None);
+        }
+        for commit in payload["commits"].as_array().into_iter().flatten() {
+            for key in ["added", "removed", "modified"] {
+                let files = commit[key].as_array().into_iter().flatten();
+                push.paths
+                    .extend(files.filter_map(|e| e.as_str()).map(str::to_owned));
+            }
+        }
+        push.paths.sort();
+        push.paths.dedup();
+        Ok(Some(push))
+    }
+    /**
+    Set the push to the triggering env and trigger the matching pipelines.
+    The ref is also passed to the runs as it may differ from the local one.
+    The pushed paths are relative to the repository root.
+    */
+    pub fn launch(&self) -> Result<()> {
+        let paths = if self.paths.is_empty() {
+            None
+        } else {
+            Some(
+                self.paths
+                    .iter()
+                    .map(|e| Git::new().get_absolute(e))
+                    .collect(),
+            )
+        };
+        workflow::Trigger::set(Some(Flag::Special(Special::Webhook)), paths)?;
+        workflow::Trigger::set_ref(self.branch.clone(), self.tag.clone(), self.commit.clone())?;
+        trigger::launch(&Trigger {
+            flag: Some(String::from(&Flag::Special(Special::Webhook))),
+            paths: self.paths.clone(),
+            scheduled_at: None,
+            upstream: None,
+            branch: self.branch.clone(),
+            tag: self.tag.clone(),
+            commit: self.commit.clone(),
+        })?;
+        Ok(())
+    }
+}
+
+/**
+Compute the HMAC-SHA256 (RFC 2104) of a message.
+*/
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let ipad: Vec<u8> = block.iter().map(|e| e ^ 0x36).collect();
+    let opad: Vec<u8> = block.iter().map(|e| e ^ 0x5c).collect();
+    let inner = Sha256::new()
+        .chain_update(ipad)
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(opad)
+        .chain_update(inner)
+        .finalize()
+        .to_vec()
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: &str) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Bad Request",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+    .into_diagnostic()?;
+    stream.flush().into_diagnostic()?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod webhook {
+    use crate::actions::webhook::{hmac_sha256, PayloadTooLarge, Push, Request};
+    use std::io::BufReader;
+    // Error handling
+    use miette::Result;
+
+    fn post(headers: &[(&str, &str)], body: &str) -> Result<Request> {
+        let mut raw = "POST /webhook HTTP/1.1\r\nHost: localhost\r\n".to_owned();
+        for (name, value) in headers {
+            raw += &format!("{}: {}\r\n", name, value);
+        }
+        raw += &format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        Request::read(&mut BufReader::new(raw.as_bytes()))
+    }
+
+    #[test]
+    fn hmac_rfc4231() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+    #[test]
+    fn read_request() -> Result<()> {
+        let request = post(&[("X-Gitea-Event", "push")], "{}")?;
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.event(), Some("push".to_owned()));
+        assert_eq!(request.body, b"{}");
+        Ok(())
+    }
+    #[test]
+    fn too_large_request() {
+        let raw = "POST /webhook HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n{}";
+        let err = Request::read(&mut BufReader::new(raw.as_bytes())).unwrap_err();
+        assert!(err.downcast_ref::<PayloadTooLarge>().is_some());
+    }
+    #[test]
+    fn verify_signatures() -> Result<()> {
+        let body = r#"{"ref":"refs/heads/main"}"#;
+        let signature = hex::encode(hmac_sha256(b"secret", body.as_bytes()));
+
+        let github = format!("sha256={}", signature);
+        let request = post(&[("X-Hub-Signature-256", &github)], body)?;
+        assert!(request.verify_signature("secret"));
+        assert!(!request.verify_signature("other"));
+
+        let request = post(&[("X-Forgejo-Signature", &signature)], body)?;
+        assert!(request.verify_signature("secret"));
+
+        let request = post(&[("X-Gitea-Signature", "not-hex")], body)?;
+        assert!(!request.verify_signature("secret"));
+        let request = post(&[], body)?;
+        assert!(!request.verify_signature("secret"));
+        Ok(())
+    }
+    #[test]
+    fn branch_payload() -> Result<()> {
+        let body = r#"{
+            "ref": "refs/heads/main",
+            "after": "9c1e2a",
+            "commits": [
+                {"added": ["src/new.rs"], "removed": [], "modified": ["README.md"]},
+                {"added": [], "removed": ["src/old.rs"], "modified": ["README.md"]}
+            ]
+        }"#;
+        let push = Push::from_payload(body.as_bytes())?;
+        assert_eq!(
+            push,
+            Some(Push {
+                branch: Some("main".to_owned()),
+                tag: None,
+                commit: Some("9c1e2a".to_owned()),
+                paths: vec![
+                    "README.md".to_owned(),
+                    "src/new.rs".to_owned(),
+                    "src/old.rs".to_owned()
+                ],
+            })
+        );
+        Ok(())
+    }
+    #[test]
+    fn tag_payload() -> Result<()> {
+        let body = r#"{"ref": "refs/tags/v1.0.0", "after": "9c1e2a", "commits": []}"#;
+        let push = Push::from_payload(body.as_bytes())?.unwrap();
+        assert_eq!(push.tag, Some("v1.0.0".to_owned()));
+        assert_eq!(push.branch, None);
+        Ok(())
+    }
+    #[test]
+    fn ignored_payloads() -> Result<()> {
+        let deleted = r#"{"ref": "refs/heads/old", "after": "0000000000", "deleted": true}"#;
+        assert_eq!(Push::from_payload(deleted.as_bytes())?, None);
+        let note = r#"{"ref": "refs/notes/commits", "after": "9c1e2a"}"#;
+        assert_eq!(Push::from_payload(note.as_bytes())?, None);
+        assert!(Push::from_payload(b"{}").is_err());
+        Ok(())
+    }
+}
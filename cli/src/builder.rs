@@ -50,6 +50,7 @@ impl Cli {
                         PossibleValue::new("javascript"),
                         PossibleValue::new("toml"),
                         PossibleValue::new("yaml"),
+                        PossibleValue::new("json"),
                     ])
                 })
             });
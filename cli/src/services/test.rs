@@ -6,6 +6,8 @@ mod service {
     use crate::{Cli, Commands, DetachableCommands, PostCommands};
     // Traits
     use crate::services::traits::FgBg;
+    // Test project
+    use crate::test::project::TempProject;
     // Error Handling
     use miette::{IntoDiagnostic, Result};
 
@@ -42,9 +44,12 @@ mod service {
             )),
             ..Cli::default()
         });
+        let project = TempProject::new("service")?;
         // println!("{:#?}", args);
         if let Some(ref mut args) = args {
             args.attach = true;
+            // Detached runs teleport to the temporary config directory, not the repository
+            args.config = Some(project.config());
         }
         let service = Service::new(Action::Run, args)?;
         service.detach()?;
@@ -59,9 +64,12 @@ mod service {
             )),
             ..Cli::default()
         });
+        let project = TempProject::new("watcher")?;
         // println!("{:#?}", args);
         if let Some(ref mut args) = args {
             args.attach = true;
+            // Detached runs teleport to the temporary config directory, not the repository
+            args.config = Some(project.config());
         }
         let service = Service::new(Action::Watch, args)?;
         service.detach()?;
@@ -18,9 +18,18 @@ impl Parser for Service {
     fn convert(&mut self) -> Result<()> {
         // Default arguments
         // Trigger options
-        let mut flag = Some(String::from(&Flag::default()));
+        let mut trigger = Trigger {
+            flag: Some(String::from(&Flag::default())),
+            paths: vec![],
+            scheduled_at: None,
+            upstream: None,
+            branch: None,
+            tag: None,
+            commit: None,
+        };
         // Run options
         let mut name = None;
+        let mut params = vec![];
 
         // Retrieve reusable arguments and mutate the defaults
         if let Some(args) = self.args.clone() {
@@ -28,12 +37,13 @@ impl Parser for Service {
                 args.commands
             {
                 match detachable_commands {
-                    DetachableCommands::Trigger(trigger) => {
-                        flag = trigger.flag;
+                    DetachableCommands::Trigger(e) => {
+                        trigger = e;
                     }
                     DetachableCommands::Run(pipeline) => {
-                        flag = pipeline.trigger.flag;
+                        trigger = pipeline.trigger;
                         name = pipeline.name;
+                        params = pipeline.params;
                     }
                     _ => {}
                 }
@@ -46,8 +56,9 @@ impl Parser for Service {
                 if let Some(ref mut args) = self.args {
                     args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
                         DetachableCommands::Run(Pipeline {
-                            trigger: Trigger { flag },
+                            trigger,
                             name,
+                            params,
                         }),
                     ));
                 }
@@ -55,7 +66,7 @@ impl Parser for Service {
             Action::Trigger => {
                 if let Some(ref mut args) = self.args {
                     args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
-                        DetachableCommands::Trigger(Trigger { flag }),
+                        DetachableCommands::Trigger(trigger),
                     ))
                 }
             }
@@ -66,6 +77,13 @@ impl Parser for Service {
                     ))
                 }
             }
+            Action::Schedule => {
+                if let Some(ref mut args) = self.args {
+                    args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
+                        DetachableCommands::Scheduler,
+                    ))
+                }
+            }
         };
         Ok(())
     }
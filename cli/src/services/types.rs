@@ -9,6 +9,7 @@ pub enum Action {
     Run,
     Trigger,
     Watch,
+    Schedule,
 }
 
 /**
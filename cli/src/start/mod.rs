@@ -1,10 +1,13 @@
 // Struct
-use crate::actions::{logs, pipeline, prompt, run, stop, trigger, watch};
+use crate::actions::{
+    check, logs, pipeline, prompt, run, schedule, secrets, stop, trigger, watch, webhook,
+};
 use crate::services::types::{Action, Service};
 use crate::types::Cli;
-use crate::types::{ColoredOutput, LogsCommands, ToggleCommands};
+use crate::types::{ColoredOutput, LogsCommands, SecretsCommands, ToggleCommands};
 use crate::types::{Commands, DetachableCommands, PostCommands, PreCommands};
 use utils::git::Hook;
+use workflow::Getters;
 // Clap
 use clap::ValueEnum;
 use clap_complete::shells::Shell;
@@ -51,6 +54,8 @@ impl PreCommands {
                 // Create gitignore file
                 template.create_ignore()?;
             }
+            PreCommands::Check => check::launch()?,
+            PreCommands::Schema => println!("{}", cast::Config::schema_to_string()?),
             PreCommands::Enable(e) => {
                 if let Some(commands) = e.commands.clone() {
                     match commands {
@@ -59,6 +64,14 @@ impl PreCommands {
                             let service = Service::new(Action::Watch, None)?;
                             service.detach()?;
                         }
+                        ToggleCommands::Scheduler => {
+                            // Kill already running scheduler
+                            schedule::Scheduler::kill()?;
+                            let mut args = CLI.lock().unwrap().clone();
+                            args.attach = true;
+                            let service = Service::new(Action::Schedule, Some(args))?;
+                            service.detach()?;
+                        }
                     }
                 }
             }
@@ -67,6 +80,7 @@ impl PreCommands {
                     match commands {
                         ToggleCommands::GitHooks => Hook::disable()?,
                         ToggleCommands::Watcher => watch::Watcher::kill()?,
+                        ToggleCommands::Scheduler => schedule::Scheduler::kill()?,
                     }
                 }
             }
@@ -82,19 +96,29 @@ impl DetachableCommands {
             DetachableCommands::Run(e) => {
                 if e.name.is_none() {
                     e.name = Some(prompt::pipeline()?);
+                    // Ask for the required params that are not set
+                    let missing = prompt::params(&e.name.clone().unwrap(), &e.params)?;
+                    e.params.extend(missing);
                     args.commands = Commands::PostCommands(PostCommands::DetachableCommands(
                         DetachableCommands::Run(e.to_owned()),
                     ))
                 }
+                // Check the params before detaching
+                let pipeline = workflow::Pipeline::get_by_name(&e.name.clone().unwrap())?;
+                pipeline.parse_params(&e.params)?;
                 match args.attach {
                     false => Service::new(Action::Run, Some(args))?.should_detach()?,
-                    true => run::launch(&e.name.clone().unwrap())?,
+                    true => run::launch(&e.name.clone().unwrap(), &e.params)?,
                 }
             }
             DetachableCommands::Watch => match args.attach {
                 false => Service::new(Action::Watch, Some(args))?.should_detach()?,
                 true => watch::Watcher::start()?,
             },
+            DetachableCommands::Scheduler => match args.attach {
+                false => Service::new(Action::Schedule, Some(args))?.should_detach()?,
+                true => schedule::Scheduler::start()?,
+            },
             DetachableCommands::Trigger(trigger) => trigger::launch(trigger)?,
         }
         Ok(())
@@ -156,6 +180,15 @@ impl PostCommands {
                     pipeline::inspect(&name, e.json)?;
                 }
             }
+            PostCommands::Secrets(e) => match &e.commands {
+                SecretsCommands::Set(secret) => secrets::set(&secret.name, secret.value.clone())?,
+                SecretsCommands::Get(secret) => secrets::get(&secret.name)?,
+                SecretsCommands::Rm(secret) => secrets::rm(&secret.name)?,
+                SecretsCommands::Ls => secrets::ls()?,
+            },
+            PostCommands::ServeWebhooks(e) => {
+                webhook::Server::new(&e.listen, &e.secret)?.start()?;
+            }
             PostCommands::DetachableCommands(e) => {
                 e.clone().start()?;
             }
@@ -1,8 +1,45 @@
 #[cfg(test)]
+pub mod project {
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+    // Error Handling
+    use miette::{IntoDiagnostic, Result};
+
+    /**
+    A temporary directory with a copy of the test config,
+    so that runs log there instead of in the repository.
+    The directory is removed when dropped.
+    */
+    pub struct TempProject {
+        pub dir: PathBuf,
+    }
+    impl TempProject {
+        pub fn new(name: &str) -> Result<TempProject> {
+            let dir = env::temp_dir().join(format!("pipelight-cli-{}-{}", name, process::id()));
+            fs::create_dir_all(&dir).into_diagnostic()?;
+            let config = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../test.pipelight.ts");
+            fs::copy(config, dir.join("test.pipelight.ts")).into_diagnostic()?;
+            Ok(TempProject { dir })
+        }
+        /**
+        Returns the path of the config file copy.
+        */
+        pub fn config(&self) -> String {
+            self.dir.join("test.pipelight.ts").display().to_string()
+        }
+    }
+    impl Drop for TempProject {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+#[cfg(test)]
 mod cli {
     // Error Handling
     use miette::{IntoDiagnostic, Result};
 
+    use super::project::TempProject;
     use assert_cmd::prelude::*; // Add methods on commands
     use std::process::Command; // Run commnds
 
@@ -20,8 +57,10 @@ mod cli {
     #[test]
     /// Run simple pipeline
     fn run_pipeline() -> Result<()> {
+        let project = TempProject::new("run")?;
         let mut cmd = Command::cargo_bin("pipelight").into_diagnostic()?;
-        cmd.arg("run")
+        cmd.current_dir(&project.dir)
+            .arg("run")
             .arg("test")
             .arg("--config")
             .arg("test.pipelight.ts");
@@ -14,7 +14,14 @@ impl Default for Pipeline {
             name: Some("default".to_owned()),
             trigger: Trigger {
                 flag: Some("blank".to_owned()),
+                paths: vec![],
+                scheduled_at: None,
+                upstream: None,
+                branch: None,
+                tag: None,
+                commit: None,
             },
+            params: vec![],
         }
     }
 }
@@ -32,6 +39,7 @@ impl Default for Cli {
             verbose: Verbosity::new(0, 0),
             internal_verbose: InternalVerbosity::new(0, 0),
             attach: true,
+            no_cache: false,
         }
     }
 }
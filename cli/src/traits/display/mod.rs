@@ -2,8 +2,8 @@
 mod test;
 // Structs
 use crate::types::{
-    Cli, DisplayCommands, Init, Logs, LogsCommands, Pipeline, Shell, Toggle, ToggleCommands,
-    Trigger,
+    Cli, DisplayCommands, Init, Logs, LogsCommands, Pipeline, Secrets, SecretsCommands,
+    ServeWebhooks, Shell, Toggle, ToggleCommands, Trigger,
 };
 use crate::types::{Commands, DetachableCommands, PostCommands, PreCommands};
 use crate::types::{InternalVerbosity, Verbosity};
@@ -30,6 +30,10 @@ impl fmt::Display for Cli {
             string += " ";
             string += "--attach";
         }
+        if self.no_cache {
+            string += " ";
+            string += "--no-cache";
+        }
         write!(f, "{}", string)
     }
 }
@@ -39,6 +43,8 @@ impl fmt::Display for Commands {
         let string = match self {
             Commands::PreCommands(pre_commands) => match pre_commands {
                 PreCommands::Init(_) => "init".to_owned(),
+                PreCommands::Check => "check".to_owned(),
+                PreCommands::Schema => "schema".to_owned(),
                 PreCommands::Completion(shell) => format!("completion{}", shell),
                 PreCommands::Enable(toggle) => format!("enable{}", toggle),
                 PreCommands::Disable(toggle) => format!("disable{}", toggle),
@@ -48,11 +54,14 @@ impl fmt::Display for Commands {
                     DetachableCommands::Run(pipeline) => format!("run{}", pipeline),
                     DetachableCommands::Trigger(trigger) => format!("trigger{}", trigger),
                     DetachableCommands::Watch => "watch".to_owned(),
+                    DetachableCommands::Scheduler => "scheduler".to_owned(),
                 },
                 PostCommands::Stop(pipeline) => format!("stop{}", pipeline),
                 PostCommands::Logs(logs) => format!("logs{}", logs),
                 PostCommands::Inspect(pipeline) => format!("inspect{}", pipeline),
                 PostCommands::Ls(list) => format!("ls{}", list),
+                PostCommands::Secrets(secrets) => format!("secrets{}", secrets),
+                PostCommands::ServeWebhooks(server) => format!("serve-webhooks{}", server),
             },
         };
         write!(f, "{}", string)
@@ -65,13 +74,14 @@ impl fmt::Display for Pipeline {
 
         if self.name.is_some() {
             string += " ";
-            string += &format!("\"{}\"", &self.name.clone().unwrap());
+            string += &quote(&self.name.clone().unwrap());
         }
-        if self.trigger.flag.is_some() {
+        string += &format!("{}", self.trigger);
+        for param in &self.params {
             string += " ";
-            string += "--flag";
+            string += "--param";
             string += " ";
-            string += &self.trigger.flag.clone().unwrap();
+            string += &quote(param);
         }
         write!(f, "{}", string)
     }
@@ -120,6 +130,21 @@ impl fmt::Display for Logs {
     }
 }
 
+/**
+Secret values are never printed.
+*/
+impl fmt::Display for Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let string = match &self.commands {
+            SecretsCommands::Set(secret) => format!(" set {}", secret.name),
+            SecretsCommands::Get(secret) => format!(" get {}", secret.name),
+            SecretsCommands::Rm(secret) => format!(" rm {}", secret.name),
+            SecretsCommands::Ls => " ls".to_owned(),
+        };
+        write!(f, "{}", string)
+    }
+}
+
 impl fmt::Display for Toggle {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut string = "".to_owned();
@@ -133,12 +158,21 @@ impl fmt::Display for Toggle {
                     string += " ";
                     string += "watcher";
                 }
+                ToggleCommands::Scheduler => {
+                    string += " ";
+                    string += "scheduler";
+                }
             }
         }
         write!(f, "{}", string)
     }
 }
 
+impl fmt::Display for ServeWebhooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, " --listen {} --secret {}", self.listen, self.secret)
+    }
+}
 impl fmt::Display for Trigger {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut string = "".to_owned();
@@ -148,10 +182,54 @@ impl fmt::Display for Trigger {
             string += " ";
             string += &self.flag.clone().unwrap();
         }
+        for path in &self.paths {
+            string += " ";
+            string += "--path";
+            string += " ";
+            string += &quote(path);
+        }
+        if self.scheduled_at.is_some() {
+            string += " ";
+            string += "--scheduled-at";
+            string += " ";
+            string += &quote(&self.scheduled_at.clone().unwrap());
+        }
+        if self.branch.is_some() {
+            string += " ";
+            string += "--branch";
+            string += " ";
+            string += &quote(&self.branch.clone().unwrap());
+        }
+        if self.tag.is_some() {
+            string += " ";
+            string += "--tag";
+            string += " ";
+            string += &quote(&self.tag.clone().unwrap());
+        }
+        if self.commit.is_some() {
+            string += " ";
+            string += "--commit";
+            string += " ";
+            string += &quote(&self.commit.clone().unwrap());
+        }
+        if self.upstream.is_some() {
+            string += " ";
+            string += "--upstream";
+            string += " ";
+            string += &quote(&self.upstream.clone().unwrap());
+        }
         write!(f, "{}", string)
     }
 }
 
+/**
+Quote a value for the shell the commands are run in,
+so that it is passed as a single literal argument.
+*/
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 fn from_internal_verbosity_to_string(e: InternalVerbosity) -> String {
     let mut string = "".to_owned();
     if e.is_silent() {
@@ -17,10 +17,18 @@ mod display {
                     name: Some("test".to_owned()),
                     trigger: Trigger {
                         flag: Some("pre-push".to_owned()),
+                        paths: vec![],
+                        scheduled_at: None,
+                        upstream: None,
+                        branch: None,
+                        tag: None,
+                        commit: None,
                     },
+                    params: vec!["env=staging".to_owned()],
                 }),
             )),
             attach: false,
+            no_cache: false,
             raw: None,
             config: None,
             // Set verbosity to default level (Error)
@@ -29,7 +37,88 @@ mod display {
         };
         // print it
         let result = format!("{}", cli);
-        assert_eq!(result, "run \"test\" --flag pre-push");
+        assert_eq!(result, "run 'test' --flag pre-push --param 'env=staging'");
+    }
+    #[test]
+    fn hostile_params() {
+        let pipeline = Pipeline {
+            name: Some("test".to_owned()),
+            trigger: Trigger {
+                flag: None,
+                paths: vec![],
+                scheduled_at: None,
+                upstream: None,
+                branch: None,
+                tag: None,
+                commit: None,
+            },
+            params: vec!["msg=\"$(touch pwned)\"".to_owned(), "msg=it's".to_owned()],
+        };
+        let result = format!("{}", pipeline);
+        assert_eq!(
+            result,
+            " 'test' --param 'msg=\"$(touch pwned)\"' --param 'msg=it'\\''s'"
+        );
+        // The shell gets the values back untouched
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf '%s\\n'{}", result))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "test\n--param\nmsg=\"$(touch pwned)\"\n--param\nmsg=it's\n"
+        );
+    }
+    #[test]
+    fn hostile_refs() {
+        let trigger = Trigger {
+            flag: Some("webhook".to_owned()),
+            paths: vec!["docs/$(touch pwned).md".to_owned()],
+            scheduled_at: None,
+            upstream: None,
+            branch: Some("main\";touch${IFS}pwned;\"".to_owned()),
+            tag: Some("v1'`touch pwned`'".to_owned()),
+            commit: Some("9c1e2a".to_owned()),
+        };
+        let result = format!("{}", trigger);
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf '%s\\n'{}", result))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "--flag\nwebhook\n--path\ndocs/$(touch pwned).md\n\
+            --branch\nmain\";touch${IFS}pwned;\"\n--tag\nv1'`touch pwned`'\n--commit\n9c1e2a\n"
+        );
+    }
+    #[test]
+    fn trigger_args() {
+        // Define a cli struct
+        let cli = Cli {
+            commands: Commands::PostCommands(PostCommands::DetachableCommands(
+                DetachableCommands::Trigger(Trigger {
+                    flag: Some("watch".to_owned()),
+                    paths: vec!["src/main.rs".to_owned()],
+                    scheduled_at: None,
+                    upstream: None,
+                    branch: None,
+                    tag: None,
+                    commit: None,
+                }),
+            )),
+            attach: false,
+            no_cache: false,
+            raw: None,
+            config: None,
+            // Set verbosity to default level (Error)
+            internal_verbose: InternalVerbosity::new(0, 0),
+            verbose: Verbosity::new(0, 0),
+        };
+        // print it
+        let result = format!("{}", cli);
+        assert_eq!(result, "trigger --flag watch --path 'src/main.rs'");
     }
     #[test]
     fn logs_args() {
@@ -44,6 +133,7 @@ mod display {
                 },
             })),
             attach: false,
+            no_cache: false,
             raw: None,
             config: None,
             internal_verbose: InternalVerbosity::new(0, 0),
@@ -63,6 +153,7 @@ mod display {
                 color: None,
             })),
             attach: false,
+            no_cache: false,
             raw: None,
             config: None,
             // fn new(verbose: u8, quiet: u8) -> Self
@@ -83,6 +174,7 @@ mod display {
                 color: None,
             })),
             attach: false,
+            no_cache: false,
             raw: None,
             config: None,
             // fn new(verbose: u8, quiet: u8) -> Self
@@ -103,6 +195,7 @@ mod display {
                 color: None,
             })),
             attach: false,
+            no_cache: false,
             raw: None,
             config: Some("test.pipelight.ts".to_owned()),
             // fn new(verbose: u8, quiet: u8) -> Self
@@ -123,6 +216,7 @@ mod display {
                 color: None,
             })),
             attach: false,
+            no_cache: false,
             raw: Some(vec!["--host".to_owned(), "linode".to_owned()]),
             config: None,
             // fn new(verbose: u8, quiet: u8) -> Self
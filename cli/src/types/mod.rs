@@ -37,6 +37,10 @@ pub struct Cli {
     #[arg(global = true, long)]
     pub attach: bool,
 
+    /// Evaluate the config file again instead of using the cached config
+    #[arg(global = true, long)]
+    pub no_cache: bool,
+
     /// Set verbosity level
     #[clap(flatten)]
     // #[serde(flatten)]
@@ -22,6 +22,10 @@ pub enum PostCommands {
     Ls(DisplayCommands),
     /// Displays pipelines with the maximum verbosity level (interactive)
     Inspect(DisplayCommands),
+    /// Manage the encrypted project secrets
+    Secrets(Secrets),
+    /// Trigger pipelines on push events sent by a forge webhook
+    ServeWebhooks(ServeWebhooks),
 }
 
 /*
@@ -36,6 +40,9 @@ pub enum DetachableCommands {
     /// Launch a watcher on the working directory (debugging)
     #[command(hide = true)]
     Watch,
+    /// Launch a scheduler on the working directory (debugging)
+    #[command(hide = true)]
+    Scheduler,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Parser)]
@@ -48,7 +55,8 @@ pub struct Watch {
 /**
 Argument for pipeline execution.
 - name: pipeline name,
-- trigger: multiple triggering environment arguments.
+- trigger: multiple triggering environment arguments,
+- params: pipeline parameter values.
 */
 #[derive(Debug, Clone, Eq, PartialEq, Parser)]
 pub struct Pipeline {
@@ -56,6 +64,9 @@ pub struct Pipeline {
     pub name: Option<String>,
     #[command(flatten)]
     pub trigger: Trigger,
+    /// Set a pipeline parameter, can be repeated
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<String>,
 }
 
 /**
@@ -66,6 +77,24 @@ pub struct Trigger {
     /// Manualy set a flag/action to bypass environment computation.
     #[arg(long, ignore_case = true)]
     pub flag: Option<String>,
+    /// The changed files, set by the watcher
+    #[arg(long = "path", value_name = "PATH", hide = true)]
+    pub paths: Vec<String>,
+    /// The date the trigger is scheduled at, set by the scheduler
+    #[arg(long, value_name = "DATE", hide = true)]
+    pub scheduled_at: Option<String>,
+    /// The pushed branch, set by the webhook receiver
+    #[arg(long, value_name = "BRANCH", hide = true)]
+    pub branch: Option<String>,
+    /// The pushed tag, set by the webhook receiver
+    #[arg(long, value_name = "TAG", hide = true)]
+    pub tag: Option<String>,
+    /// The pushed commit, set by the webhook receiver
+    #[arg(long, value_name = "COMMIT", hide = true)]
+    pub commit: Option<String>,
+    /// The upstream run uuid, set by the pipeline that fired an "after" trigger
+    #[arg(long, value_name = "UUID", hide = true)]
+    pub upstream: Option<String>,
 }
 /**
 Arguments to query logs.
@@ -87,6 +116,54 @@ pub enum LogsCommands {
     Rm,
 }
 
+/**
+Arguments to manage project secrets.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Parser)]
+pub struct Secrets {
+    #[command(subcommand)]
+    pub commands: SecretsCommands,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Subcommand)]
+pub enum SecretsCommands {
+    /// Add or replace a secret, the value is read from stdin if omitted
+    Set(Secret),
+    /// Print a secret value
+    Get(SecretName),
+    /// Remove a secret
+    Rm(SecretName),
+    /// List secret names
+    Ls,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Parser)]
+pub struct Secret {
+    /// The secret name
+    pub name: String,
+    /// The secret value
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Parser)]
+pub struct SecretName {
+    /// The secret name
+    pub name: String,
+}
+
+/**
+Arguments to receive webhooks.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Parser)]
+pub struct ServeWebhooks {
+    /// The address to listen on
+    #[arg(long, value_name = "ADDRESS", default_value = "127.0.0.1:8080")]
+    pub listen: String,
+    /// The project secret that holds the webhook signing key
+    #[arg(long, value_name = "NAME", default_value = "WEBHOOK_SECRET")]
+    pub secret: String,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Parser)]
 pub struct DisplayCommands {
     /// The pipeline name
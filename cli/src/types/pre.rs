@@ -17,6 +17,10 @@ pub enum PreCommands {
     Completion(Shell),
     /// Create a `pipelight` config template file
     Init(Init),
+    /// Report every problem found in the config file
+    Check,
+    /// Print the JSON Schema of the config file format
+    Schema,
     // Enable pipelight git hooks.
     #[command(arg_required_else_help = true)]
     Enable(Toggle),
@@ -39,6 +43,8 @@ pub enum ToggleCommands {
     GitHooks,
     /// Watcher toggle
     Watcher,
+    /// Scheduler toggle
+    Scheduler,
 }
 
 /**
@@ -0,0 +1,64 @@
+// Serde
+use serde::Serializer;
+use std::collections::HashMap;
+// Tests
+mod test;
+
+/**
+Environment variable names containing one of those words
+are considered sensitive and have their value masked in logs.
+*/
+pub const SENSITIVE_KEYS: [&str; 7] = [
+    "SECRET",
+    "TOKEN",
+    "PASSWORD",
+    "PASSWD",
+    "KEY",
+    "CREDENTIAL",
+    "AUTH",
+];
+
+/**
+The string that replaces a sensitive value.
+*/
+pub use utils::secrets::MASK;
+
+/**
+Returns true if the environment variable name looks like it holds a secret.
+*/
+pub fn is_sensitive(key: &str) -> bool {
+    let key = key.to_uppercase();
+    SENSITIVE_KEYS.iter().any(|e| key.contains(e))
+}
+
+/**
+Returns a copy of the environment where sensitive values are masked.
+*/
+pub fn mask(env: &HashMap<String, String>) -> HashMap<String, String> {
+    env.iter()
+        .map(|(key, value)| {
+            if is_sensitive(key) {
+                (key.to_owned(), MASK.to_owned())
+            } else {
+                (key.to_owned(), value.to_owned())
+            }
+        })
+        .collect()
+}
+
+/**
+Serde helper to serialize an environment with its sensitive values masked.
+Use it with `#[serde(serialize_with = "exec::env::serialize_masked")]`.
+*/
+pub fn serialize_masked<S>(
+    env: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match env {
+        Some(env) => serializer.serialize_some(&mask(env)),
+        None => serializer.serialize_none(),
+    }
+}
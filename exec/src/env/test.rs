@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod mask {
+    use crate::env::{is_sensitive, mask, MASK};
+    use crate::types::Process;
+    use std::collections::HashMap;
+
+    #[test]
+    fn sensitive_keys() {
+        assert!(is_sensitive("GITHUB_TOKEN"));
+        assert!(is_sensitive("db_password"));
+        assert!(!is_sensitive("RUST_LOG"));
+    }
+    #[test]
+    fn mask_env() {
+        let env = HashMap::from([
+            ("API_KEY".to_owned(), "abc".to_owned()),
+            ("RUST_LOG".to_owned(), "debug".to_owned()),
+        ]);
+        let res = mask(&env);
+        assert_eq!(res.get("API_KEY").map(|e| e.as_str()), Some(MASK));
+        assert_eq!(res.get("RUST_LOG").map(|e| e.as_str()), Some("debug"));
+    }
+    #[test]
+    fn serialize_process() {
+        let mut process = Process::new("echo test");
+        process.env = Some(HashMap::from([(
+            "DEPLOY_SECRET".to_owned(),
+            "abc".to_owned(),
+        )]));
+        let json = serde_json::to_string(&process).unwrap();
+        assert!(!json.contains("abc"));
+        assert!(json.contains(MASK));
+    }
+}
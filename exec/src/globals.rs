@@ -19,6 +19,14 @@ that pipe their outputs(stdout/stderr) into files.
 pub static OUTDIR: Lazy<Arc<Mutex<String>>> =
     Lazy::new(|| Arc::new(Mutex::new(".pipelight/_internals/out".to_owned())));
 
+/**
+Change the output directory of the invoked processes,
+for processes that run outside of a project.
+*/
+pub fn set_outdir(dir: &str) {
+    *OUTDIR.lock().unwrap() = dir.to_owned();
+}
+
 /**
 Returns the  user session shell when found.
 */
@@ -4,6 +4,7 @@
 #![allow(unused_must_use)]
 
 // Internal Imports
+pub mod env;
 mod globals;
 mod io;
 pub mod processes;
@@ -11,5 +12,6 @@ mod traits;
 mod types;
 
 // Re-export
+pub use globals::set_outdir;
 pub use traits::Statuable;
 pub use types::*;
@@ -2,17 +2,99 @@
 use crate::types::{Io, Process, State, Status};
 use utils::dates::Duration;
 // Unix process manipulation
-use std::process::{Command, Stdio};
+use rustix::process::{kill_process_group, Pid, Signal};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Output, Stdio};
+// Timeouts
+use std::io::Read;
+use std::thread;
+use std::time::{Duration as StdDuration, Instant};
 
 // File manipulation
 use std::fs::{create_dir_all, File};
 // Globals
 use crate::globals::{get_shell, OUTDIR, SHELL};
 // Error Handling
-use log::info;
+use log::{info, warn};
 use miette::{IntoDiagnostic, Result};
 
+/**
+Time given to a timed out process to gracefully exit
+after SIGTERM and before SIGKILL.
+*/
+pub const KILL_GRACE_PERIOD: StdDuration = StdDuration::from_secs(5);
+
 impl Process {
+    /**
+    Build the shell command that executes the process stdin
+    inside the process working directory and with the process environment.
+    */
+    fn command(&self) -> Command {
+        let mut command = Command::new(&(*SHELL.lock().unwrap()));
+        command.arg("-c").arg(self.io.stdin.as_ref().unwrap());
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+        if let Some(env) = &self.env {
+            command.envs(env);
+        }
+        // Spawn in its own process group to kill the whole subprocess tree on timeout.
+        if self.timeout.is_some() {
+            command.process_group(0);
+        }
+        command
+    }
+
+    /**
+    Await the subprocess and collect its outputs.
+    If the process has a timeout and it expires, the process group
+    receives a SIGTERM and then a SIGKILL after a grace period.
+    Returns the process output and whether it has timed out.
+    */
+    fn wait(&mut self, mut child: Child) -> Result<(Output, bool)> {
+        self.pid = Some(child.id() as i32);
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Ok((child.wait_with_output().into_diagnostic()?, false)),
+        };
+        // Read pipes in background to not block the subprocess on full pipes.
+        let stdout = child.stdout.take().map(|mut e| {
+            thread::spawn(move || {
+                let mut buf = vec![];
+                e.read_to_end(&mut buf).map(|_| buf)
+            })
+        });
+        let stderr = child.stderr.take().map(|mut e| {
+            thread::spawn(move || {
+                let mut buf = vec![];
+                e.read_to_end(&mut buf).map(|_| buf)
+            })
+        });
+
+        let timed_out = !has_exited(&mut child, timeout)?;
+        if timed_out {
+            warn!("Process timed out, terminating its process group");
+            let pgid = Pid::from_raw(child.id() as i32).unwrap();
+            kill_process_group(pgid, Signal::Term).ok();
+            if !has_exited(&mut child, KILL_GRACE_PERIOD)? {
+                kill_process_group(pgid, Signal::Kill).ok();
+            }
+        }
+        let status = child.wait().into_diagnostic()?;
+        let output = Output {
+            status,
+            stdout: match stdout {
+                Some(handle) => handle.join().unwrap().into_diagnostic()?,
+                None => vec![],
+            },
+            stderr: match stderr {
+                Some(handle) => handle.join().unwrap().into_diagnostic()?,
+                None => vec![],
+            },
+        };
+        Ok((output, timed_out))
+    }
+
     /**
     Execute/Await a subprocess and inherit the parent process output outputs(stdout/stderr)
     to the parent process.
@@ -21,9 +103,8 @@ impl Process {
         info!("Run subprocess piped to parent");
         get_shell()?;
         let mut duration = Duration::default();
-        let child = Command::new(&(*SHELL.lock().unwrap()))
-            .arg("-c")
-            .arg(self.io.stdin.as_ref().unwrap())
+        let child = self
+            .command()
             .stdin(Stdio::null())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -32,7 +113,7 @@ impl Process {
 
         // Hydrate struct
         duration.start();
-        let output = child.wait_with_output().into_diagnostic()?;
+        let (output, timed_out) = self.wait(child)?;
         duration.stop();
         self.io = Io {
             uuid: self.io.uuid,
@@ -41,7 +122,7 @@ impl Process {
         };
         self.state = State {
             duration: Some(duration),
-            status: Some(Status::from(&output)),
+            status: Some(get_status(&output, timed_out)),
         };
         Ok(())
     }
@@ -54,9 +135,8 @@ impl Process {
         info!("Run subprocess piped to parent");
         get_shell()?;
         let mut duration = Duration::default();
-        let child = Command::new(&(*SHELL.lock().unwrap()))
-            .arg("-c")
-            .arg(self.io.stdin.as_ref().unwrap())
+        let child = self
+            .command()
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -65,7 +145,7 @@ impl Process {
 
         // Hydrate struct
         duration.start();
-        let output = child.wait_with_output().into_diagnostic()?;
+        let (output, timed_out) = self.wait(child)?;
         duration.stop();
         self.io = Io {
             uuid: self.io.uuid,
@@ -74,7 +154,7 @@ impl Process {
         };
         self.state = State {
             duration: Some(duration),
-            status: Some(Status::from(&output)),
+            status: Some(get_status(&output, timed_out)),
         };
         Ok(())
     }
@@ -95,9 +175,8 @@ impl Process {
         let stderr_path = format!("{}/{}_stderr", *OUTDIR.lock().unwrap(), self.uuid.unwrap());
 
         // Ensure internal log dir exists
-        let child = Command::new(&(*SHELL.lock().unwrap()))
-            .arg("-c")
-            .arg(self.io.stdin.as_ref().unwrap())
+        let child = self
+            .command()
             .stdin(Stdio::null())
             .stdout(File::create(stdout_path).into_diagnostic()?)
             .stderr(File::create(stderr_path).into_diagnostic()?)
@@ -106,13 +185,13 @@ impl Process {
 
         // Hydrate struct
         duration.start();
-        let output = child.wait_with_output().into_diagnostic()?;
+        let (output, timed_out) = self.wait(child)?;
         duration.stop();
         self.io.read()?;
         self.io.clean()?;
         self.state = State {
             duration: Some(duration),
-            status: Some(Status::from(&output)),
+            status: Some(get_status(&output, timed_out)),
         };
         Ok(())
     }
@@ -120,15 +199,18 @@ impl Process {
     /**
     Execute/NoAwait a subprocess and mute the input(stdin) and  outputs(stdout/stderr).
     NoAwait means it immediatly returns once the subprocess is succesfully spawned and don't wait for output.
+
+    For every caller, the subprocess leads its own process group:
+    - the signals sent to the caller group (Ctrl-C, terminal hang up) don't reach it,
+    - stopping it, which kills its process group, doesn't kill the caller (watcher, scheduler...).
     */
     pub fn run_detached(&mut self) -> Result<()> {
         info!("Run detached subprocess");
         get_shell()?;
         let mut duration = Duration::default();
         duration.start();
-        Command::new(&(*SHELL.lock().unwrap()))
-            .arg("-c")
-            .arg(self.io.stdin.as_ref().unwrap())
+        self.command()
+            .process_group(0)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -142,3 +224,28 @@ impl Process {
         Ok(())
     }
 }
+
+/**
+Poll the child until it exits or the timeout expires.
+Returns whether the child has exited.
+*/
+fn has_exited(child: &mut Child, timeout: StdDuration) -> Result<bool> {
+    let start = Instant::now();
+    loop {
+        if child.try_wait().into_diagnostic()?.is_some() {
+            return Ok(true);
+        }
+        if start.elapsed() >= timeout {
+            return Ok(false);
+        }
+        thread::sleep(StdDuration::from_millis(10));
+    }
+}
+
+fn get_status(output: &Output, timed_out: bool) -> Status {
+    if timed_out {
+        Status::TimedOut
+    } else {
+        Status::from(output)
+    }
+}
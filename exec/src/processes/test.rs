@@ -1,8 +1,14 @@
 #[cfg(test)]
 mod basic {
-    use crate::globals::OUTDIR;
+    use crate::globals::set_outdir;
     use crate::types::{Io, Process, State, Status};
+    use std::collections::HashMap;
+    use std::env;
     use std::fs::remove_dir_all;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+    // Unix process manipulation
+    use rustix::process::{getpgid, kill_process_group, Pid, Signal};
     #[test]
     fn run_piped() {
         let mut process = Process::new("echo test");
@@ -11,11 +17,48 @@ mod basic {
         assert_eq!(Some(Status::Succeeded), process.state.status);
     }
     #[test]
+    fn run_with_env() {
+        let mut process = Process::new("echo $PIPELIGHT_TEST_VAR");
+        process.env = Some(HashMap::from([(
+            "PIPELIGHT_TEST_VAR".to_owned(),
+            "test".to_owned(),
+        )]));
+        process.run_piped().unwrap();
+        assert_eq!(Some("test\n"), process.io.stdout.as_deref());
+    }
+    #[test]
+    fn run_with_cwd() {
+        let mut process = Process::new("pwd");
+        process.cwd = Some("/tmp".to_owned());
+        process.run_piped().unwrap();
+        assert_eq!(Some("/tmp\n"), process.io.stdout.as_deref());
+    }
+    #[test]
+    fn run_with_timeout() {
+        let mut process = Process::new("echo test; sleep 10");
+        process.timeout = Some(Duration::from_millis(200));
+        let start = Instant::now();
+        process.run_piped().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(Some("test\n"), process.io.stdout.as_deref());
+        assert_eq!(Some(Status::TimedOut), process.state.status);
+    }
+    /**
+    A temporary directory removed when dropped,
+    even if the test fails.
+    */
+    struct TempDir(PathBuf);
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = remove_dir_all(&self.0);
+        }
+    }
+    #[test]
     fn run_fs() {
+        let dir = TempDir(env::temp_dir().join(format!("pipelight-exec-{}", std::process::id())));
+        set_outdir(&dir.0.join("_internals/out").display().to_string());
         let mut process = Process::new("echo test");
         process.run_fs().unwrap();
-        // clean dir
-        remove_dir_all(&(*OUTDIR.lock().unwrap())).unwrap();
         assert_eq!(Some("test\n"), process.io.stdout.as_deref());
         assert_eq!(Some(Status::Succeeded), process.state.status);
     }
@@ -26,4 +69,26 @@ mod basic {
         assert_eq!(None, process.io.stdout.as_deref());
         assert_eq!(Some(Status::Succeeded), process.state.status);
     }
+    #[test]
+    fn run_detached_process_group() {
+        let path = std::env::temp_dir().join(format!("pipelight-pgid-{}", std::process::id()));
+        let mut process = Process::new(&format!("echo $$ > {}; sleep 10", path.display()));
+        process.run_detached().unwrap();
+
+        let start = Instant::now();
+        let mut pid = None;
+        while pid.is_none() && start.elapsed() < Duration::from_secs(5) {
+            pid = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|e| e.trim().parse::<i32>().ok());
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        std::fs::remove_file(&path).unwrap();
+        let pid = Pid::from_raw(pid.unwrap()).unwrap();
+        // The detached process leads its own group, not the caller one
+        let pgid = getpgid(Some(pid)).unwrap();
+        assert_eq!(pgid, pid);
+        assert_ne!(pgid, getpgid(None).unwrap());
+        kill_process_group(pgid, Signal::Kill).unwrap();
+    }
 }
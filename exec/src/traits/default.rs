@@ -19,6 +19,9 @@ impl Process {
                 ..Io::default()
             },
             state: State::default(),
+            cwd: None,
+            env: None,
+            timeout: None,
         }
     }
 }
@@ -36,6 +39,9 @@ impl Default for Process {
                 ..Io::default()
             },
             state: State::default(),
+            cwd: None,
+            env: None,
+            timeout: None,
         }
     }
 }
@@ -17,6 +17,8 @@ impl fmt::Display for Status {
             Status::Failed => write!(f, "{} {}", icon.red(), "Failed".normal().bold()),
             Status::Running => write!(f, "{} {}", icon.green(), "Running".bold()),
             Status::Aborted => write!(f, "{} {}", icon.yellow(), "Aborted".bold()),
+            Status::TimedOut => write!(f, "{} {}", icon.magenta(), "Timed out".bold()),
+            Status::Skipped => write!(f, "{} {}", icon.dimmed(), "Skipped".dimmed()),
         };
         Ok(())
     }
@@ -1,2 +1,4 @@
+// Tests
+mod test;
 mod io;
 mod state;
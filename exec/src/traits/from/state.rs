@@ -9,13 +9,15 @@ use log::warn;
 
 impl From<&String> for Status {
     fn from(status: &String) -> Status {
-        let cased: &str = &status.to_case(Case::Snake);
+        let cased: &str = &status.to_case(Case::Kebab);
         match cased {
             "started" => Status::Started,
             "succeeded" => Status::Succeeded,
             "failed" => Status::Failed,
             "running" => Status::Running,
             "aborted" => Status::Aborted,
+            "timed-out" => Status::TimedOut,
+            "skipped" => Status::Skipped,
             _ => {
                 warn!("unexpected string, assuminng default state");
                 Status::default()
@@ -31,6 +33,8 @@ impl From<&Status> for String {
             Status::Failed => "failed".to_owned(),
             Status::Running => "running".to_owned(),
             Status::Aborted => "aborted".to_owned(),
+            Status::TimedOut => "timed-out".to_owned(),
+            Status::Skipped => "skipped".to_owned(),
         }
     }
 }
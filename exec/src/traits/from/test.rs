@@ -0,0 +1,14 @@
+#[cfg(test)]
+mod status {
+    use crate::types::Status;
+
+    #[test]
+    fn same_spelling_as_serde() {
+        let status = Status::TimedOut;
+        let string = String::from(&status);
+        assert_eq!(string, "timed-out");
+        assert_eq!(serde_json::to_string(&status).unwrap(), "\"timed-out\"");
+        assert_eq!(Status::from(&string), status);
+        assert_eq!(Status::from(&"TimedOut".to_owned()), status);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::cmp::PartialEq;
 use utils::dates::Duration;
 use uuid::Uuid;
@@ -16,6 +17,8 @@ pub enum Status {
     Failed,
     Running,
     Aborted,
+    TimedOut,
+    Skipped,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -30,7 +33,15 @@ pub struct Process {
     pub pid: Option<i32>,
     pub state: State,
     pub io: Io,
-    // pub cwd: Option<String>,
+    // Working directory of the spawned shell
+    pub cwd: Option<String>,
+    // Environment variables added to the spawned shell
+    #[serde(serialize_with = "crate::env::serialize_masked")]
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    // Time left to the process before it is killed
+    #[serde(skip)]
+    pub timeout: Option<std::time::Duration>,
 }
 
 /**
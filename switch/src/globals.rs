@@ -5,7 +5,7 @@ use utils::git::Flag;
 use once_cell::sync::Lazy;
 use std::sync::{Arc, Mutex};
 // Teleport
-use utils::teleport::Portal;
+use utils::teleport::{global_config_file, Portal};
 // Logs
 use workflow::{Config, Trigger};
 // Cli
@@ -43,23 +43,69 @@ pub fn full_hydrate_logger() -> Result<()> {
 pub fn hydrate_trigger() -> Result<()> {
     let args = CLI.lock().unwrap().clone();
     let mut flag = None;
+    let mut paths = vec![];
+    let mut scheduled_at = None;
+    let mut branch = None;
+    let mut tag = None;
+    let mut commit = None;
     if let Commands::PostCommands(PostCommands::DetachableCommands(detachable_commands)) =
         args.commands
     {
         match detachable_commands {
             DetachableCommands::Trigger(trigger) => {
                 flag = trigger.flag;
+                paths = trigger.paths;
+                scheduled_at = trigger.scheduled_at;
+                branch = trigger.branch;
+                tag = trigger.tag;
+                commit = trigger.commit;
             }
             DetachableCommands::Run(pipeline) => {
                 flag = pipeline.trigger.flag;
+                paths = pipeline.trigger.paths;
+                scheduled_at = pipeline.trigger.scheduled_at;
+                branch = pipeline.trigger.branch;
+                tag = pipeline.trigger.tag;
+                commit = pipeline.trigger.commit;
             }
             _ => {}
         }
     }
+    // Changed files are computed from git when not explicitly set
+    let paths = if paths.is_empty() { None } else { Some(paths) };
     if let Some(flag) = flag {
-        Trigger::set(Some(Flag::from(&flag)))?;
+        Trigger::set(Some(Flag::from(&flag)), paths)?;
     } else {
-        Trigger::set(None)?;
+        Trigger::set(None, paths)?;
+    }
+    if let Some(scheduled_at) = scheduled_at {
+        Trigger::set_scheduled_at(&scheduled_at)?;
+    }
+    // The git ref received by the webhook receiver overrides the local one
+    if branch.is_some() || tag.is_some() {
+        Trigger::set_ref(branch, tag, commit)?;
+    }
+    Ok(())
+}
+
+/**
+Set the upstream run of a chained pipeline to the triggering env.
+The upstream is read from the logs, so the portal must be hydrated first.
+*/
+pub fn hydrate_upstream() -> Result<()> {
+    let args = CLI.lock().unwrap().clone();
+    let mut upstream = None;
+    if let Commands::PostCommands(PostCommands::DetachableCommands(detachable_commands)) =
+        args.commands
+    {
+        match detachable_commands {
+            DetachableCommands::Trigger(trigger) => upstream = trigger.upstream,
+            DetachableCommands::Run(pipeline) => upstream = pipeline.trigger.upstream,
+            _ => {}
+        }
+    }
+    if let Some(uuid) = upstream {
+        Trigger::set_upstream(&uuid)?;
     }
     Ok(())
 }
@@ -87,12 +133,17 @@ pub fn hydrate_portal() -> Result<()> {
             *PORTAL.lock().unwrap() = portal;
             return Ok(());
         }
+        // Only the global pipelines are available outside of a project
+        Err(_) if global_config_file().is_some() => {
+            info!("No project config file found, using the global config only");
+            Ok(())
+        }
         Err(e) => {
             let message = "Could not find a configuration file";
             let help = "Create a default configuration file: \"pipelight init --help\"";
             return Err(LibError::new(message, help, e).into());
         }
-    };
+    }
 }
 
 // Hydrate config
@@ -101,8 +152,22 @@ pub fn hydrate_config() -> Result<()> {
     let portal = PORTAL.lock().unwrap().clone();
     let args = CLI.lock().unwrap().clone();
 
-    let casted_config = cast::Config::load(&portal.target.file_path.unwrap(), args.raw.clone())?;
-    let config = Config::from(&casted_config);
+    let mut config = Config::default();
+    if let Some(file_path) = portal.target.file_path {
+        let casted_config = if args.no_cache {
+            cast::Config::load(&file_path, args.raw.clone())?
+        } else {
+            cast::Config::load_cached(&file_path, args.raw.clone())?
+        };
+        config = Config::from(&casted_config);
+    }
+    // The global config is not cached, there may be no project to store the cache in
+    if let Some(file_path) = global_config_file() {
+        trace!("Found global config file at: {}", file_path);
+        let casted_config = cast::Config::load(&file_path, args.raw.clone())?;
+        config.merge_global(Config::from(&casted_config));
+    }
+    config.has_valid_graphs()?;
     *CONFIG.lock().unwrap() = config.clone();
 
     Ok(())
@@ -124,9 +189,13 @@ pub fn set_globals() -> Result<()> {
         // hydrate the PORTAL global var
         hydrate_portal()?;
         // hydrate the CONFIG global var
-        (*PORTAL.lock().unwrap()).teleport()?;
-        full_hydrate_logger()?;
+        // Outside of a project, there is no ".pipelight" directory to log into
+        if PORTAL.lock().unwrap().target.directory_path.is_some() {
+            (*PORTAL.lock().unwrap()).teleport()?;
+            full_hydrate_logger()?;
+        }
         hydrate_config()?;
+        hydrate_upstream()?;
     }
     Ok(())
 }
@@ -28,6 +28,7 @@ impl From<&Style> for FileType {
             Style::Javascript => FileType::JavaScript,
             Style::Toml => FileType::Toml,
             Style::Yaml => FileType::Yaml,
+            Style::Json => FileType::Json,
         }
     }
 }
@@ -40,6 +41,8 @@ impl From<&FileType> for Style {
             FileType::Tml => Style::Toml,
             FileType::Yaml => Style::Yaml,
             FileType::Yml => Style::Yaml,
+            FileType::Json => Style::Json,
+            FileType::Json5 => Style::Json,
         }
     }
 }
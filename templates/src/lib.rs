@@ -6,4 +6,4 @@ mod types;
 mod test;
 
 // Re-export
-pub use types::{Style, Template};
+pub use types::{Style, Template, SCHEMA_FILE};
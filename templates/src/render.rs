@@ -8,7 +8,7 @@ use std::fs;
 use std::io::Write;
 use std::path::Path;
 
-use crate::types::{Assets, Style, Template};
+use crate::types::{Assets, Style, Template, SCHEMA_FILE};
 use utils::files::{is_filename, FileType};
 
 impl Template {
@@ -70,6 +70,10 @@ impl Template {
     pub fn create(&self) -> Result<()> {
         let rendered = self.create_config_template()?;
         self.write_config_file(&rendered)?;
+        // Toml, Yaml and Json templates reference the schema file
+        if [Style::Toml, Style::Yaml, Style::Json].contains(&self.style) {
+            self.write_schema_file()?;
+        }
         Ok(())
     }
     pub fn create_ignore(&self) -> Result<()> {
@@ -115,6 +119,19 @@ impl Template {
         Ok(())
     }
     /**
+    Write the config JSON Schema next to the config file,
+    for editors to provide completion and validation.
+    The file is overwritten to match the running pipelight version.
+    */
+    fn write_schema_file(&self) -> Result<()> {
+        let path = Path::new(&self.file_path)
+            .parent()
+            .unwrap()
+            .join(SCHEMA_FILE);
+        fs::write(path, cast::Config::schema_to_string()?).into_diagnostic()?;
+        Ok(())
+    }
+    /**
     Generate in memory the config file template.
     */
     pub fn create_ignore_template(&self) -> Result<String> {
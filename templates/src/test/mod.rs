@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod template {
-    use crate::Template;
+    use crate::{Style, Template};
     use std::env;
     use std::path::Path;
 
@@ -26,6 +26,24 @@ mod template {
         test_dir
     }
 
+    #[test]
+    fn reference_schema() {
+        for (style, modeline) in [
+            (Style::Toml, "#:schema ./pipelight.schema.json"),
+            (
+                Style::Yaml,
+                "# yaml-language-server: $schema=./pipelight.schema.json",
+            ),
+            (Style::Json, "{\n  \"$schema\": \"./pipelight.schema.json\""),
+        ] {
+            let template = Template {
+                style,
+                ..Template::default()
+            };
+            let rendered = template.create_config_template().unwrap();
+            assert!(rendered.starts_with(modeline));
+        }
+    }
     // #[test]
     fn handlebars_find_template_files() {
         let res = Template::default().create_config_template();
@@ -4,6 +4,11 @@ use std::env;
 // Embed files
 use rust_embed::RustEmbed;
 
+/**
+The JSON Schema file referenced by the Toml, Yaml and Json templates.
+*/
+pub const SCHEMA_FILE: &str = "pipelight.schema.json";
+
 #[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq, PartialOrd, Eq, Ord)]
 #[serde(rename_all = "kebab-case")]
 pub enum Style {
@@ -13,6 +18,7 @@ pub enum Style {
     Javascript,
     Toml,
     Yaml,
+    Json,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
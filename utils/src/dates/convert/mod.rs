@@ -59,6 +59,56 @@ pub fn iso8601_to_std_duration(duration: &str) -> Result<std::time::Duration> {
     Err(Error::msg("Couldn't parse duration: Bad iso8601 duration"))
 }
 
+/**
+Convert a human readable duration string like "10m", "1h30m" or "500ms"
+into the standard duration struct(std::time::Duration)
+*/
+pub fn human_duration_to_std_duration(duration: &str) -> Result<std::time::Duration> {
+    let mut res = std::time::Duration::ZERO;
+    let mut number = "".to_owned();
+    let mut unit = "".to_owned();
+    let mut chars = duration.trim().chars().peekable();
+    while chars.peek().is_some() {
+        number.clear();
+        unit.clear();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            number.push(c);
+        }
+        while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+            unit.push(c);
+        }
+        let value = number.parse::<u64>().map_err(|_| {
+            Error::msg(format!("Couldn't parse duration: Bad duration {:?}", duration))
+        })?;
+        res += match unit.as_str() {
+            "ms" => std::time::Duration::from_millis(value),
+            "s" => std::time::Duration::from_secs(value),
+            "m" => std::time::Duration::from_secs(value * 60),
+            "h" => std::time::Duration::from_secs(value * 60 * 60),
+            "d" => std::time::Duration::from_secs(value * 60 * 60 * 24),
+            _ => {
+                let message = format!(
+                    "Couldn't parse duration: Unknown unit {:?} in {:?}",
+                    unit, duration
+                );
+                return Err(Error::msg(message));
+            }
+        };
+    }
+    if duration.trim().is_empty() {
+        return Err(Error::msg("Couldn't parse duration: Empty duration"));
+    }
+    Ok(res)
+}
+
+/**
+Convert a duration string, either ISO8601 ("PT10M") or human readable ("10m"),
+into the standard duration struct(std::time::Duration)
+*/
+pub fn str_to_std_duration(duration: &str) -> Result<std::time::Duration> {
+    iso8601_to_std_duration(duration).or_else(|_| human_duration_to_std_duration(duration))
+}
+
 /**
 */
 pub fn std_duration_to_human_duration(duration: std::time::Duration) -> Result<String> {
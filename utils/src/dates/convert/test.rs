@@ -10,6 +10,23 @@ mod convert {
         iso8601_to_std_duration(&string).unwrap();
     }
     #[test]
+    fn human_string_to_duration() {
+        assert_eq!(
+            human_duration_to_std_duration("10m").unwrap(),
+            time::Duration::from_secs(600)
+        );
+        assert_eq!(
+            human_duration_to_std_duration("1h30m").unwrap(),
+            time::Duration::from_secs(5400)
+        );
+        assert_eq!(
+            str_to_std_duration("PT10S").unwrap(),
+            time::Duration::from_secs(10)
+        );
+        assert!(str_to_std_duration("10 minutes").is_err());
+        assert!(str_to_std_duration("m").is_err());
+    }
+    #[test]
     fn duration_to_string() {
         let std = time::Duration::new(5, 0);
         std_duration_to_iso8601(&std).unwrap();
@@ -1,6 +1,8 @@
 mod compute;
 pub mod convert;
+mod schedule;
 mod types;
 
 // Re-exports
+pub use schedule::*;
 pub use types::*;
@@ -0,0 +1,157 @@
+// Test
+mod test;
+// Dates
+use chrono::{DateTime, Datelike, Duration, Local, Timelike};
+// Error Handling
+use miette::{Error, Result};
+
+/**
+A cron-like schedule with a minute resolution,
+parsed from a standard 5 fields expression "minute hour day-of-month month day-of-week"
+or from a shortcut (@hourly, @daily, @weekly, @monthly, @yearly).
+
+Fields accept wildcards, lists (1,15), ranges (1-5) and steps (0-30/5).
+Like cron, when both day-of-month and day-of-week are restricted,
+a date matches if any of them does.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Schedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    weekdays: Vec<u32>,
+    // Whether the day fields are wildcards
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    pub fn new(expression: &str) -> Result<Schedule> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            e => e,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            let message = format!(
+                "Bad schedule \"{}\": expected 5 fields (minute hour day month weekday)",
+                expression
+            );
+            return Err(Error::msg(message));
+        }
+        // Sunday is either 0 or 7
+        let mut weekdays = parse_field(fields[4], 0, 7)?;
+        if weekdays.contains(&7) {
+            weekdays.retain(|e| *e != 7);
+            if !weekdays.contains(&0) {
+                weekdays.insert(0, 0);
+            }
+        }
+        Ok(Schedule {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+    /**
+    Return true if the date minute is scheduled.
+    */
+    pub fn is_match(&self, date: &DateTime<Local>) -> bool {
+        let day = self.days.contains(&date.day());
+        let weekday = self
+            .weekdays
+            .contains(&date.weekday().num_days_from_sunday());
+        let day = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        day && self.minutes.contains(&date.minute())
+            && self.hours.contains(&date.hour())
+            && self.months.contains(&date.month())
+    }
+    /**
+    Return the scheduled dates after "since" and up to "until" (included),
+    truncated to the minute.
+    */
+    pub fn between(
+        &self,
+        since: &DateTime<Local>,
+        until: &DateTime<Local>,
+    ) -> Vec<DateTime<Local>> {
+        let mut dates = vec![];
+        let mut date = truncate(since) + Duration::minutes(1);
+        while date <= *until {
+            if self.is_match(&date) {
+                dates.push(date);
+            }
+            date += Duration::minutes(1);
+        }
+        dates
+    }
+}
+
+/**
+Truncate a date to the minute.
+*/
+pub fn truncate(date: &DateTime<Local>) -> DateTime<Local> {
+    date.with_second(0)
+        .and_then(|e| e.with_nanosecond(0))
+        .unwrap_or(*date)
+}
+
+/**
+Parse a comma separated list of values, ranges and steps
+into the sorted list of allowed values.
+*/
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>> {
+    let mut values = vec![];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, parse_value(step, 1, u32::MAX)?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            // "5/10" means from 5 to the max every 10
+            if part.contains('/') {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+        if start > end {
+            let message = format!("Bad schedule range \"{}\"", range);
+            return Err(Error::msg(message));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32> {
+    match value.parse::<u32>() {
+        Ok(e) if e >= min && e <= max => Ok(e),
+        _ => {
+            let message = format!(
+                "Bad schedule value \"{}\": expected a number between {} and {}",
+                value, min, max
+            );
+            Err(Error::msg(message))
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod schedule {
+    use crate::dates::Schedule;
+    use chrono::{DateTime, Local, TimeZone};
+
+    fn date(s: &str) -> DateTime<Local> {
+        let naive = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&naive).unwrap()
+    }
+
+    #[test]
+    fn parse_expressions() {
+        assert!(Schedule::new("0 3 * * *").is_ok());
+        assert!(Schedule::new("*/15 9-17 * * 1-5").is_ok());
+        assert!(Schedule::new("@daily").is_ok());
+        assert!(Schedule::new("0 3 * *").is_err());
+        assert!(Schedule::new("60 3 * * *").is_err());
+        assert!(Schedule::new("0 5-3 * * *").is_err());
+        assert!(Schedule::new("a b c d e").is_err());
+    }
+    #[test]
+    fn match_dates() {
+        let nightly = Schedule::new("0 3 * * *").unwrap();
+        assert!(nightly.is_match(&date("2023-10-12 03:00")));
+        assert!(!nightly.is_match(&date("2023-10-12 03:01")));
+
+        // 2023-10-14 is a saturday
+        let weekdays = Schedule::new("*/15 9-17 * * 1-5").unwrap();
+        assert!(weekdays.is_match(&date("2023-10-13 09:45")));
+        assert!(!weekdays.is_match(&date("2023-10-14 09:45")));
+
+        // Sunday as 7
+        let sunday = Schedule::new("0 0 * * 7").unwrap();
+        assert!(sunday.is_match(&date("2023-10-15 00:00")));
+
+        // Day of month OR day of week when both are restricted
+        let either = Schedule::new("0 0 1 * 1").unwrap();
+        assert!(either.is_match(&date("2023-10-01 00:00")));
+        assert!(either.is_match(&date("2023-10-16 00:00")));
+        assert!(!either.is_match(&date("2023-10-17 00:00")));
+    }
+    #[test]
+    fn dates_between() {
+        let hourly = Schedule::new("@hourly").unwrap();
+        let dates = hourly.between(&date("2023-10-12 03:00"), &date("2023-10-12 06:00"));
+        assert_eq!(
+            dates,
+            vec![
+                date("2023-10-12 04:00"),
+                date("2023-10-12 05:00"),
+                date("2023-10-12 06:00")
+            ]
+        );
+    }
+}
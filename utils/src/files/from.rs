@@ -14,6 +14,8 @@ impl From<&String> for FileType {
             "tml" => FileType::Tml,
             "ts" => FileType::TypeScript,
             "js" => FileType::JavaScript,
+            "json" => FileType::Json,
+            "json5" => FileType::Json5,
             _ => {
                 let message = format!("Couldn't parse file with extension .{}", extension);
                 let _hint = "Assuming default typescript file";
@@ -35,6 +37,8 @@ impl From<&FileType> for String {
             FileType::Tml => "tml".to_owned(),
             FileType::TypeScript => "ts".to_owned(),
             FileType::JavaScript => "js".to_owned(),
+            FileType::Json => "json".to_owned(),
+            FileType::Json5 => "json5".to_owned(),
         }
     }
 }
@@ -13,4 +13,6 @@ pub enum FileType {
     Tml,
     Yaml,
     Yml,
+    Json,
+    Json5,
 }
@@ -1,5 +1,8 @@
 // Structs
 use crate::git::Git;
+use git2::{BranchType, Diff, Oid};
+use std::env;
+use std::path::PathBuf;
 // Error Handling
 use miette::{Error, IntoDiagnostic, Result};
 
@@ -43,4 +46,169 @@ impl Git {
         let commit_id = head.peel_to_commit().into_diagnostic()?.id().to_string();
         Ok(commit_id)
     }
+    /**
+    Returns the files staged in the index (pre-commit)
+    */
+    pub fn get_staged_files(&self) -> Result<Vec<String>> {
+        let repo = self.repo.as_ref().unwrap();
+        // No HEAD tree on the initial commit
+        let tree = repo.head().ok().and_then(|e| e.peel_to_tree().ok());
+        let diff = repo
+            .diff_tree_to_index(tree.as_ref(), None, None)
+            .into_diagnostic()?;
+        Ok(diff_files(&diff))
+    }
+    /**
+    Returns the files changed by the latest commit, HEAD against its parent (post-commit)
+    */
+    pub fn get_commit_files(&self) -> Result<Vec<String>> {
+        let repo = self.repo.as_ref().unwrap();
+        let commit = repo
+            .head()
+            .into_diagnostic()?
+            .peel_to_commit()
+            .into_diagnostic()?;
+        let tree = commit.tree().into_diagnostic()?;
+        // No parent on the initial commit
+        let parent = commit.parent(0).ok().and_then(|e| e.tree().ok());
+        let diff = repo
+            .diff_tree_to_tree(parent.as_ref(), Some(&tree), None)
+            .into_diagnostic()?;
+        Ok(diff_files(&diff))
+    }
+    /**
+    Returns the files changed by the commits being pushed (pre-push).
+
+    The hook receives a line per pushed ref on its standard input:
+    "<local ref> <local sha> <remote ref> <remote sha>".
+    Every pushed commit is compared to the last commit it shares with the remote one,
+    or to its parent when the remote ref is new.
+    Deleted remote refs change no files.
+
+    Falls back to the upstream branch when the pushed refs are unknown.
+    */
+    pub fn get_pushed_files(&self, updates: &str) -> Result<Vec<String>> {
+        let repo = self.repo.as_ref().unwrap();
+        let mut files: Vec<String> = vec![];
+        let mut is_known = false;
+        for line in updates.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [_, local, _, remote] = fields[..] else {
+                continue;
+            };
+            is_known = true;
+            let local = Oid::from_str(local).into_diagnostic()?;
+            let remote = Oid::from_str(remote).into_diagnostic()?;
+            if local.is_zero() {
+                continue;
+            }
+            let commit = repo.find_commit(local).into_diagnostic()?;
+            let tree = commit.tree().into_diagnostic()?;
+            // The remote commit may be unknown locally
+            let base = match remote.is_zero() {
+                true => None,
+                false => repo
+                    .merge_base(remote, local)
+                    .and_then(|e| repo.find_commit(e))
+                    .ok(),
+            };
+            let base = base
+                .or_else(|| commit.parent(0).ok())
+                .and_then(|e| e.tree().ok());
+            let diff = repo
+                .diff_tree_to_tree(base.as_ref(), Some(&tree), None)
+                .into_diagnostic()?;
+            for file in diff_files(&diff) {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        if !is_known {
+            return self.get_upstream_files();
+        }
+        Ok(files)
+    }
+    /**
+    Returns the files changed by HEAD since the last commit it shares with the upstream branch.
+    Falls back to the latest commit when the branch has no upstream.
+    */
+    fn get_upstream_files(&self) -> Result<Vec<String>> {
+        let repo = self.repo.as_ref().unwrap();
+        let head = repo
+            .head()
+            .into_diagnostic()?
+            .peel_to_commit()
+            .into_diagnostic()?;
+        let upstream = repo
+            .find_branch(&self.get_branch()?, BranchType::Local)
+            .and_then(|e| e.upstream())
+            .and_then(|e| e.get().peel_to_commit());
+        let upstream = match upstream {
+            Ok(upstream) => upstream,
+            Err(_) => return self.get_commit_files(),
+        };
+        let base = repo
+            .merge_base(upstream.id(), head.id())
+            .and_then(|e| repo.find_commit(e))
+            .and_then(|e| e.tree())
+            .into_diagnostic()?;
+        let tree = head.tree().into_diagnostic()?;
+        let diff = repo
+            .diff_tree_to_tree(Some(&base), Some(&tree), None)
+            .into_diagnostic()?;
+        Ok(diff_files(&diff))
+    }
+    /**
+    Returns the repository root directory, none for bare repositories.
+    */
+    pub fn get_root(&self) -> Option<PathBuf> {
+        let root = self.repo.as_ref()?.workdir()?;
+        Some(root.canonicalize().unwrap_or(root.to_owned()))
+    }
+    /**
+    Returns the absolute path of a path relative to the repository root.
+    */
+    pub fn get_absolute(&self, path: &str) -> String {
+        match self.get_root() {
+            Some(root) => root.join(path).to_string_lossy().to_string(),
+            None => path.to_owned(),
+        }
+    }
+    /**
+    Returns a path relative to the repository root, like the paths of the git diffs,
+    from an absolute path or a path relative to the working directory.
+    Paths outside of the repository are returned as is.
+    */
+    pub fn get_root_relative(&self, path: &str) -> String {
+        let Some(root) = self.get_root() else {
+            return path.to_owned();
+        };
+        let cwd = env::current_dir()
+            .and_then(|e| e.canonicalize())
+            .unwrap_or_default();
+        match cwd.join(path).strip_prefix(&root) {
+            Ok(e) => e.to_string_lossy().to_string(),
+            Err(_) => path.to_owned(),
+        }
+    }
+}
+
+/**
+Returns the paths, relative to the repository root, touched by a diff.
+Renamed files are listed under both their old and new path.
+*/
+fn diff_files(diff: &Diff) -> Vec<String> {
+    let mut files: Vec<String> = vec![];
+    for delta in diff.deltas() {
+        for file in [delta.old_file(), delta.new_file()] {
+            if let Some(path) = file.path() {
+                let path = path.to_string_lossy().to_string();
+                if !files.contains(&path) {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    files
 }
@@ -1,3 +1,6 @@
+// Tests
+mod test;
+
 mod git_query;
 mod hooks;
 mod traits;
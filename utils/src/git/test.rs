@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod git_query {
+    use crate::git::Git;
+    use git2::{Oid, Repository, Signature};
+    use std::fs;
+    use std::path::Path;
+
+    /**
+    Write a file and commit it on top of HEAD.
+    */
+    fn commit(repo: &Repository, file: &str) -> Oid {
+        let root = repo.workdir().unwrap();
+        fs::write(root.join(file), file).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|e| e.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, file, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn pushed_files() {
+        let dir = std::env::temp_dir().join(format!("pipelight-git-{}", std::process::id()));
+        let repo = Repository::init(&dir).unwrap();
+        let first = commit(&repo, "a.txt");
+        let second = commit(&repo, "b.txt");
+        let third = commit(&repo, "c.txt");
+        let git = Git { repo: Some(repo) };
+        let zero = Oid::zero();
+
+        let updates = format!("refs/heads/main {} refs/heads/main {}\n", third, first);
+        let files = git.get_pushed_files(&updates).unwrap();
+        assert_eq!(files, vec!["b.txt", "c.txt"]);
+        // New remote ref
+        let updates = format!("refs/heads/dev {} refs/heads/dev {}\n", second, zero);
+        let files = git.get_pushed_files(&updates).unwrap();
+        assert_eq!(files, vec!["b.txt"]);
+        // Deleted remote ref
+        let updates = format!("(delete) {} refs/heads/old {}\n", zero, first);
+        let files = git.get_pushed_files(&updates).unwrap();
+        assert!(files.is_empty());
+
+        let root = git.get_root().unwrap();
+        let path = root.join("src/main.rs").display().to_string();
+        assert_eq!(git.get_root_relative(&path), "src/main.rs");
+        assert_eq!(git.get_absolute("src/main.rs"), path);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -20,6 +20,9 @@ impl From<&String> for Special {
             "manual" => Manual,
             "blank" => Blank,
             "watch" => Watch,
+            "schedule" => Schedule,
+            "webhook" => Webhook,
+            "after" => After,
             _ => {
                 let message = format!("The special flag {} is not known", cased);
                 error!("{}", message);
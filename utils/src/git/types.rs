@@ -78,4 +78,7 @@ pub enum Special {
     Manual,
     Watch,
     Blank,
+    Schedule,
+    Webhook,
+    After,
 }
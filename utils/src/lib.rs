@@ -9,5 +9,6 @@ pub mod error;
 pub mod files;
 pub mod git;
 pub mod logger;
+pub mod secrets;
 pub mod signal;
 pub mod teleport;
@@ -0,0 +1,202 @@
+/**
+Project secrets are stored in `.pipelight/secrets`,
+a json map of names and values encrypted with ChaCha20-Poly1305.
+
+The encryption key is read from the PIPELIGHT_SECRETS_KEY environment variable
+(64 hexadecimal characters) or from the user key file
+`<config_dir>/pipelight/secrets.key` that is created on first use.
+
+Loaded secret values are registered into a global
+so they can be redacted from every printed or logged output.
+*/
+// Tests
+mod test;
+// Structs
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+// Encryption
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+// Global vars
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+// Error Handling
+use miette::{Error, IntoDiagnostic, Result};
+
+/**
+The project secrets file, relative to the config file directory.
+*/
+pub const SECRETS_FILE: &str = ".pipelight/secrets";
+/**
+The string that replaces a secret value.
+*/
+pub const MASK: &str = "********";
+
+const NONCE_SIZE: usize = 12;
+
+/**
+Lazy global that contains the loaded secrets to be redacted.
+*/
+pub static SECRETS: Lazy<Arc<Mutex<BTreeMap<String, String>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(BTreeMap::new())));
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Secrets {
+    pub path: PathBuf,
+    pub values: BTreeMap<String, String>,
+}
+
+impl Secrets {
+    /**
+    Load the project secrets, empty if the secrets file doesn't exist.
+    */
+    pub fn load() -> Result<Secrets> {
+        Secrets::load_from(Path::new(SECRETS_FILE))
+    }
+    pub fn load_from(path: &Path) -> Result<Secrets> {
+        if !path.exists() {
+            return Ok(Secrets {
+                path: path.to_owned(),
+                ..Secrets::default()
+            });
+        }
+        Secrets::load_with_key(path, &key()?)
+    }
+    /**
+    Load and decrypt the secrets file with the given key.
+    */
+    pub fn load_with_key(path: &Path, key: &Key) -> Result<Secrets> {
+        let mut secrets = Secrets {
+            path: path.to_owned(),
+            ..Secrets::default()
+        };
+        if !path.exists() {
+            return Ok(secrets);
+        }
+        let bytes = fs::read(path).into_diagnostic()?;
+        if bytes.len() < NONCE_SIZE {
+            return Err(Error::msg("The secrets file is corrupted"));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_SIZE);
+        let cipher = ChaCha20Poly1305::new(key);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::msg("Couldn't decrypt the secrets file with the current key"))?;
+        secrets.values = serde_json::from_slice(&plaintext).into_diagnostic()?;
+        Ok(secrets)
+    }
+    /**
+    Encrypt and write the secrets file.
+    */
+    pub fn save(&self) -> Result<()> {
+        self.save_with_key(&key()?)
+    }
+    /**
+    Encrypt the secrets with the given key and write the secrets file.
+    */
+    pub fn save_with_key(&self, key: &Key) -> Result<()> {
+        let plaintext = serde_json::to_vec(&self.values).into_diagnostic()?;
+        let cipher = ChaCha20Poly1305::new(key);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_ref())
+            .map_err(|_| Error::msg("Couldn't encrypt the secrets"))?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        write_private(&self.path, &bytes)
+    }
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.values.get(name).cloned()
+    }
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_owned(), value.to_owned());
+    }
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+    pub fn names(&self) -> Vec<String> {
+        self.values.keys().cloned().collect()
+    }
+    /**
+    Register the secret values for them to be redacted.
+    */
+    pub fn register(&self) {
+        SECRETS.lock().unwrap().extend(self.values.clone());
+    }
+}
+
+/**
+Returns a registered secret value.
+*/
+pub fn get(name: &str) -> Option<String> {
+    SECRETS.lock().unwrap().get(name).cloned()
+}
+
+/**
+Replace every registered secret value with the mask.
+Only apply it to plain values (outputs, commands),
+never to serialized data whose keys and syntax could be masked too.
+*/
+pub fn redact(string: &str) -> String {
+    let mut res = string.to_owned();
+    for value in SECRETS.lock().unwrap().values() {
+        if value.is_empty() {
+            continue;
+        }
+        res = res.replace(value, MASK);
+    }
+    res
+}
+
+/**
+Returns the encryption key from the environment or from the user key file.
+*/
+fn key() -> Result<Key> {
+    if let Ok(hex_key) = env::var("PIPELIGHT_SECRETS_KEY") {
+        return from_hex(&hex_key);
+    }
+    let path = key_file()?;
+    if path.exists() {
+        let hex_key = fs::read_to_string(&path).into_diagnostic()?;
+        from_hex(hex_key.trim())
+    } else {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        write_private(&path, hex::encode(key).as_bytes())?;
+        Ok(key)
+    }
+}
+fn key_file() -> Result<PathBuf> {
+    match dirs::config_dir() {
+        Some(dir) => Ok(dir.join("pipelight").join("secrets.key")),
+        None => Err(Error::msg(
+            "Couldn't find a user config directory to store the secrets key",
+        )),
+    }
+}
+fn from_hex(hex_key: &str) -> Result<Key> {
+    let bytes = hex::decode(hex_key).into_diagnostic()?;
+    if bytes.len() != 32 {
+        return Err(Error::msg("The secrets key must be 32 bytes long"));
+    }
+    Ok(*Key::from_slice(&bytes))
+}
+/**
+Write a file only readable by its owner.
+*/
+fn write_private(path: &Path, bytes: &[u8]) -> Result<()> {
+    fs::write(path, bytes).into_diagnostic()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).into_diagnostic()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod secrets {
+    use crate::secrets::{redact, Secrets, SECRETS};
+    use chacha20poly1305::Key;
+    use std::{env, fs, process};
+
+    #[test]
+    fn encrypt_and_redact() {
+        let key = Key::from([0; 32]);
+        let dir = env::temp_dir().join(format!("pipelight-secrets-{}", process::id()));
+        let path = dir.join("secrets");
+
+        let mut secrets = Secrets::load_with_key(&path, &key).unwrap();
+        secrets.set("encrypt_and_redact_token", "s3cr\"et");
+        secrets.save_with_key(&key).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        let loaded = Secrets::load_with_key(&path, &key);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_ne!(bytes, b"s3cr\"et");
+
+        let secrets = loaded.unwrap();
+        assert_eq!(
+            secrets.get("encrypt_and_redact_token").as_deref(),
+            Some("s3cr\"et")
+        );
+        secrets.register();
+        let redacted = redact("token=s3cr\"et");
+        SECRETS.lock().unwrap().remove("encrypt_and_redact_token");
+        assert_eq!(redacted, "token=********");
+    }
+}
@@ -0,0 +1,37 @@
+// Structs
+use crate::files::FileType;
+// Filesystem
+use std::path::PathBuf;
+// Traits
+use strum::IntoEnumIterator;
+
+/**
+Returns the directory of the user global config,
+"~/.config/pipelight" on linux.
+*/
+pub fn global_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|e| e.join("pipelight"))
+}
+
+/**
+Returns the path of the user global config file if it exists,
+like "~/.config/pipelight/pipelight.toml".
+*/
+pub fn global_config_file() -> Option<String> {
+    let dir = global_config_dir()?;
+    FileType::iter()
+        .map(|e| dir.join(format!("pipelight.{}", String::from(&e))))
+        .find(|e| e.is_file())
+        .map(|e| e.display().to_string())
+}
+
+/**
+Returns the directory of the global pipelines logs and outputs,
+"~/.local/state/pipelight" on linux.
+There is no project ".pipelight" directory for them.
+*/
+pub fn global_state_dir() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|e| e.join("pipelight"))
+}
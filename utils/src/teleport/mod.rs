@@ -1,5 +1,6 @@
 mod default;
 mod gate;
+mod global;
 mod portal;
 mod types;
 
@@ -7,4 +8,11 @@ mod types;
 mod test;
 
 // Re-export
+pub use global::*;
 pub use types::*;
+
+/**
+The directory of single pipeline definition files,
+relative to the config file directory.
+*/
+pub const PIPELINES_DIR: &str = ".pipelight/pipelines";
@@ -4,6 +4,7 @@ use std::env;
 use crate::files::FileType;
 // Struct
 use crate::teleport::types::Portal;
+use crate::teleport::PIPELINES_DIR;
 // Trait
 use strum::IntoEnumIterator;
 // Git
@@ -161,6 +162,16 @@ impl Portal {
                 }
             }
         }
+        // A pipelines directory can stand in for the config file
+        if !exists {
+            let directory = self.current.directory_path.clone().unwrap();
+            let path = Path::new(&directory).join(PIPELINES_DIR);
+            if path.is_dir() {
+                exists = true;
+                self.target.file(path.display().to_string())?;
+                self.target.directory(directory)?;
+            }
+        }
         if !exists {
             if self.parent().is_ok() {
                 self.search_prefix()?;
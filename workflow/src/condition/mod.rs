@@ -0,0 +1,164 @@
+/**
+A tiny expression language to conditionally run steps.
+
+```text
+tag != null && steps.test.status == "succeeded"
+action == "pre-push" || env.FORCE == "true"
+!(branch == "master") && steps["run tests"].status != "failed"
+```
+
+Expressions are made of:
+- literals: strings ("..." or '...'), `null`, `true` and `false`,
+- variables: `branch`, `tag`, `commit`, `action`, `env.NAME`, `params.NAME`,
+  `steps.NAME.status`, `pipeline.name` and `pipeline.uuid`,
+- operators: `==`, `!=`, `&&`, `||`, `!` and parenthesis.
+
+Unknown variables and unset environment variables evaluate to `null`.
+*/
+// Tests
+mod test;
+mod parse;
+// Structs
+use crate::types::{Parallel, Pipeline, Step, StepOrParallel, Trigger};
+use exec::{Statuable, Status};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+// Error Handling
+use miette::Result;
+
+/**
+A parsed condition.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expr {
+    Value(Value),
+    Variable(Vec<String>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Equal(Box<Expr>, Box<Expr>),
+    NotEqual(Box<Expr>, Box<Expr>),
+}
+
+/**
+Values handled by the expression language.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    String(String),
+}
+
+/**
+The environment a condition is evaluated against.
+*/
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Context {
+    pub pipeline_name: Option<String>,
+    pub pipeline_uuid: Option<String>,
+    pub trigger: Option<Trigger>,
+    pub env: HashMap<String, String>,
+    pub params: BTreeMap<String, String>,
+    pub steps: HashMap<String, Status>,
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(boolean) => *boolean,
+            Value::String(string) => !string.is_empty(),
+        }
+    }
+}
+
+impl Expr {
+    /**
+    Parse a condition string.
+    */
+    pub fn new(condition: &str) -> Result<Expr> {
+        parse::parse(condition)
+    }
+    /**
+    Evaluate the condition against the context.
+    */
+    pub fn eval(&self, context: &Context) -> bool {
+        self.value(context).is_truthy()
+    }
+    fn value(&self, context: &Context) -> Value {
+        match self {
+            Expr::Value(value) => value.to_owned(),
+            Expr::Variable(path) => {
+                context.get(&path.iter().map(|e| e.as_str()).collect::<Vec<&str>>())
+            }
+            Expr::Not(e) => Value::Bool(!e.eval(context)),
+            Expr::And(a, b) => Value::Bool(a.eval(context) && b.eval(context)),
+            Expr::Or(a, b) => Value::Bool(a.eval(context) || b.eval(context)),
+            Expr::Equal(a, b) => Value::Bool(a.value(context) == b.value(context)),
+            Expr::NotEqual(a, b) => Value::Bool(a.value(context) != b.value(context)),
+        }
+    }
+}
+
+impl Context {
+    /**
+    Build the context of a step that is about to run in the given pipeline.
+    */
+    pub fn new(pipeline: &Pipeline, env: &HashMap<String, String>) -> Context {
+        let mut steps = HashMap::new();
+        for step_or_parallel in &pipeline.steps {
+            match step_or_parallel {
+                StepOrParallel::Step(step) => add_step_status(&mut steps, step),
+                StepOrParallel::Parallel(Parallel { steps: inner, .. }) => {
+                    for step in inner {
+                        add_step_status(&mut steps, step);
+                    }
+                }
+            }
+        }
+        Context {
+            pipeline_name: Some(pipeline.name.to_owned()),
+            pipeline_uuid: Some(pipeline.uuid.to_string()),
+            trigger: pipeline.event.as_ref().map(|e| e.trigger.clone()),
+            env: env.to_owned(),
+            params: pipeline
+                .event
+                .as_ref()
+                .and_then(|e| e.params.clone())
+                .unwrap_or_default(),
+            steps,
+        }
+    }
+    /**
+    Resolve a variable path.
+    */
+    pub fn get(&self, path: &[&str]) -> Value {
+        let value = match path {
+            ["pipeline", "name"] => self.pipeline_name.clone(),
+            ["pipeline", "uuid"] => self.pipeline_uuid.clone(),
+            ["branch"] => self.trigger.as_ref().and_then(|e| e.get_branch().ok()?),
+            ["tag"] => self.trigger.as_ref().and_then(|e| e.get_tag().ok()?),
+            ["commit"] => self.trigger.as_ref().and_then(|e| e.get_commit().ok()?),
+            ["action"] => self
+                .trigger
+                .as_ref()
+                .and_then(|e| e.get_action().ok()?)
+                .map(|e| String::from(&e)),
+            ["env", name] => self.env.get(*name).cloned().or_else(|| env::var(name).ok()),
+            ["params", name] => self.params.get(*name).cloned(),
+            ["steps", name, "status"] => self.steps.get(*name).map(String::from),
+            _ => None,
+        };
+        match value {
+            Some(value) => Value::String(value),
+            None => Value::Null,
+        }
+    }
+}
+
+fn add_step_status(steps: &mut HashMap<String, Status>, step: &Step) {
+    if let Some(status) = step.get_status() {
+        steps.insert(step.name.to_owned(), status);
+    }
+}
@@ -0,0 +1,226 @@
+// Structs
+use super::{Expr, Value};
+// Error Handling
+use crate::error::ConditionError;
+use miette::Result;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Ident(String),
+    String(String),
+    Dot,
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Not,
+    And,
+    Or,
+    Equal,
+    NotEqual,
+}
+
+/**
+Split the condition into tokens with their offset in the condition string.
+*/
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>> {
+    let mut tokens = vec![];
+    let chars: Vec<(usize, char)> = src.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let next = chars.get(i + 1).map(|e| e.1);
+        let token = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => Token::And,
+            ('|', Some('|')) => Token::Or,
+            ('=', Some('=')) => Token::Equal,
+            ('!', Some('=')) => Token::NotEqual,
+            ('!', _) => Token::Not,
+            ('.', _) => Token::Dot,
+            ('[', _) => Token::LeftBracket,
+            (']', _) => Token::RightBracket,
+            ('(', _) => Token::LeftParen,
+            (')', _) => Token::RightParen,
+            ('"', _) | ('\'', _) => {
+                let mut string = "".to_owned();
+                i += 1;
+                while i < chars.len() && chars[i].1 != c {
+                    string.push(chars[i].1);
+                    i += 1;
+                }
+                if i == chars.len() {
+                    return Err(ConditionError::new("unterminated string", src, offset).into());
+                }
+                i += 1;
+                tokens.push((Token::String(string), offset));
+                continue;
+            }
+            (c, _) if c.is_alphanumeric() || c == '_' || c == '-' => {
+                let mut ident = "".to_owned();
+                while i < chars.len()
+                    && (chars[i].1.is_alphanumeric() || chars[i].1 == '_' || chars[i].1 == '-')
+                {
+                    ident.push(chars[i].1);
+                    i += 1;
+                }
+                tokens.push((Token::Ident(ident), offset));
+                continue;
+            }
+            _ => {
+                let message = format!("unexpected character {:?}", c);
+                return Err(ConditionError::new(&message, src, offset).into());
+            }
+        };
+        // Two characters operators
+        if matches!(
+            token,
+            Token::And | Token::Or | Token::Equal | Token::NotEqual
+        ) {
+            i += 1;
+        }
+        i += 1;
+        tokens.push((token, offset));
+    }
+    Ok(tokens)
+}
+
+/**
+A recursive descent parser over the condition tokens.
+*/
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|e| &e.0)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|e| e.0.clone());
+        self.position += 1;
+        token
+    }
+    fn error(&self, message: &str) -> miette::Report {
+        let offset = match self.tokens.get(self.position) {
+            Some((_, offset)) => *offset,
+            None => self.src.len().saturating_sub(1),
+        };
+        ConditionError::new(message, self.src, offset).into()
+    }
+    fn expect(&mut self, token: Token) -> Result<()> {
+        if self.peek() == Some(&token) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", token)))
+        }
+    }
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.comparison()
+    }
+    fn comparison(&mut self) -> Result<Expr> {
+        let expr = self.primary()?;
+        match self.peek() {
+            Some(Token::Equal) => {
+                self.next();
+                Ok(Expr::Equal(Box::new(expr), Box::new(self.primary()?)))
+            }
+            Some(Token::NotEqual) => {
+                self.next();
+                Ok(Expr::NotEqual(Box::new(expr), Box::new(self.primary()?)))
+            }
+            _ => Ok(expr),
+        }
+    }
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::LeftParen) => {
+                let expr = self.or()?;
+                self.expect(Token::RightParen)?;
+                Ok(expr)
+            }
+            Some(Token::String(string)) => Ok(Expr::Value(Value::String(string))),
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "null" => Ok(Expr::Value(Value::Null)),
+                "true" => Ok(Expr::Value(Value::Bool(true))),
+                "false" => Ok(Expr::Value(Value::Bool(false))),
+                _ => self.variable(ident),
+            },
+            _ => {
+                self.position -= 1;
+                Err(self.error("expected a value or a variable"))
+            }
+        }
+    }
+    fn variable(&mut self, head: String) -> Result<Expr> {
+        let mut path = vec![head];
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.next();
+                    match self.next() {
+                        Some(Token::Ident(ident)) => path.push(ident),
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.error("expected a variable name"));
+                        }
+                    }
+                }
+                Some(Token::LeftBracket) => {
+                    self.next();
+                    match self.next() {
+                        Some(Token::String(string)) => path.push(string),
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.error("expected a quoted name"));
+                        }
+                    }
+                    self.expect(Token::RightBracket)?;
+                }
+                _ => return Ok(Expr::Variable(path)),
+            }
+        }
+    }
+}
+
+/**
+Parse a condition string into an expression tree.
+*/
+pub fn parse(src: &str) -> Result<Expr> {
+    let mut parser = Parser {
+        src,
+        tokens: tokenize(src)?,
+        position: 0,
+    };
+    let expr = parser.or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected token"));
+    }
+    Ok(expr)
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod condition {
+    use crate::condition::{Context, Expr};
+    use crate::types::{Trigger, TriggerBranch, TriggerTag};
+    use exec::Status;
+    use std::collections::HashMap;
+    use utils::git::{Flag, Hook};
+
+    fn context() -> Context {
+        Context {
+            trigger: Some(Trigger::TriggerTag(TriggerTag {
+                action: Some(Flag::Hook(Hook::PrePush)),
+                tag: Some("v1.0.0".to_owned()),
+                ..TriggerTag::default()
+            })),
+            env: HashMap::from([("FORCE".to_owned(), "true".to_owned())]),
+            steps: HashMap::from([
+                ("test".to_owned(), Status::Succeeded),
+                ("run lints".to_owned(), Status::Failed),
+            ]),
+            ..Context::default()
+        }
+    }
+    #[test]
+    fn trigger_variables() {
+        let expr = Expr::new("tag != null && branch == null").unwrap();
+        assert!(expr.eval(&context()));
+        let expr = Expr::new("action == 'pre-push'").unwrap();
+        assert!(expr.eval(&context()));
+
+        let context = Context {
+            trigger: Some(Trigger::TriggerBranch(TriggerBranch {
+                branch: Some("master".to_owned()),
+                ..TriggerBranch::default()
+            })),
+            ..Context::default()
+        };
+        let expr = Expr::new("tag != null").unwrap();
+        assert!(!expr.eval(&context));
+    }
+    #[test]
+    fn steps_and_env() {
+        let expr = Expr::new(r#"tag != null && steps.test.status == "succeeded""#).unwrap();
+        assert!(expr.eval(&context()));
+        let expr = Expr::new(r#"!(steps["run lints"].status == "failed") || env.FORCE"#).unwrap();
+        assert!(expr.eval(&context()));
+        let expr = Expr::new("steps.unknown.status").unwrap();
+        assert!(!expr.eval(&context()));
+    }
+    #[test]
+    fn parse_errors() {
+        assert!(Expr::new("tag ==").is_err());
+        assert!(Expr::new("(tag != null").is_err());
+        assert!(Expr::new("tag = 'v1'").is_err());
+        assert!(Expr::new("'unterminated").is_err());
+    }
+}
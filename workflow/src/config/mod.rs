@@ -7,6 +7,7 @@ use miette::Result;
 // Struct
 use crate::pipeline::Filters;
 use crate::types::Config;
+use utils::dates::convert::iso8601_to_std_duration;
 use utils::git::Flag;
 
 impl Config {
@@ -15,6 +16,24 @@ impl Config {
         Ok(config)
     }
     /**
+    Add the pipelines of the user global config.
+    Project pipelines take precedence over global pipelines with the same name,
+    and project options over global options.
+    */
+    pub fn merge_global(&mut self, global: Config) {
+        let mut pipelines = self.pipelines.clone().unwrap_or_default();
+        for mut pipeline in global.pipelines.unwrap_or_default() {
+            if !pipelines.iter().any(|e| e.name == pipeline.name) {
+                pipeline.global = true;
+                pipelines.push(pipeline);
+            }
+        }
+        self.pipelines = Some(pipelines);
+        if self.options.is_none() {
+            self.options = global.options;
+        }
+    }
+    /**
     Check if any of the pipelines have a trigger with "watch" flag.
     */
     pub fn has_watchable(&self) -> Result<bool> {
@@ -51,6 +70,19 @@ impl Config {
         }
         Ok(false)
     }
+    /**
+    Check every pipeline steps dependencies for unknown steps and cycles.
+    */
+    pub fn has_valid_graphs(&self) -> Result<()> {
+        if let Some(pipelines) = &self.pipelines {
+            for pipeline in pipelines {
+                if pipeline.is_graph() {
+                    pipeline.get_graph()?;
+                }
+            }
+        }
+        Ok(())
+    }
     /**
      Report if config has a global options.attach property
     */
@@ -85,4 +117,15 @@ impl Config {
             Ok(false)
         }
     }
+    /**
+    Returns the quiet period that coalesces a burst of file changes
+    into a single watch trigger, if any.
+    */
+    pub fn get_watch_debounce(&self) -> Result<Option<std::time::Duration>> {
+        let debounce = self.options.as_ref().and_then(|e| e.watch_debounce.clone());
+        match debounce {
+            Some(debounce) => Ok(Some(iso8601_to_std_duration(&debounce)?)),
+            None => Ok(None),
+        }
+    }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod config {
-    use crate::types::{Config, Pipeline};
+    use crate::types::{Config, ConfigOpts, Pipeline};
     use crate::{Trigger, TriggerBranch, TriggerTag};
     use utils::git::{Flag, Special};
     // Error Handling
@@ -37,4 +37,42 @@ mod config {
         let boolean = config.has_watchable().unwrap();
         assert!(!boolean);
     }
+    #[test]
+    fn merge_global_pipelines() {
+        let pipeline = |name: &str| Pipeline {
+            name: name.to_owned(),
+            ..Pipeline::default()
+        };
+        let mut config = Config {
+            pipelines: Some(vec![pipeline("test")]),
+            ..Config::default()
+        };
+        let global = Config {
+            pipelines: Some(vec![pipeline("test"), pipeline("backup")]),
+            ..Config::default()
+        };
+        config.merge_global(global);
+        let pipelines = config.pipelines.unwrap();
+        // Project pipelines take precedence
+        assert_eq!(pipelines.len(), 2);
+        assert!(!pipelines[0].global);
+        assert_eq!(pipelines[1].name, "backup");
+        assert!(pipelines[1].global);
+        assert_ne!(pipelines[1].get_log_dir(), pipelines[0].get_log_dir());
+    }
+    #[test]
+    fn watch_debounce() {
+        let mut config = Config::default();
+        assert_eq!(config.get_watch_debounce().unwrap(), None);
+        let options = cast::ConfigOpts {
+            attach: None,
+            log_level: None,
+            watch_debounce: Some("500ms".to_owned()),
+        };
+        config.options = Some(ConfigOpts::from(&options));
+        assert_eq!(
+            config.get_watch_debounce().unwrap(),
+            Some(std::time::Duration::from_millis(500))
+        );
+    }
 }
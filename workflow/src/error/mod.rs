@@ -1,5 +1,5 @@
 // Error Handling
-use miette::{Diagnostic, ErrReport, MietteDiagnostic, MietteError, Report, Result};
+use miette::{Diagnostic, ErrReport, MietteDiagnostic, MietteError, Report, Result, SourceSpan};
 use thiserror::Error;
 
 /**
@@ -15,3 +15,46 @@ impl IsError {
         Ok(diag)
     }
 }
+
+/**
+A pipeline parameter report type with hint.
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(code(workflow::param))]
+#[error("{message}")]
+pub struct ParamError {
+    pub message: String,
+    #[help]
+    pub help: String,
+}
+impl ParamError {
+    pub fn new(message: &str, help: &str) -> Self {
+        ParamError {
+            message: message.to_owned(),
+            help: help.to_owned(),
+        }
+    }
+}
+
+/**
+A step condition report type with code span on the faulty expression part.
+*/
+#[derive(Error, Diagnostic, Debug)]
+#[diagnostic(code(workflow::condition))]
+#[error("Could not parse the condition: {message}")]
+pub struct ConditionError {
+    pub message: String,
+    #[label("here")]
+    pub at: SourceSpan,
+    #[source_code]
+    pub src: String,
+}
+impl ConditionError {
+    pub fn new(message: &str, src: &str, offset: usize) -> Self {
+        ConditionError {
+            message: message.to_owned(),
+            at: SourceSpan::new(offset.into(), 1.into()),
+            src: src.to_owned(),
+        }
+    }
+}
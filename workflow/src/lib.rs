@@ -5,6 +5,7 @@
 // Error struct
 pub mod error;
 // Methods
+pub mod condition;
 mod config;
 pub mod globals;
 mod logs;
@@ -7,21 +7,41 @@ use crate::traits::Getters;
 use miette::{Error, IntoDiagnostic, Result};
 // Global vars
 use crate::globals::LOGS;
+use crate::logs::global_logs_dir;
+// Filesystem
+use std::env;
+use std::path::Path;
+use uuid::Uuid;
 
 impl Logs {
     /**
-    Read logs and store them into a global variable.
+    Read the project and global pipelines logs and store them into a global variable.
     Sorted by ascending date by default.
     */
     pub fn hydrate(&mut self) -> Result<Self> {
         // Get global
         if LOGS.lock().unwrap().clone().is_none() {
             // Read log files
-            let json_logs: Vec<String> = cast::Logs::read(".pipelight/logs/")?;
+            let dirs: Vec<String> = [Some(".pipelight/logs/".to_owned()), global_logs_dir()]
+                .into_iter()
+                .flatten()
+                .filter(|e| Path::new(e).exists())
+                .collect();
+            if dirs.is_empty() {
+                let message = "No logs to display.";
+                return Err(Error::msg(message));
+            }
+            let mut json_logs: Vec<String> = vec![];
+            for dir in dirs {
+                json_logs.extend(cast::Logs::read(&dir)?);
+            }
+            let project = env::current_dir().ok().map(|e| e.display().to_string());
             let mut pipelines: Vec<Pipeline> = vec![];
             for json in json_logs {
                 let pipeline = serde_json::from_str::<Pipeline>(&json).into_diagnostic()?;
-                pipelines.push(pipeline);
+                if pipeline.is_from_project(project.as_deref()) {
+                    pipelines.push(pipeline);
+                }
             }
             pipelines = Filters::sort_by_date_asc(pipelines)?;
             // Set global
@@ -78,4 +98,14 @@ impl Logs {
             }
         }
     }
+    pub fn get_by_uuid(uuid: &Uuid) -> Result<Pipeline> {
+        let pipelines = Logs::get()?;
+        match pipelines.into_iter().find(|p| p.uuid == *uuid) {
+            Some(p) => Ok(p),
+            None => {
+                let message = format!("Couldn't find a pipeline run {}, in logs", uuid);
+                Err(Error::msg(message))
+            }
+        }
+    }
 }
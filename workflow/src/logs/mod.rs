@@ -13,6 +13,15 @@ use rayon::prelude::*;
 use miette::Result;
 // Global vars
 use crate::globals::LOGS;
+use utils::teleport::global_state_dir;
+
+/**
+Returns the log directory of the global pipelines,
+that have no project ".pipelight" directory.
+*/
+pub(crate) fn global_logs_dir() -> Option<String> {
+    global_state_dir().map(|e| e.join("logs").display().to_string())
+}
 
 impl Logs {
     /**
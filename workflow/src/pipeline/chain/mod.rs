@@ -0,0 +1,96 @@
+// Test
+mod test;
+// Structs
+use crate::types::{Pipeline, Trigger, Upstream};
+use utils::git::{Flag, Special};
+// Traits
+use crate::traits::Getters;
+use exec::Status;
+// Error Handling
+use miette::Result;
+
+/**
+The statuses of an ended pipeline run.
+*/
+const FINAL_STATUSES: [Status; 4] = [
+    Status::Succeeded,
+    Status::Failed,
+    Status::Aborted,
+    Status::TimedOut,
+];
+
+impl From<&Pipeline> for Upstream {
+    fn from(e: &Pipeline) -> Upstream {
+        // Append the run's own upstream to the chain
+        let mut chain = vec![];
+        let upstream = e
+            .event
+            .as_ref()
+            .and_then(|e| e.trigger.get_upstream().unwrap());
+        if let Some(upstream) = upstream {
+            chain = upstream.chain;
+            chain.push(upstream.pipeline);
+        }
+        Upstream {
+            pipeline: e.name.clone(),
+            uuid: e.uuid,
+            status: e.status.clone(),
+            chain,
+        }
+    }
+}
+
+impl Trigger {
+    /**
+    Return the triggering env of the runs chained to an upstream run,
+    the upstream env with the "after" action.
+    */
+    pub fn chained(&self, upstream: &Upstream) -> Trigger {
+        let mut env = self.clone();
+        let action = Some(Flag::Special(Special::After));
+        match &mut env {
+            Trigger::TriggerBranch(e) => {
+                e.action = action;
+                e.upstream = Some(upstream.to_owned());
+            }
+            Trigger::TriggerTag(e) => {
+                e.action = action;
+                e.upstream = Some(upstream.to_owned());
+            }
+        };
+        env
+    }
+}
+
+impl Pipeline {
+    /**
+    Return the pipelines to run once this pipeline run has ended,
+    whose "after" triggers match the pipeline name and final status.
+
+    A pipeline is never chained to itself nor to a pipeline of its upstream chain,
+    to prevent endless loops.
+    */
+    pub fn get_downstream(&self) -> Result<Vec<Pipeline>> {
+        match &self.status {
+            Some(status) if FINAL_STATUSES.contains(status) => {}
+            _ => return Ok(vec![]),
+        };
+        let upstream = Upstream::from(self);
+        let env = match &self.event {
+            Some(event) => event.trigger.chained(&upstream),
+            None => Trigger::get()?.chained(&upstream),
+        };
+        let mut pipelines = vec![];
+        for pipeline in Pipeline::get()? {
+            if pipeline.name == upstream.pipeline || upstream.chain.contains(&pipeline.name) {
+                continue;
+            }
+            if let Some(triggers) = pipeline.triggers.clone() {
+                if env.has_match_strict(triggers)? {
+                    pipelines.push(pipeline);
+                }
+            }
+        }
+        Ok(pipelines)
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod chain {
+    use crate::globals::CONFIG;
+    use crate::types::{
+        Config, Event, Pipeline, Status, Trigger, TriggerAfter, TriggerBranch, Upstream,
+    };
+    use utils::git::{Flag, Special};
+
+    fn chained(name: &str, upstream: Option<&str>, status: Option<Vec<Status>>) -> Pipeline {
+        Pipeline {
+            name: name.to_owned(),
+            triggers: Some(vec![Trigger::TriggerBranch(TriggerBranch {
+                action: Some(Flag::Special(Special::After)),
+                after: Some(TriggerAfter {
+                    pipeline: upstream.map(str::to_owned),
+                    status,
+                }),
+                ..TriggerBranch::default()
+            })]),
+            ..Pipeline::default()
+        }
+    }
+    fn names(pipelines: Vec<Pipeline>) -> Vec<String> {
+        pipelines.into_iter().map(|e| e.name).collect()
+    }
+
+    #[test]
+    fn downstream_pipelines() {
+        *CONFIG.lock().unwrap() = Config {
+            pipelines: Some(vec![
+                chained("build", Some("deploy"), None),
+                chained("deploy", Some("build"), Some(vec![Status::Succeeded])),
+                chained("notify-failure", None, Some(vec![Status::Failed])),
+            ]),
+            ..Config::default()
+        };
+        let mut build = Pipeline {
+            name: "build".to_owned(),
+            status: Some(Status::Succeeded),
+            ..Pipeline::default()
+        };
+        assert_eq!(names(build.get_downstream().unwrap()), vec!["deploy"]);
+
+        build.status = Some(Status::Failed);
+        assert_eq!(
+            names(build.get_downstream().unwrap()),
+            vec!["notify-failure"]
+        );
+
+        // Running pipelines have no downstream
+        build.status = Some(Status::Running);
+        assert!(build.get_downstream().unwrap().is_empty());
+
+        // A failing notification doesn't notify itself
+        let notify = Pipeline {
+            name: "notify-failure".to_owned(),
+            status: Some(Status::Failed),
+            ..Pipeline::default()
+        };
+        assert!(notify.get_downstream().unwrap().is_empty());
+
+        // The upstream chain is never run again
+        build.status = Some(Status::Succeeded);
+        let deploy = Pipeline {
+            name: "deploy".to_owned(),
+            status: Some(Status::Succeeded),
+            event: Some(Event {
+                trigger: Trigger::TriggerBranch(TriggerBranch {
+                    upstream: Some(Upstream::from(&build)),
+                    ..TriggerBranch::default()
+                }),
+                ..Event::new()
+            }),
+            ..Pipeline::default()
+        };
+        assert!(deploy.get_downstream().unwrap().is_empty());
+        assert_eq!(Upstream::from(&deploy).chain, vec!["build"]);
+    }
+}
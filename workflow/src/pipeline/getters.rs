@@ -50,6 +50,16 @@ impl Pipeline {
         }
         Ok(procs)
     }
+    pub fn get_conditions(&self) -> Result<Vec<String>> {
+        let mut conditions: Vec<String> = vec![];
+        for step in &self.steps {
+            conditions.extend(step.get_conditions()?);
+        }
+        if let Some(fallback) = &self.fallback {
+            conditions.extend(fallback.get_conditions()?);
+        }
+        Ok(conditions)
+    }
     pub fn get_default_loglevel(&self) -> Result<LevelFilter> {
         if let Some(options) = &self.options {
             if let Some(log_level) = options.log_level {
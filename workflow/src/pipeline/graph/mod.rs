@@ -0,0 +1,145 @@
+// Test
+mod test;
+// Structs
+use crate::types::{Pipeline, PipelineOpts, StepOrParallel};
+use std::collections::HashMap;
+use std::thread;
+// Error Handling
+use crate::error::IsError;
+use miette::Result;
+
+/**
+Steps can declare dependencies on other steps with `needs`.
+A pipeline with at least one dependency is run as a graph:
+every step starts as soon as the steps it needs have succeeded.
+
+Only top level steps can declare dependencies.
+A step inside a parallel group can be needed, in which case
+the whole parallel group is awaited.
+*/
+impl Pipeline {
+    /**
+    Returns true if any step declares dependencies.
+    */
+    pub fn is_graph(&self) -> bool {
+        self.steps.iter().any(|e| match e {
+            StepOrParallel::Step(step) => step.needs.is_some(),
+            StepOrParallel::Parallel(_) => false,
+        })
+    }
+    /**
+    Returns the maximum number of steps running at once.
+    Defaults to the available parallelism.
+    */
+    pub fn get_concurrency(&self) -> usize {
+        if let Some(PipelineOpts {
+            concurrency: Some(concurrency),
+            ..
+        }) = &self.options
+        {
+            return (*concurrency).max(1);
+        }
+        thread::available_parallelism().map_or(1, |e| e.get())
+    }
+    /**
+    Returns, for every top level step, the indexes of the steps it needs.
+    Fails on unknown step names and on dependency cycles.
+    */
+    pub fn get_graph(&self) -> Result<Vec<Vec<usize>>> {
+        // Map step names to top level indexes
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        for (i, step_or_parallel) in self.steps.iter().enumerate() {
+            match step_or_parallel {
+                StepOrParallel::Step(step) => {
+                    indexes.insert(step.name.to_owned(), i);
+                }
+                StepOrParallel::Parallel(parallel) => {
+                    for step in &parallel.steps {
+                        indexes.insert(step.name.to_owned(), i);
+                    }
+                }
+            }
+        }
+        // Resolve dependencies
+        let mut graph: Vec<Vec<usize>> = vec![];
+        for step_or_parallel in &self.steps {
+            let mut dependencies = vec![];
+            if let StepOrParallel::Step(step) = step_or_parallel {
+                for need in step.needs.clone().unwrap_or_default() {
+                    match indexes.get(&need) {
+                        Some(index) => dependencies.push(*index),
+                        None => {
+                            let message = format!(
+                                "In pipeline {:?}, step {:?} needs an unknown step {:?}",
+                                self.name, step.name, need
+                            );
+                            let help = "Check the step names in \"needs\"";
+                            return Err(IsError::new(&message, help)?.into());
+                        }
+                    }
+                }
+            }
+            graph.push(dependencies);
+        }
+        self.has_no_cycle(&graph)?;
+        Ok(graph)
+    }
+    /**
+    Depth first search for a dependency cycle.
+    */
+    fn has_no_cycle(&self, graph: &[Vec<usize>]) -> Result<()> {
+        // 0: not visited, 1: in the current path, 2: done
+        let mut colors = vec![0; graph.len()];
+        let mut path: Vec<usize> = vec![];
+        for i in 0..graph.len() {
+            if let Some(cycle) = visit(graph, i, &mut colors, &mut path) {
+                let names: Vec<String> = cycle.iter().map(|e| self.get_step_name(*e)).collect();
+                let message = format!(
+                    "In pipeline {:?}, steps have a dependency cycle: {}",
+                    self.name,
+                    names.join(" -> ")
+                );
+                let help = "Remove one of the \"needs\" of the cycle";
+                return Err(IsError::new(&message, help)?.into());
+            }
+        }
+        Ok(())
+    }
+    fn get_step_name(&self, index: usize) -> String {
+        match &self.steps[index] {
+            StepOrParallel::Step(step) => step.name.to_owned(),
+            StepOrParallel::Parallel(_) => "parallel".to_owned(),
+        }
+    }
+}
+
+/**
+Returns the cycle as a list of step indexes if one is reachable from the node.
+*/
+fn visit(
+    graph: &[Vec<usize>],
+    node: usize,
+    colors: &mut Vec<u8>,
+    path: &mut Vec<usize>,
+) -> Option<Vec<usize>> {
+    match colors[node] {
+        2 => return None,
+        1 => {
+            let start = path.iter().position(|e| *e == node).unwrap();
+            let mut cycle = path[start..].to_vec();
+            cycle.push(node);
+            return Some(cycle);
+        }
+        _ => {}
+    }
+    colors[node] = 1;
+    path.push(node);
+    for dependency in &graph[node] {
+        if let Some(cycle) = visit(graph, *dependency, colors, path) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    colors[node] = 2;
+    None
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod graph {
+    use crate::types::{Pipeline, Step, StepOrParallel};
+
+    fn step(name: &str, needs: &[&str]) -> StepOrParallel {
+        StepOrParallel::Step(Step {
+            name: name.to_owned(),
+            needs: Some(needs.iter().map(|e| e.to_string()).collect()),
+            ..Step::default()
+        })
+    }
+    #[test]
+    fn get_graph() {
+        let p = Pipeline {
+            steps: vec![
+                step("build", &[]),
+                step("test", &["build"]),
+                step("lint", &[]),
+            ],
+            ..Pipeline::default()
+        };
+        assert!(p.is_graph());
+        assert_eq!(p.get_graph().unwrap(), vec![vec![], vec![0], vec![]]);
+    }
+    #[test]
+    fn unknown_need() {
+        let p = Pipeline {
+            steps: vec![step("test", &["build"])],
+            ..Pipeline::default()
+        };
+        assert!(p.get_graph().is_err());
+    }
+    #[test]
+    fn cycle() {
+        let p = Pipeline {
+            steps: vec![
+                step("a", &["c"]),
+                step("b", &["a"]),
+                step("c", &["b"]),
+                step("d", &[]),
+            ],
+            ..Pipeline::default()
+        };
+        let err = p.get_graph().unwrap_err();
+        assert!(err.to_string().contains("a -> c -> b -> a"));
+    }
+}
@@ -3,7 +3,10 @@ mod test;
 // Unix process manipulation
 use rustix::process::test_kill_process;
 // Structs
+use crate::condition::Expr;
+use crate::error::IsError;
 use crate::types::{Logs, Pipeline, Trigger};
+use std::path::Path;
 use utils::git::{Flag, Special};
 // Traits
 use exec::Status;
@@ -49,6 +52,33 @@ impl Pipeline {
         Err(Error::msg(message))
     }
     /**
+    Check that every command working directory exists.
+    Should be called before the pipeline starts to fail early
+    instead of failing on a step in the middle of the run.
+    */
+    pub fn has_valid_cwd(&self) -> Result<()> {
+        for process in self.get_procs()? {
+            if let Some(cwd) = process.cwd {
+                if !Path::new(&cwd).is_dir() {
+                    let message = format!("The working directory {:?} doesn't exist", cwd);
+                    let help = "Create the directory or fix the step \"cwd\" option";
+                    return Err(IsError::new(&message, help)?.into());
+                }
+            }
+        }
+        Ok(())
+    }
+    /**
+    Check that every step condition is a valid expression.
+    Should be called before the pipeline starts.
+    */
+    pub fn has_valid_conditions(&self) -> Result<()> {
+        for condition in self.get_conditions()? {
+            Expr::new(&condition)?;
+        }
+        Ok(())
+    }
+    /**
     Check if the pipeline instance(loaded from logs) is running.
 
     It cascade checks the following conditions:
@@ -123,6 +153,17 @@ impl Pipeline {
             false
         }
     }
+    /**
+    Whether the pipeline log belongs to the project.
+    Global pipelines log into the same user directory for every project,
+    so they only belong to the project they were run from.
+    */
+    pub fn is_from_project(&self, project: Option<&str>) -> bool {
+        if !self.global {
+            return true;
+        }
+        self.event.as_ref().and_then(|e| e.project.as_deref()) == project
+    }
     /**
      Report if pipeline has options
     */
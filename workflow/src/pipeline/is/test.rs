@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod is {
-    use crate::types::{Command, Pipeline, Step, StepOrParallel};
+    use crate::types::{Command, Event, Pipeline, Step, StepOpts, StepOrParallel};
     /**
     Test if a triggered pipeline has an already running instance.
     */
@@ -8,4 +8,41 @@ mod is {
     fn is_triggerable_strict() {}
     #[test]
     fn is_triggerable() {}
+    #[test]
+    fn has_valid_cwd() {
+        let mut command = Command::new("pwd");
+        command.process.cwd = Some("/tmp".to_owned());
+        let mut p = Pipeline {
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![command],
+                options: Some(StepOpts {
+                    cwd: Some("/tmp".to_owned()),
+                    ..StepOpts::default()
+                }),
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        assert!(p.has_valid_cwd().is_ok());
+        if let StepOrParallel::Step(step) = &mut p.steps[0] {
+            step.commands[0].process.cwd = Some("/not/a/directory".to_owned());
+        }
+        assert!(p.has_valid_cwd().is_err());
+    }
+    #[test]
+    fn is_from_project() {
+        let mut p = Pipeline {
+            global: true,
+            event: Some(Event {
+                project: Some("/home/user/a".to_owned()),
+                ..Event::new()
+            }),
+            ..Pipeline::default()
+        };
+        assert!(p.is_from_project(Some("/home/user/a")));
+        assert!(!p.is_from_project(Some("/home/user/b")));
+        // Project pipelines logs are in the project directory
+        p.global = false;
+        assert!(p.is_from_project(Some("/home/user/b")));
+    }
 }
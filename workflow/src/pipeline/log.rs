@@ -1,5 +1,6 @@
 // Structs
-use crate::types::{Pipeline, StepOrParallel};
+use crate::logs::global_logs_dir;
+use crate::types::{Command, Fallback, Pipeline, Step, StepOrParallel};
 // Traits
 use exec::{Statuable, Status};
 // Globals
@@ -7,6 +8,7 @@ use once_cell::sync::Lazy;
 use std::io::Write;
 use std::sync::{Arc, Mutex};
 use utils::globals::LOGGER;
+use utils::secrets;
 // Fylesystem manipulation
 use std::fs;
 use std::fs::{create_dir_all, File};
@@ -22,12 +24,28 @@ pub static OUTDIR: Lazy<Arc<Mutex<String>>> =
     Lazy::new(|| Arc::new(Mutex::new(".pipelight/logs".to_owned())));
 
 impl Pipeline {
+    /**
+    Returns the absolute path of the log directory.
+    Global pipelines log into the user state directory.
+    */
+    pub fn get_log_dir(&self) -> String {
+        if self.global {
+            if let Some(dir) = global_logs_dir() {
+                return dir;
+            }
+        }
+        let dir = OUTDIR.lock().unwrap().clone();
+        match std::env::current_dir() {
+            Ok(cwd) => cwd.join(dir).to_str().unwrap().to_owned(),
+            Err(_) => dir,
+        }
+    }
     /**
     Delete the pipeline log file.
     */
     pub fn clean(&self) -> Result<()> {
         //Ensure dir
-        let dir = OUTDIR.lock().unwrap();
+        let dir = self.get_log_dir();
         fs::create_dir_all(dir.clone()).into_diagnostic()?;
 
         let stdout_path = format!("{}/{}.json", dir.clone(), self.uuid);
@@ -48,10 +66,10 @@ impl Pipeline {
     */
     pub fn log(&self) -> Result<()> {
         //Ensure dir
-        let dir = OUTDIR.lock().unwrap();
+        let dir = self.get_log_dir();
         fs::create_dir_all(dir.clone()).into_diagnostic()?;
 
-        let json = serde_json::to_string(&self).unwrap() + "\n";
+        let json = serde_json::to_string(&self.redacted()).unwrap() + "\n";
 
         let stdout_path = format!("{}/{}.json", dir.clone(), self.uuid);
         let mut f = File::options()
@@ -64,6 +82,18 @@ impl Pipeline {
         Ok(())
     }
     /**
+    Returns a copy of the pipeline with the secret values masked
+    in the commands inputs, outputs and environment.
+    */
+    pub fn redacted(&self) -> Pipeline {
+        let mut pipeline = self.clone();
+        redact_steps(&mut pipeline.steps);
+        if let Some(fallback) = &mut pipeline.fallback {
+            redact_fallback(fallback);
+        }
+        pipeline
+    }
+    /**
     On demand,
     Add the current process stdout/stderr to a runnnig pipeline log.
     Beware: Concurent std read/write
@@ -92,3 +122,49 @@ impl Pipeline {
         Ok(())
     }
 }
+
+fn redact_steps(steps: &mut [StepOrParallel]) {
+    for step_or_parallel in steps {
+        match step_or_parallel {
+            StepOrParallel::Step(step) => redact_step(step),
+            StepOrParallel::Parallel(parallel) => {
+                parallel.steps.iter_mut().for_each(redact_step);
+                if let Some(fallback) = &mut parallel.fallback {
+                    redact_fallback(fallback);
+                }
+            }
+        }
+    }
+}
+fn redact_step(step: &mut Step) {
+    step.commands.iter_mut().for_each(redact_command);
+    if let Some(fallback) = &mut step.fallback {
+        redact_fallback(fallback);
+    }
+}
+fn redact_fallback(fallback: &mut Fallback) {
+    for steps in [
+        &mut fallback.on_started,
+        &mut fallback.on_failure,
+        &mut fallback.on_success,
+        &mut fallback.on_abortion,
+        &mut fallback.on_timeout,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        redact_steps(steps);
+    }
+}
+fn redact_command(command: &mut Command) {
+    let io = &mut command.process.io;
+    for value in [&mut io.stdin, &mut io.stdout, &mut io.stderr]
+        .into_iter()
+        .flatten()
+    {
+        *value = secrets::redact(value);
+    }
+    for value in command.process.env.iter_mut().flat_map(|e| e.values_mut()) {
+        *value = secrets::redact(value);
+    }
+}
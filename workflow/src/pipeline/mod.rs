@@ -1,8 +1,12 @@
+mod chain;
 mod filters;
+mod graph;
 pub mod getters;
 mod is;
 mod log;
+mod params;
 mod run;
+mod schedule;
 mod stop;
 
 // Re-export
@@ -0,0 +1,130 @@
+// Tests
+mod test;
+
+// Structs
+use crate::types::{Event, Param, ParamType, Pipeline};
+use std::collections::BTreeMap;
+// Error Handling
+use crate::error::ParamError;
+use miette::Result;
+
+impl Pipeline {
+    /**
+    Parse the "name=value" run arguments and check them against the declared params.
+    Returns the value of every param, defaults included.
+    */
+    pub fn parse_params(&self, args: &[String]) -> Result<BTreeMap<String, String>> {
+        let declared = self.params.clone().unwrap_or_default();
+        let mut values = BTreeMap::new();
+        for arg in args {
+            let Some((name, value)) = arg.split_once('=') else {
+                let message = format!("Invalid parameter {:?}", arg);
+                let help = "Set parameters with \"--param <name>=<value>\"";
+                return Err(ParamError::new(&message, help).into());
+            };
+            let Some(param) = declared.iter().find(|e| e.name == name) else {
+                let message = format!("Unknown parameter {:?} for pipeline {:?}", name, self.name);
+                let names = declared
+                    .iter()
+                    .map(|e| e.name.to_owned())
+                    .collect::<Vec<String>>();
+                let help = match names.is_empty() {
+                    true => "The pipeline has no parameters".to_owned(),
+                    false => format!("Declared parameters are: {}", names.join(", ")),
+                };
+                return Err(ParamError::new(&message, &help).into());
+            };
+            param.validate(value)?;
+            values.insert(name.to_owned(), value.to_owned());
+        }
+        for param in &declared {
+            if values.contains_key(&param.name) {
+                continue;
+            }
+            match &param.default {
+                Some(default) => {
+                    values.insert(param.name.to_owned(), default.to_owned());
+                }
+                None => {
+                    let message = format!(
+                        "Missing required parameter {:?} for pipeline {:?}",
+                        param.name, self.name
+                    );
+                    let mut help = format!("Set it with \"--param {}=<value>\"", param.name);
+                    if let Some(description) = &param.description {
+                        help += &format!("\n{}: {}", param.name, description);
+                    }
+                    return Err(ParamError::new(&message, &help).into());
+                }
+            }
+        }
+        Ok(values)
+    }
+    /**
+    Set the param values of the next run, they are recorded in the run event.
+    */
+    pub fn set_params(&mut self, values: BTreeMap<String, String>) {
+        let event = self.event.get_or_insert_with(Event::new);
+        event.params = Some(values);
+    }
+    /**
+    Returns the required params that are missing from the run arguments.
+    */
+    pub fn missing_params(&self, args: &[String]) -> Vec<Param> {
+        self.params
+            .iter()
+            .flatten()
+            .filter(|param| param.default.is_none())
+            .filter(|param| {
+                !args
+                    .iter()
+                    .any(|arg| arg.split_once('=').map(|e| e.0) == Some(param.name.as_str()))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl Param {
+    /**
+    Check a value against the param type and allowed values.
+    */
+    pub fn validate(&self, value: &str) -> Result<()> {
+        let valid = match self.kind {
+            ParamType::String => true,
+            ParamType::Number => value.parse::<f64>().is_ok(),
+            ParamType::Boolean => value == "true" || value == "false",
+        };
+        if !valid {
+            let message = format!(
+                "The parameter {:?} expects a {}, got {:?}",
+                self.name,
+                String::from(&self.kind),
+                value
+            );
+            let help = match self.kind {
+                ParamType::Boolean => "Use true or false",
+                _ => "Use a number like 3 or 0.5",
+            };
+            return Err(ParamError::new(&message, help).into());
+        }
+        if let Some(values) = &self.values {
+            if !values.iter().any(|e| e == value) {
+                let message = format!("The parameter {:?} can't be {:?}", self.name, value);
+                let help = format!("Allowed values are: {}", values.join(", "));
+                return Err(ParamError::new(&message, &help).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&ParamType> for String {
+    fn from(kind: &ParamType) -> String {
+        match kind {
+            ParamType::String => "string".to_owned(),
+            ParamType::Number => "number".to_owned(),
+            ParamType::Boolean => "boolean".to_owned(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod params {
+    use crate::types::{Param, ParamType, Pipeline};
+
+    fn pipeline() -> Pipeline {
+        Pipeline {
+            name: "deploy".to_owned(),
+            params: Some(vec![
+                Param {
+                    name: "env".to_owned(),
+                    values: Some(vec!["staging".to_owned(), "production".to_owned()]),
+                    ..Param::default()
+                },
+                Param {
+                    name: "replicas".to_owned(),
+                    kind: ParamType::Number,
+                    default: Some("2".to_owned()),
+                    ..Param::default()
+                },
+                Param {
+                    name: "dry_run".to_owned(),
+                    kind: ParamType::Boolean,
+                    default: Some("false".to_owned()),
+                    ..Param::default()
+                },
+            ]),
+            ..Pipeline::default()
+        }
+    }
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn defaults() {
+        let values = pipeline()
+            .parse_params(&args(&["env=staging", "replicas=3"]))
+            .unwrap();
+        assert_eq!(values.get("env").unwrap(), "staging");
+        assert_eq!(values.get("replicas").unwrap(), "3");
+        assert_eq!(values.get("dry_run").unwrap(), "false");
+    }
+    #[test]
+    fn invalid_values() {
+        let p = pipeline();
+        // Missing required param
+        assert!(p.parse_params(&[]).is_err());
+        // Unknown param
+        assert!(p
+            .parse_params(&args(&["env=staging", "region=eu"]))
+            .is_err());
+        // Not an allowed value
+        assert!(p.parse_params(&args(&["env=dev"])).is_err());
+        // Wrong types
+        assert!(p
+            .parse_params(&args(&["env=staging", "replicas=many"]))
+            .is_err());
+        assert!(p
+            .parse_params(&args(&["env=staging", "dry_run=yes"]))
+            .is_err());
+        // Not a name=value pair
+        assert!(p.parse_params(&args(&["staging"])).is_err());
+    }
+    #[test]
+    fn missing_params() {
+        let p = pipeline();
+        let missing = p.missing_params(&args(&["replicas=3"]));
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].name, "env");
+        assert!(p.missing_params(&args(&["env=production"])).is_empty());
+    }
+}
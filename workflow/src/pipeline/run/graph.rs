@@ -0,0 +1,160 @@
+// Types
+use super::lock::{lock_state, unlock_state, unlocked};
+use super::PtrWrapper;
+use crate::types::{Mode, Pipeline, StepOrParallel};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+// Error Handling
+use miette::Result;
+// Traits
+use exec::{Statuable, Status};
+
+#[derive(Debug, Clone, Copy)]
+struct StepPtrWrapper(*mut StepOrParallel);
+unsafe impl Sync for StepPtrWrapper {}
+unsafe impl Send for StepPtrWrapper {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Pending,
+    Running,
+    Done,
+}
+
+impl StepOrParallel {
+    /**
+    A dependency is satisfied when it succeeded
+    or when its failure mode lets the pipeline go on.
+    */
+    fn satisfies_dependents(&self) -> bool {
+        match self.get_status() {
+            Some(Status::Succeeded) => true,
+            Some(Status::Failed) | Some(Status::TimedOut) => matches!(
+                self.get_mode(),
+                Some(Mode::JumpNextOnFailure) | Some(Mode::ContinueOnFailure)
+            ),
+            _ => false,
+        }
+    }
+    /**
+    Returns true if the step failed and should stop the pipeline.
+    */
+    fn stops_pipeline(&self) -> bool {
+        match self.get_status() {
+            Some(Status::Succeeded) | Some(Status::Skipped) | None => false,
+            _ => !matches!(
+                self.get_mode(),
+                Some(Mode::JumpNextOnFailure) | Some(Mode::ContinueOnFailure)
+            ),
+        }
+    }
+}
+
+impl Pipeline {
+    /**
+    Run the steps as a dependency graph.
+    A step is started as soon as every step it needs is satisfied,
+    with at most "concurrency" steps running at once.
+    Steps that need a skipped step are skipped,
+    steps that need a failed step are never run.
+    */
+    pub(super) fn run_graph(
+        ptr: *mut Pipeline,
+        env: &HashMap<String, String>,
+        deadline: Option<Instant>,
+    ) -> Result<()> {
+        let (graph, concurrency) = unsafe { ((*ptr).get_graph()?, (*ptr).get_concurrency()) };
+        let mut states = vec![State::Pending; graph.len()];
+        let mut error = None;
+
+        let (tx, rx) = mpsc::channel::<(usize, Result<()>)>();
+        let ptr_wrapper = PtrWrapper(ptr);
+        // Steps statuses are read and set with the state lock held
+        lock_state();
+        thread::scope(|scope| {
+            let mut running = 0;
+            let mut stopped = false;
+            loop {
+                // Start every ready step,
+                // again while skipped steps make other steps ready
+                let mut changed = true;
+                while changed && !stopped {
+                    changed = false;
+                    for i in 0..graph.len() {
+                        if running >= concurrency {
+                            break;
+                        }
+                        if states[i] != State::Pending
+                            || !graph[i].iter().all(|e| states[*e] == State::Done)
+                        {
+                            continue;
+                        }
+                        let dependencies: Vec<&StepOrParallel> =
+                            unsafe { graph[i].iter().map(|e| &(*ptr).steps[*e]).collect() };
+                        if dependencies
+                            .iter()
+                            .any(|e| e.get_status() == Some(Status::Skipped))
+                        {
+                            unsafe {
+                                (*ptr).steps[i].set_status(Some(Status::Skipped));
+                            }
+                            states[i] = State::Done;
+                            changed = true;
+                            continue;
+                        }
+                        if !dependencies.iter().all(|e| e.satisfies_dependents()) {
+                            continue;
+                        }
+                        states[i] = State::Running;
+                        running += 1;
+                        let step = unsafe { StepPtrWrapper(&mut (*ptr).steps[i]) };
+                        let tx = tx.clone();
+                        scope.spawn(move || {
+                            // Move the wrappers rather than their raw pointer fields
+                            let (step, ptr) = (step, ptr_wrapper);
+                            lock_state();
+                            let res = unsafe { (*step.0).run(ptr.0, env, deadline) };
+                            unlock_state();
+                            tx.send((i, res)).unwrap();
+                        });
+                    }
+                }
+                if running == 0 {
+                    break;
+                }
+                // Wait for a step to end
+                let (i, res) = unlocked(|| rx.recv().unwrap());
+                running -= 1;
+                states[i] = State::Done;
+                if let Err(e) = res {
+                    stopped = true;
+                    error.get_or_insert(e);
+                }
+                if unsafe { (*ptr).steps[i].stops_pipeline() } {
+                    stopped = true;
+                }
+            }
+        });
+        unlock_state();
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+    /**
+    Set the pipeline status from the graph steps statuses.
+    */
+    pub(super) fn set_graph_status(&mut self) {
+        let status = self
+            .steps
+            .iter()
+            .find(|e| e.stops_pipeline())
+            .map(|e| e.get_status());
+        match status {
+            Some(status) => self.set_status(status),
+            None => self.set_status(Some(Status::Succeeded)),
+        }
+    }
+}
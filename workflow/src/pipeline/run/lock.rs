@@ -0,0 +1,52 @@
+/**
+Steps that run concurrently (graph and parallel steps) share the global pipeline
+through a raw pointer: they mutate their own step state
+while every log() serializes the whole pipeline.
+
+A running step holds the state lock while it touches the pipeline
+and releases it only while it waits (running process, retry delay),
+so that mutations and serialization never overlap.
+*/
+// Globals
+use std::cell::RefCell;
+use std::sync::{Mutex, MutexGuard};
+
+static STATE: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    static GUARD: RefCell<Option<MutexGuard<'static, ()>>> = const { RefCell::new(None) };
+}
+
+/**
+Acquire the pipeline state lock for the current thread.
+Does nothing if the thread already holds it.
+*/
+pub(super) fn lock_state() {
+    GUARD.with(|guard| {
+        let mut guard = guard.borrow_mut();
+        if guard.is_none() {
+            // A panicking step doesn't corrupt the lock
+            *guard = Some(STATE.lock().unwrap_or_else(|e| e.into_inner()));
+        }
+    });
+}
+
+/**
+Release the pipeline state lock of the current thread.
+Returns whether the thread held it, for it to be acquired back.
+*/
+pub(super) fn unlock_state() -> bool {
+    GUARD.with(|guard| guard.borrow_mut().take().is_some())
+}
+
+/**
+Run a blocking operation with the state lock released.
+*/
+pub(super) fn unlocked<T>(f: impl FnOnce() -> T) -> T {
+    let locked = unlock_state();
+    let res = f();
+    if locked {
+        lock_state();
+    }
+    res
+}
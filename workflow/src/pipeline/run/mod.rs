@@ -1,7 +1,14 @@
 // Types
+use crate::step::env::resolve_secrets;
 use crate::types::{Command, Event, Mode, Parallel, Pipeline, Step, StepOrParallel};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Instant;
 use utils::dates::Duration;
+use utils::secrets::Secrets;
+use utils::teleport::global_state_dir;
 // Error Handling
+use log::{error, warn};
 use miette::Result;
 // Traits
 use exec::{Statuable, Status};
@@ -9,6 +16,11 @@ use exec::{Statuable, Status};
 use once_cell::sync::Lazy;
 // Parallelism
 use rayon::prelude::*;
+// Dependency graph
+mod graph;
+// Concurrent steps synchronization
+mod lock;
+use lock::{lock_state, unlock_state, unlocked};
 // Tests
 mod test;
 
@@ -29,9 +41,26 @@ impl Pipeline {
             if (*ptr).has_homologous_already_running().is_ok() {
                 return Ok(());
             }
+            (*ptr).has_valid_cwd()?;
+            (*ptr).has_valid_conditions()?;
             if (*ptr).triggers.is_some() {}
         }
 
+        // Global pipelines have no project directory to write outputs into
+        unsafe {
+            if (*ptr).global {
+                if let Some(dir) = global_state_dir() {
+                    exec::set_outdir(&dir.join("_internals/out").display().to_string());
+                }
+            }
+        }
+
+        // Register project secrets for them to be redacted from outputs
+        match Secrets::load() {
+            Ok(secrets) => secrets.register(),
+            Err(e) => warn!("Couldn't load the project secrets: {}", e),
+        }
+
         // Duration
         let mut d = Duration::default();
         d.start()?;
@@ -39,8 +68,18 @@ impl Pipeline {
             (*ptr).duration = Some(d.clone());
         }
 
-        // Event
-        let event = Event::new();
+        // Event, with the params set before the run or their defaults
+        let params = unsafe {
+            match (*ptr).event.as_ref().and_then(|e| e.params.clone()) {
+                Some(params) => Some(params),
+                None if (*ptr).params.is_some() => Some((*ptr).parse_params(&[])?),
+                None => None,
+            }
+        };
+        let event = Event {
+            params,
+            ..Event::new()
+        };
 
         // Set event = Pid , Status and Duration
         unsafe {
@@ -53,12 +92,20 @@ impl Pipeline {
             (*ptr).set_status(Some(Status::Running));
             (*ptr).log()?;
 
-            for step in &mut (*ptr).steps {
-                step.run(ptr)?;
-                if (step.get_status() != Some(Status::Succeeded))
-                    && (step.get_mode().is_none() || step.get_mode() == Some(Mode::StopOnFailure))
-                {
-                    break;
+            let env = (*ptr).get_env();
+            let deadline = (*ptr).get_deadline();
+            if (*ptr).is_graph() {
+                Pipeline::run_graph(ptr, &env, deadline)?;
+            } else {
+                for step in &mut (*ptr).steps {
+                    step.run(ptr, &env, deadline)?;
+                    if (step.get_status() != Some(Status::Succeeded))
+                        && (step.get_status() != Some(Status::Skipped))
+                        && (step.get_mode().is_none()
+                            || step.get_mode() == Some(Mode::StopOnFailure))
+                    {
+                        break;
+                    }
                 }
             }
         }
@@ -69,45 +116,66 @@ impl Pipeline {
             (*ptr).duration = Some(d.clone());
         }
 
-        // Set pipeline status to last Step status
+        // Set pipeline status to last executed Step status
         unsafe {
-            let last_step = (*ptr).steps.last().unwrap();
-            if last_step.get_status().is_some() {
-                if last_step.get_mode() == Some(Mode::JumpNextOnFailure) {
-                    if last_step.get_status() == Some(Status::Failed) {
-                        (*ptr).set_status(Some(Status::Succeeded))
+            if (*ptr).is_graph() {
+                (*ptr).set_graph_status();
+            } else {
+                let last_step = (*ptr)
+                    .steps
+                    .iter()
+                    .rev()
+                    .find(|e| e.get_status() != Some(Status::Skipped))
+                    .unwrap_or((*ptr).steps.last().unwrap());
+                if last_step.get_status() == Some(Status::Skipped) {
+                    // Every step has been skipped
+                    (*ptr).set_status(Some(Status::Succeeded))
+                } else if last_step.get_status().is_some() {
+                    if last_step.get_mode() == Some(Mode::JumpNextOnFailure) {
+                        // A timeout is a failure
+                        if last_step.get_status() == Some(Status::Failed)
+                            || last_step.get_status() == Some(Status::TimedOut)
+                        {
+                            (*ptr).set_status(Some(Status::Succeeded))
+                        } else {
+                            (*ptr).set_status(last_step.get_status())
+                        }
                     } else {
                         (*ptr).set_status(last_step.get_status())
                     }
                 } else {
-                    (*ptr).set_status(last_step.get_status())
+                    (*ptr).set_status(Some(Status::Failed))
                 }
-            } else {
-                (*ptr).set_status(Some(Status::Failed))
             }
             (*ptr).log()?;
         }
 
         // Execute fallbacks
         unsafe {
+            let env = (*ptr).get_env();
             if (*ptr).fallback.is_some() {
                 let fallback = &mut (*ptr).fallback.as_mut().unwrap();
                 if (*ptr).status == Some(Status::Failed) && fallback.on_failure.is_some() {
                     // let steps = (*ptr).on_failure.as_mut().unwrap();
                     for step in fallback.on_failure.as_mut().unwrap() {
-                        step.run(ptr)?;
+                        step.run(ptr, &env, None)?;
                     }
                 }
                 if (*ptr).status == Some(Status::Succeeded) && fallback.on_failure.is_some() {
                     // let steps = (*ptr).on_failure.as_mut().unwrap();
                     for step in fallback.on_success.as_mut().unwrap() {
-                        step.run(ptr)?;
+                        step.run(ptr, &env, None)?;
                     }
                 }
                 if (*ptr).status == Some(Status::Aborted) && fallback.on_success.is_some() {
                     // let steps = (*ptr).on_failure.as_mut().unwrap();
                     for step in fallback.on_abortion.as_mut().unwrap() {
-                        step.run(ptr)?;
+                        step.run(ptr, &env, None)?;
+                    }
+                }
+                if (*ptr).status == Some(Status::TimedOut) && fallback.on_timeout.is_some() {
+                    for step in fallback.on_timeout.as_mut().unwrap() {
+                        step.run(ptr, &env, None)?;
                     }
                 }
                 // Duration
@@ -125,16 +193,35 @@ impl Pipeline {
 }
 
 impl StepOrParallel {
-    fn run(&mut self, ptr: *mut Pipeline) -> Result<()> {
+    fn run(
+        &mut self,
+        ptr: *mut Pipeline,
+        env: &HashMap<String, String>,
+        deadline: Option<Instant>,
+    ) -> Result<()> {
         match self {
-            StepOrParallel::Step(res) => res.run(ptr),
-            StepOrParallel::Parallel(res) => res.run(ptr),
+            StepOrParallel::Step(res) => res.run(ptr, env, deadline),
+            StepOrParallel::Parallel(res) => res.run(ptr, env, deadline),
         }
     }
 }
 
 impl Parallel {
-    fn run(&mut self, ptr: *mut Pipeline) -> Result<()> {
+    fn run(
+        &mut self,
+        ptr: *mut Pipeline,
+        env: &HashMap<String, String>,
+        deadline: Option<Instant>,
+    ) -> Result<()> {
+        let env = self.get_env(env);
+        let deadline = self.get_deadline(deadline);
+        if !self.should_run(ptr, &env)? {
+            self.set_status(Some(Status::Skipped));
+            unsafe {
+                (*ptr).log()?;
+            }
+            return Ok(());
+        }
         // Duration
         let mut d = Duration::default();
         d.start()?;
@@ -144,9 +231,14 @@ impl Parallel {
 
         // Pass wrapped pointer to threads
         let ptr_wrapper = PtrWrapper(ptr);
-        self.steps
-            .par_iter_mut()
-            .for_each(|e| e.unsafe_run(ptr_wrapper).unwrap());
+        unlocked(|| {
+            self.steps.par_iter_mut().for_each(|e| {
+                lock_state();
+                let res = e.unsafe_run(ptr_wrapper, &env, deadline);
+                unlock_state();
+                res.unwrap()
+            })
+        });
 
         // Set parallel global status
         let steps_res: Vec<Status> = self
@@ -155,8 +247,12 @@ impl Parallel {
             .map(|e| e.clone().status.unwrap())
             .collect();
 
-        if steps_res.contains(&Status::Failed) {
+        if steps_res.iter().all(|e| e == &Status::Skipped) {
+            self.set_status(Some(Status::Skipped));
+        } else if steps_res.contains(&Status::Failed) {
             self.set_status(Some(Status::Failed));
+        } else if steps_res.contains(&Status::TimedOut) {
+            self.set_status(Some(Status::TimedOut));
         } else if steps_res.contains(&Status::Aborted) {
             self.set_status(Some(Status::Aborted));
         } else {
@@ -179,13 +275,32 @@ struct PtrWrapper(*mut Pipeline);
 unsafe impl Sync for PtrWrapper {}
 unsafe impl Send for PtrWrapper {}
 impl Step {
-    fn unsafe_run(&mut self, ptr: PtrWrapper) -> Result<()> {
+    fn unsafe_run(
+        &mut self,
+        ptr: PtrWrapper,
+        env: &HashMap<String, String>,
+        deadline: Option<Instant>,
+    ) -> Result<()> {
         let ptr = ptr.0;
-        self.run(ptr)
+        self.run(ptr, env, deadline)
     }
-    fn run(&mut self, ptr: *mut Pipeline) -> Result<()> {
+    fn run(
+        &mut self,
+        ptr: *mut Pipeline,
+        env: &HashMap<String, String>,
+        inherited_deadline: Option<Instant>,
+    ) -> Result<()> {
         // Options
         let mode = self.get_mode();
+        let env = self.get_env(env);
+        let deadline = self.get_deadline(inherited_deadline);
+        if !self.should_run(ptr, &env)? {
+            self.set_status(Some(Status::Skipped));
+            unsafe {
+                (*ptr).log()?;
+            }
+            return Ok(());
+        }
         // Duration
         let mut d = Duration::default();
         d.start()?;
@@ -194,18 +309,36 @@ impl Step {
         self.set_status(Some(Status::Running));
 
         // Run commands
-        for command in &mut self.commands {
-            command.run(ptr)?;
+        let command_env = unsafe { self.get_context_env(&*ptr, &env) };
+        let retries = self.get_retries();
+        let mut i = 0;
+        while i < self.commands.len() {
+            let command = &mut self.commands[i];
+            command.run(ptr, &command_env, deadline)?;
+
+            // Retry failed command as a new command record
+            let attempt = command.attempt.unwrap_or(1);
+            if command.get_status() == Some(Status::Failed) && attempt <= retries {
+                command.attempt = Some(attempt);
+                let retry = command.retry();
+                if let Some(delay) = self.get_retry_delay(attempt + 1) {
+                    unlocked(|| thread::sleep(delay));
+                }
+                self.commands.insert(i + 1, retry);
+                i += 1;
+                continue;
+            }
 
             if (command.get_status().is_none() || command.get_status() != Some(Status::Succeeded))
                 && (mode.is_none() || mode != Some(Mode::ContinueOnFailure))
             {
                 break;
             }
+            i += 1;
         }
 
-        // Set global status after run
-        let final_status = &self.commands.last().unwrap().get_status();
+        // Set global status to the last executed command status
+        let final_status = &self.commands.iter().rev().find_map(|e| e.get_status());
         if final_status.is_some() {
             self.status = final_status.clone();
         } else {
@@ -224,17 +357,22 @@ impl Step {
             let fallback = &mut self.fallback.as_mut().unwrap();
             if self.status == Some(Status::Failed) && fallback.on_failure.is_some() {
                 for step in fallback.on_failure.as_mut().unwrap() {
-                    step.run(ptr)?;
+                    step.run(ptr, &env, inherited_deadline)?;
                 }
             }
             if self.status == Some(Status::Succeeded) && fallback.on_success.is_some() {
                 for step in fallback.on_success.as_mut().unwrap() {
-                    step.run(ptr)?;
+                    step.run(ptr, &env, inherited_deadline)?;
                 }
             }
             if self.status == Some(Status::Aborted) && fallback.on_abortion.is_some() {
                 for step in fallback.on_success.as_mut().unwrap() {
-                    step.run(ptr)?;
+                    step.run(ptr, &env, inherited_deadline)?;
+                }
+            }
+            if self.status == Some(Status::TimedOut) && fallback.on_timeout.is_some() {
+                for step in fallback.on_timeout.as_mut().unwrap() {
+                    step.run(ptr, &env, inherited_deadline)?;
                 }
             }
             unsafe {
@@ -246,7 +384,35 @@ impl Step {
 }
 
 impl Command {
-    fn run(&mut self, ptr: *mut Pipeline) -> Result<()> {
+    fn run(
+        &mut self,
+        ptr: *mut Pipeline,
+        env: &HashMap<String, String>,
+        deadline: Option<Instant>,
+    ) -> Result<()> {
+        // Abort the command if a secret reference can't be resolved
+        let env = &match resolve_secrets(env) {
+            Ok(env) => env,
+            Err(e) => {
+                error!("{:?}", e);
+                self.set_status(Some(Status::Aborted));
+                return Ok(());
+            }
+        };
+        if !env.is_empty() {
+            self.process.env = Some(env.to_owned());
+        }
+        self.interpolate(ptr, env);
+        // Give the process the time left before the deadline
+        if let Some(deadline) = deadline {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if timeout.is_zero() {
+                self.set_status(Some(Status::TimedOut));
+                return Ok(());
+            }
+            self.process.timeout = Some(timeout);
+        }
+
         // Duration
         let mut d = Duration::default();
         d.start()?;
@@ -258,7 +424,7 @@ impl Command {
         }
 
         // Run process
-        let res = self.process.run_fs();
+        let res = unlocked(|| self.process.run_fs());
         let _ = match res {
             Ok(_) => Ok(()),
             Err(e) => {
@@ -1,8 +1,44 @@
 #[cfg(test)]
 mod pipeline {
-    use crate::types::{Command, Pipeline, Step, StepOrParallel};
+    use crate::pipeline::log::OUTDIR;
+    use crate::types::{
+        Command, Param, Pipeline, RetryDelay, Status, Step, StepOpts, StepOrParallel,
+    };
+    use exec::Statuable;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::{Mutex, MutexGuard};
+    use std::{env, fs, process};
+
+    // Pipelines are run through a global,
+    // so tests that run pipelines must not be executed concurrently.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    /**
+    The run lock, along with the temporary directory
+    the logs and process outputs are sent to.
+    The directory is removed when the lock is released.
+    */
+    struct RunLock {
+        dir: PathBuf,
+        _guard: MutexGuard<'static, ()>,
+    }
+    impl Drop for RunLock {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+    fn lock() -> RunLock {
+        let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = env::temp_dir().join(format!("pipelight-run-{}", process::id()));
+        *OUTDIR.lock().unwrap() = dir.join("logs").display().to_string();
+        exec::set_outdir(&dir.join("_internals/out").display().to_string());
+        RunLock { dir, _guard: guard }
+    }
+
     #[test]
     fn can_run() {
+        let _lock = lock();
         // Set a logger
         let mut p = Pipeline {
             steps: vec![StepOrParallel::Step(Step {
@@ -13,4 +49,327 @@ mod pipeline {
         };
         assert!(p.run().is_ok());
     }
+    #[test]
+    fn step_env_overrides_pipeline_env() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "env".to_owned(),
+            env: Some(HashMap::from([
+                ("TARGET".to_owned(), "pipeline".to_owned()),
+                ("RUST_LOG".to_owned(), "info".to_owned()),
+            ])),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("echo $TARGET $RUST_LOG")],
+                env: Some(HashMap::from([("TARGET".to_owned(), "step".to_owned())])),
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone();
+                assert_eq!(stdout.as_deref(), Some("step info\n"));
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn step_timeout() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "timeout".to_owned(),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("sleep 10"), Command::new("echo test")],
+                options: Some(StepOpts {
+                    timeout: Some("PT0.2S".to_owned()),
+                    ..StepOpts::default()
+                }),
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        assert_eq!(p.status, Some(Status::TimedOut));
+    }
+    #[test]
+    fn step_retries() {
+        let lock = lock();
+        fs::create_dir_all(&lock.dir).unwrap();
+        let file = lock.dir.join("step_retries").display().to_string();
+        // Fails on the first two attempts.
+        let command = format!(
+            "echo x >> {file}; test $(wc -l < {file}) -ge 3",
+            file = file
+        );
+        let mut p = Pipeline {
+            name: "retries".to_owned(),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new(&command)],
+                options: Some(StepOpts {
+                    retries: Some(3),
+                    retry_delay: Some(RetryDelay::Exponential("PT0.01S".to_owned())),
+                    ..StepOpts::default()
+                }),
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        assert_eq!(p.status, Some(Status::Succeeded));
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let attempts: Vec<Option<u32>> = step.commands.iter().map(|e| e.attempt).collect();
+                assert_eq!(attempts, vec![Some(1), Some(2), Some(3)]);
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn retry_interpolated_command() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "retryinterp".to_owned(),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("echo ${{ pipeline.name }}; false")],
+                options: Some(StepOpts {
+                    retries: Some(1),
+                    ..StepOpts::default()
+                }),
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdouts: Vec<Option<&str>> = step
+                    .commands
+                    .iter()
+                    .map(|e| e.process.io.stdout.as_deref())
+                    .collect();
+                assert_eq!(stdouts, vec![Some("retryinterp\n"); 2]);
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn skip_step_on_false_condition() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "condition".to_owned(),
+            steps: vec![
+                StepOrParallel::Step(Step {
+                    name: "test".to_owned(),
+                    commands: vec![Command::new("true")],
+                    ..Step::default()
+                }),
+                StepOrParallel::Step(Step {
+                    name: "publish".to_owned(),
+                    condition: Some("tag != null".to_owned()),
+                    commands: vec![Command::new("false")],
+                    ..Step::default()
+                }),
+                StepOrParallel::Step(Step {
+                    name: "coverage".to_owned(),
+                    condition: Some("steps.test.status == 'succeeded'".to_owned()),
+                    commands: vec![Command::new("true")],
+                    ..Step::default()
+                }),
+            ],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        assert_eq!(p.steps[1].get_status(), Some(Status::Skipped));
+        assert_eq!(p.steps[2].get_status(), Some(Status::Succeeded));
+        assert_eq!(p.status, Some(Status::Succeeded));
+    }
+    #[test]
+    fn run_steps_graph() {
+        let _lock = lock();
+        let step = |name: &str, needs: &[&str], command: &str| {
+            StepOrParallel::Step(Step {
+                name: name.to_owned(),
+                needs: Some(needs.iter().map(|e| e.to_string()).collect()),
+                commands: vec![Command::new(command)],
+                ..Step::default()
+            })
+        };
+        let mut p = Pipeline {
+            name: "graph".to_owned(),
+            steps: vec![
+                step("deploy", &["test", "lint"], "true"),
+                step("build", &[], "true"),
+                step("test", &["build"], "true"),
+                step("lint", &["build"], "false"),
+                step("docs", &[], "true"),
+            ],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        assert_eq!(p.steps[0].get_status(), None);
+        assert_eq!(p.steps[1].get_status(), Some(Status::Succeeded));
+        assert_eq!(p.steps[3].get_status(), Some(Status::Failed));
+        assert_eq!(p.status, Some(Status::Failed));
+    }
+    #[test]
+    fn state_lock_released_while_waiting() {
+        use super::super::lock::{lock_state, unlock_state, unlocked};
+        use std::thread;
+        lock_state();
+        // Another step can take the lock while this one waits
+        let other = unlocked(|| {
+            thread::spawn(|| {
+                lock_state();
+                unlock_state()
+            })
+            .join()
+            .unwrap()
+        });
+        assert!(other);
+        // The lock is given back after the wait
+        assert!(unlock_state());
+        assert!(!unlock_state());
+    }
+    #[test]
+    fn interpolate_commands() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "interpolation".to_owned(),
+            env: Some(HashMap::from([("TARGET".to_owned(), "prod".to_owned())])),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new(
+                    "echo ${{ pipeline.name }} ${{ env.TARGET }} ${{ env.UNSET_VARIABLE }}",
+                )],
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone();
+                assert_eq!(stdout.as_deref(), Some("interpolation prod\n"));
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn context_env() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "context".to_owned(),
+            steps: vec![StepOrParallel::Step(Step {
+                name: "print".to_owned(),
+                commands: vec![Command::new(
+                    "echo $PIPELIGHT_PIPELINE $PIPELIGHT_STEP $PIPELIGHT_RUN_ID",
+                )],
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone().unwrap();
+                assert_eq!(stdout, format!("context print {}\n", p.uuid));
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn secret_env() {
+        let _lock = lock();
+        utils::secrets::Secrets {
+            values: [
+                ("deploy_token".to_owned(), "s3cret".to_owned()),
+                // Short values must not corrupt the logs
+                ("flag".to_owned(), "name".to_owned()),
+            ]
+            .into(),
+            ..Default::default()
+        }
+        .register();
+        let mut p = Pipeline {
+            name: "secrets".to_owned(),
+            env: Some(HashMap::from([(
+                "DEPLOY".to_owned(),
+                "${{ secrets.deploy_token }}".to_owned(),
+            )])),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("echo $DEPLOY")],
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone();
+                assert_eq!(stdout.as_deref(), Some("s3cret\n"));
+            }
+            _ => panic!(),
+        }
+        let json = serde_json::to_string(&p.redacted()).unwrap();
+        assert!(!json.contains("s3cret"));
+        let log = serde_json::from_str::<Pipeline>(&json).unwrap();
+        assert_eq!(log.name, "secrets");
+    }
+    #[test]
+    fn param_env() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "params".to_owned(),
+            params: Some(vec![Param {
+                name: "version".to_owned(),
+                ..Param::default()
+            }]),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("echo $PARAM_VERSION ${{ params.version }}")],
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        let values = p.parse_params(&["version=1.2.0".to_owned()]).unwrap();
+        p.set_params(values);
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone();
+                assert_eq!(stdout.as_deref(), Some("1.2.0 1.2.0\n"));
+            }
+            _ => panic!(),
+        }
+        // Values are recorded in the run event
+        let params = p.event.unwrap().params.unwrap();
+        assert_eq!(params.get("version").unwrap(), "1.2.0");
+    }
+    #[test]
+    fn interpolated_values_are_not_code() {
+        let _lock = lock();
+        let mut p = Pipeline {
+            name: "injection".to_owned(),
+            params: Some(vec![Param {
+                name: "version".to_owned(),
+                ..Param::default()
+            }]),
+            steps: vec![StepOrParallel::Step(Step {
+                commands: vec![Command::new("echo \"${{ params.version }}\"")],
+                ..Step::default()
+            })],
+            ..Pipeline::default()
+        };
+        let values = p
+            .parse_params(&["version=1; echo $(echo injected)".to_owned()])
+            .unwrap();
+        p.set_params(values);
+        p.run().unwrap();
+        match &p.steps[0] {
+            StepOrParallel::Step(step) => {
+                let stdout = step.commands[0].process.io.stdout.clone();
+                assert_eq!(stdout.as_deref(), Some("1; echo $(echo injected)\n"));
+            }
+            _ => panic!(),
+        }
+    }
 }
@@ -0,0 +1,57 @@
+// Test
+mod test;
+// Structs
+use crate::types::{CatchUp, Pipeline, Trigger};
+use utils::dates::Schedule;
+// Dates
+use chrono::{DateTime, Local};
+// Error Handling
+use miette::Result;
+
+impl Pipeline {
+    /**
+    Whether one of the pipeline schedules fires at the given minute.
+    */
+    pub fn is_scheduled_at(&self, date: &DateTime<Local>) -> Result<bool> {
+        for trigger in self.triggers.iter().flatten() {
+            let schedule = match trigger {
+                Trigger::TriggerBranch(e) => &e.schedule,
+                Trigger::TriggerTag(e) => &e.schedule,
+            };
+            if let Some(schedule) = schedule {
+                if Schedule::new(schedule)?.is_match(date) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+    /**
+    Return the dates of the scheduled runs missed between "since" and "until",
+    that should be caught up according to the triggers catch-up policy.
+    */
+    pub fn get_missed_schedules(
+        &self,
+        since: &DateTime<Local>,
+        until: &DateTime<Local>,
+    ) -> Result<Vec<DateTime<Local>>> {
+        let mut dates = vec![];
+        for trigger in self.triggers.iter().flatten() {
+            let (schedule, catch_up) = match trigger {
+                Trigger::TriggerBranch(e) => (e.schedule.clone(), e.catch_up.clone()),
+                Trigger::TriggerTag(e) => (e.schedule.clone(), e.catch_up.clone()),
+            };
+            if let Some(schedule) = schedule {
+                let missed = Schedule::new(&schedule)?.between(since, until);
+                match catch_up.unwrap_or_default() {
+                    CatchUp::Skip => {}
+                    CatchUp::Latest => dates.extend(missed.last()),
+                    CatchUp::All => dates.extend(missed),
+                }
+            }
+        }
+        dates.sort();
+        dates.dedup();
+        Ok(dates)
+    }
+}
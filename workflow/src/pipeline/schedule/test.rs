@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod schedule {
+    use crate::types::{CatchUp, Pipeline, Trigger, TriggerBranch};
+    use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+    use utils::git::{Flag, Special};
+
+    fn date(s: &str) -> DateTime<Local> {
+        let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&naive).unwrap()
+    }
+    fn scheduled(schedule: &str, catch_up: Option<CatchUp>) -> Pipeline {
+        Pipeline {
+            triggers: Some(vec![Trigger::TriggerBranch(TriggerBranch {
+                action: Some(Flag::Special(Special::Schedule)),
+                schedule: Some(schedule.to_owned()),
+                catch_up,
+                ..TriggerBranch::default()
+            })]),
+            ..Pipeline::default()
+        }
+    }
+
+    #[test]
+    fn catch_up_policies() {
+        let since = date("2023-10-12 02:30");
+        let until = date("2023-10-12 06:10");
+
+        let pipeline = scheduled("@hourly", None);
+        let missed = pipeline.get_missed_schedules(&since, &until).unwrap();
+        assert!(missed.is_empty());
+
+        let pipeline = scheduled("@hourly", Some(CatchUp::Latest));
+        let missed = pipeline.get_missed_schedules(&since, &until).unwrap();
+        assert_eq!(missed, vec![date("2023-10-12 06:00")]);
+
+        let pipeline = scheduled("@hourly", Some(CatchUp::All));
+        let missed = pipeline.get_missed_schedules(&since, &until).unwrap();
+        assert_eq!(missed.len(), 4);
+    }
+    #[test]
+    fn scheduled_at() {
+        let pipeline = scheduled("0 3 * * *", None);
+        assert!(pipeline.is_scheduled_at(&date("2023-10-12 03:00")).unwrap());
+        assert!(!pipeline.is_scheduled_at(&date("2023-10-12 03:01")).unwrap());
+        assert!(!Pipeline::default()
+            .is_scheduled_at(&date("2023-10-12 03:00"))
+            .unwrap());
+    }
+}
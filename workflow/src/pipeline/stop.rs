@@ -1,10 +1,19 @@
 // Structs
-use crate::types::{Pipeline, Status};
+use crate::types::{Logs, Pipeline, Status};
+use utils::git::{Flag, Special};
+// Restart delay
+use std::thread;
+use std::time::{Duration, Instant};
 // Error Handling
 use miette::{IntoDiagnostic, Result};
 // Unix process manipiulation
 use rustix::process::{kill_process_group, Signal};
 
+/**
+Time given to a stopped pipeline to exit before it is restarted.
+*/
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
 /**
 Abort process execution
 Kil the process group
@@ -23,4 +32,27 @@ impl Pipeline {
         }
         Ok(())
     }
+    /**
+    Abort the running instances of the pipeline triggered by the watcher,
+    so that newer file changes restart the pipeline instead of being dropped.
+    Waits for the stopped runs to exit, as running instances prevent the restart.
+    */
+    pub fn stop_watch_runs(&self) -> Result<()> {
+        // No logs means no running instance
+        let runs = Logs::get_many_by_name(&self.name).unwrap_or_default();
+        for mut run in runs {
+            let action = match &run.event {
+                Some(event) => event.trigger.get_action()?,
+                None => None,
+            };
+            if action == Some(Flag::Special(Special::Watch)) {
+                run.stop()?;
+                let start = Instant::now();
+                while run.is_running()? && start.elapsed() < STOP_TIMEOUT {
+                    thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+        Ok(())
+    }
 }
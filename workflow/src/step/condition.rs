@@ -0,0 +1,41 @@
+// Structs
+use crate::condition::{Context, Expr};
+use crate::types::{Parallel, Pipeline, Step};
+use std::collections::HashMap;
+// Error Handling
+use miette::Result;
+
+/**
+Evaluate an optional condition against the running pipeline.
+No condition means the step always runs.
+*/
+fn eval(
+    condition: &Option<String>,
+    ptr: *mut Pipeline,
+    env: &HashMap<String, String>,
+) -> Result<bool> {
+    if let Some(condition) = condition {
+        let expr = Expr::new(condition)?;
+        let context = unsafe { Context::new(&*ptr, env) };
+        Ok(expr.eval(&context))
+    } else {
+        Ok(true)
+    }
+}
+
+impl Step {
+    /**
+    Returns false if the step condition evaluates to false.
+    */
+    pub fn should_run(&self, ptr: *mut Pipeline, env: &HashMap<String, String>) -> Result<bool> {
+        eval(&self.condition, ptr, env)
+    }
+}
+impl Parallel {
+    /**
+    Returns false if the parallel group condition evaluates to false.
+    */
+    pub fn should_run(&self, ptr: *mut Pipeline, env: &HashMap<String, String>) -> Result<bool> {
+        eval(&self.condition, ptr, env)
+    }
+}
@@ -0,0 +1,121 @@
+// Structs
+use crate::types::{Parallel, Pipeline, Step};
+use cast::interpolation::{interpolate, param_env_name, placeholders};
+use std::collections::HashMap;
+use utils::secrets;
+// Error Handling
+use crate::error::IsError;
+use miette::Result;
+
+/**
+Merge an environment over an inherited one.
+Values from the child environment override the inherited ones.
+*/
+fn merge(
+    inherited: &HashMap<String, String>,
+    env: &Option<HashMap<String, String>>,
+) -> HashMap<String, String> {
+    let mut res = inherited.to_owned();
+    if let Some(env) = env {
+        res.extend(env.to_owned());
+    }
+    res
+}
+
+impl Pipeline {
+    /**
+    Returns the environment inherited by every pipeline step.
+    */
+    pub fn get_env(&self) -> HashMap<String, String> {
+        merge(&HashMap::new(), &self.env)
+    }
+    /**
+    Returns the PIPELIGHT_* variables describing the current run,
+    and the PARAM_* variables of the run parameters.
+    Values that are unknown, like the tag of a branch trigger, are empty.
+    */
+    pub fn get_context_env(&self) -> HashMap<String, String> {
+        let mut res = self.get_run_env();
+        let params = self.event.as_ref().and_then(|e| e.params.clone());
+        for (name, value) in params.unwrap_or_default() {
+            res.insert(param_env_name(&name), value);
+        }
+        res
+    }
+    fn get_run_env(&self) -> HashMap<String, String> {
+        let trigger = self.event.as_ref().map(|e| e.trigger.clone());
+        let branch = trigger.as_ref().and_then(|e| e.get_branch().ok()?);
+        let tag = trigger.as_ref().and_then(|e| e.get_tag().ok()?);
+        let commit = trigger.as_ref().and_then(|e| e.get_commit().ok()?);
+        let action = trigger
+            .as_ref()
+            .and_then(|e| e.get_action().ok()?)
+            .map(|e| String::from(&e));
+        HashMap::from([
+            ("PIPELIGHT_PIPELINE".to_owned(), self.name.to_owned()),
+            ("PIPELIGHT_RUN_ID".to_owned(), self.uuid.to_string()),
+            ("PIPELIGHT_BRANCH".to_owned(), branch.unwrap_or_default()),
+            ("PIPELIGHT_TAG".to_owned(), tag.unwrap_or_default()),
+            ("PIPELIGHT_COMMIT".to_owned(), commit.unwrap_or_default()),
+            ("PIPELIGHT_ACTION".to_owned(), action.unwrap_or_default()),
+            ("PIPELIGHT_LOG_DIR".to_owned(), self.get_log_dir()),
+        ])
+    }
+}
+impl Parallel {
+    /**
+    Returns the environment inherited by the parallel steps.
+    */
+    pub fn get_env(&self, inherited: &HashMap<String, String>) -> HashMap<String, String> {
+        merge(inherited, &self.env)
+    }
+}
+impl Step {
+    /**
+    Returns the effective environment of the step commands.
+    */
+    pub fn get_env(&self, inherited: &HashMap<String, String>) -> HashMap<String, String> {
+        merge(inherited, &self.env)
+    }
+    /**
+    Returns the step commands environment with the PIPELIGHT_* run variables.
+    Run variables override the user defined ones.
+    */
+    pub fn get_context_env(
+        &self,
+        pipeline: &Pipeline,
+        env: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut res = env.to_owned();
+        res.extend(pipeline.get_context_env());
+        res.insert("PIPELIGHT_STEP".to_owned(), self.name.to_owned());
+        res
+    }
+}
+
+/**
+Replace the `${{ secrets.NAME }}` references of environment values
+with the registered project secrets.
+*/
+pub fn resolve_secrets(env: &HashMap<String, String>) -> Result<HashMap<String, String>> {
+    let mut res = HashMap::new();
+    for (key, value) in env {
+        for placeholder in placeholders(value) {
+            if let Some(name) = placeholder.variable.strip_prefix("secrets.") {
+                if secrets::get(name).is_none() {
+                    let message = format!("Unknown secret {:?} in {:?}", name, key);
+                    let help = format!("Set it with \"pipelight secrets set {} <value>\"", name);
+                    return Err(IsError::new(&message, &help)?.into());
+                }
+            }
+        }
+        let value = interpolate(value, |placeholder| {
+            match placeholder.variable.strip_prefix("secrets.") {
+                Some(name) => secrets::get(name).unwrap(),
+                None => value[placeholder.start..placeholder.end].to_owned(),
+            }
+        });
+        res.insert(key.to_owned(), value);
+    }
+    Ok(res)
+}
@@ -17,4 +17,11 @@ impl Step {
             None
         }
     }
+    pub fn get_cwd(&self) -> Option<String> {
+        if let Some(options) = &self.options {
+            options.cwd.clone()
+        } else {
+            None
+        }
+    }
 }
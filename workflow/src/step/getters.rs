@@ -12,6 +12,16 @@ impl Step {
         }
         Ok(procs)
     }
+    pub fn get_conditions(&self) -> Result<Vec<String>> {
+        let mut conditions: Vec<String> = vec![];
+        if let Some(condition) = self.condition.clone() {
+            conditions.push(condition);
+        }
+        if let Some(fallback) = &self.fallback {
+            conditions.extend(fallback.get_conditions()?);
+        }
+        Ok(conditions)
+    }
 }
 impl Parallel {
     pub fn get_procs(&self) -> Result<Vec<Process>> {
@@ -21,6 +31,19 @@ impl Parallel {
         }
         Ok(procs)
     }
+    pub fn get_conditions(&self) -> Result<Vec<String>> {
+        let mut conditions: Vec<String> = vec![];
+        if let Some(condition) = self.condition.clone() {
+            conditions.push(condition);
+        }
+        for step in &self.steps {
+            conditions.extend(step.get_conditions()?);
+        }
+        if let Some(fallback) = &self.fallback {
+            conditions.extend(fallback.get_conditions()?);
+        }
+        Ok(conditions)
+    }
 }
 impl StepOrParallel {
     pub fn get_procs(&self) -> Result<Vec<Process>> {
@@ -35,6 +58,12 @@ impl StepOrParallel {
         }
         Ok(procs)
     }
+    pub fn get_conditions(&self) -> Result<Vec<String>> {
+        match self {
+            StepOrParallel::Step(step) => step.get_conditions(),
+            StepOrParallel::Parallel(parallel) => parallel.get_conditions(),
+        }
+    }
 }
 impl Fallback {
     pub fn get_procs(&self) -> Result<Vec<Process>> {
@@ -44,6 +73,7 @@ impl Fallback {
             self.on_failure.clone(),
             self.on_success.clone(),
             self.on_abortion.clone(),
+            self.on_timeout.clone(),
         ];
         for vec_step_or_parallel in fallbacks {
             if let Some(vec_step_or_parallel) = vec_step_or_parallel {
@@ -54,4 +84,18 @@ impl Fallback {
         }
         Ok(procs)
     }
+    pub fn get_conditions(&self) -> Result<Vec<String>> {
+        let mut conditions: Vec<String> = vec![];
+        let fallbacks = [
+            &self.on_started,
+            &self.on_failure,
+            &self.on_success,
+            &self.on_abortion,
+            &self.on_timeout,
+        ];
+        for step_or_parallel in fallbacks.into_iter().flatten().flatten() {
+            conditions.extend(step_or_parallel.get_conditions()?);
+        }
+        Ok(conditions)
+    }
 }
@@ -0,0 +1,46 @@
+// Structs
+use crate::condition::{Context, Value};
+use crate::types::{Command, Pipeline};
+use cast::interpolation::{interpolate, interpolation_env_name};
+use std::collections::HashMap;
+
+impl Command {
+    /**
+    Replace the `${{ variable }}` placeholders of the command
+    with the running pipeline context.
+    Values are passed to the process as environment variables
+    and placeholders replaced with references to them,
+    for the values to never be interpreted as shell code.
+    Unset variables are empty.
+    The original command text is kept for retries to be interpolated again.
+    */
+    pub(crate) fn interpolate(&mut self, ptr: *mut Pipeline, env: &HashMap<String, String>) {
+        if let Some(stdin) = &self.process.io.stdin {
+            if self.original_stdin.is_none() {
+                self.original_stdin = Some(stdin.clone());
+            }
+            let context = unsafe { Context::new(&*ptr, env) };
+            let mut values = HashMap::new();
+            let res = interpolate(stdin, |placeholder| {
+                let path: Vec<&str> = match placeholder.env_name() {
+                    Some(name) => vec!["env", name],
+                    None => placeholder.variable.split('.').collect(),
+                };
+                let value = match context.get(&path) {
+                    Value::Null => "".to_owned(),
+                    Value::Bool(boolean) => boolean.to_string(),
+                    Value::String(string) => string,
+                };
+                let name = interpolation_env_name(&placeholder.variable);
+                values.insert(name.clone(), value);
+                format!("${{{}}}", name)
+            });
+            if !values.is_empty() {
+                let mut process_env = self.process.env.clone().unwrap_or_default();
+                process_env.extend(values);
+                self.process.env = Some(process_env);
+            }
+            self.process.io.stdin = Some(res);
+        }
+    }
+}
@@ -1,2 +1,7 @@
+mod condition;
+pub mod env;
 mod execution_mode;
 mod getters;
+mod interpolation;
+mod retry;
+mod timeout;
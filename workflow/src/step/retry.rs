@@ -0,0 +1,49 @@
+// Structs
+use crate::types::{Command, RetryDelay, Step};
+use std::time::Duration;
+// Date utilities
+use utils::dates::convert::iso8601_to_std_duration;
+
+impl Step {
+    /**
+    Returns how many times a failed command can be re-run.
+    */
+    pub fn get_retries(&self) -> u32 {
+        self.options
+            .as_ref()
+            .and_then(|e| e.retries)
+            .unwrap_or_default()
+    }
+    /**
+    Returns the time to wait before running the given attempt number.
+    */
+    pub fn get_retry_delay(&self, attempt: u32) -> Option<Duration> {
+        let retry_delay = self.options.as_ref().and_then(|e| e.retry_delay.clone())?;
+        match retry_delay {
+            RetryDelay::Fixed(delay) => iso8601_to_std_duration(&delay).ok(),
+            RetryDelay::Exponential(delay) => {
+                let delay = iso8601_to_std_duration(&delay).ok()?;
+                // Double the delay from the second attempt on.
+                let factor = 2_u32.saturating_pow(attempt.saturating_sub(2));
+                Some(delay.saturating_mul(factor))
+            }
+        }
+    }
+}
+
+impl Command {
+    /**
+    Returns a fresh copy of the command (new process and outputs)
+    to be run as the next attempt, from the command text before interpolation.
+    */
+    pub fn retry(&self) -> Command {
+        let stdin = self
+            .original_stdin
+            .as_ref()
+            .or(self.process.io.stdin.as_ref());
+        let mut command = Command::new(stdin.unwrap());
+        command.process.cwd = self.process.cwd.clone();
+        command.attempt = Some(self.attempt.unwrap_or(1) + 1);
+        command
+    }
+}
@@ -0,0 +1,50 @@
+// Structs
+use crate::types::{Parallel, Pipeline, Step};
+use std::time::Instant;
+// Date utilities
+use utils::dates::convert::iso8601_to_std_duration;
+
+/**
+Returns the earliest deadline between the inherited one
+and the one computed from now and the given timeout.
+*/
+fn earliest(inherited: Option<Instant>, timeout: &Option<String>) -> Option<Instant> {
+    let own = timeout
+        .as_ref()
+        .and_then(|e| iso8601_to_std_duration(e).ok())
+        .map(|e| Instant::now() + e);
+    match (inherited, own) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl Pipeline {
+    /**
+    Returns the instant at which the pipeline times out.
+    To be called when the pipeline starts.
+    */
+    pub fn get_deadline(&self) -> Option<Instant> {
+        let timeout = self.options.as_ref().and_then(|e| e.timeout.clone());
+        earliest(None, &timeout)
+    }
+}
+impl Parallel {
+    /**
+    Returns the instant at which the parallel group times out.
+    To be called when the parallel group starts.
+    */
+    pub fn get_deadline(&self, inherited: Option<Instant>) -> Option<Instant> {
+        earliest(inherited, &self.timeout)
+    }
+}
+impl Step {
+    /**
+    Returns the instant at which the step times out.
+    To be called when the step starts.
+    */
+    pub fn get_deadline(&self, inherited: Option<Instant>) -> Option<Instant> {
+        let timeout = self.options.as_ref().and_then(|e| e.timeout.clone());
+        earliest(inherited, &timeout)
+    }
+}
@@ -3,6 +3,7 @@ use crate::types::{Command, Event, Logs, Mode, Node, Parallel, Pipeline, Step, S
 use crate::types::{Trigger, TriggerBranch, TriggerTag};
 use exec::Process;
 use log::LevelFilter;
+use std::env;
 use utils::git::{Flag, Special};
 use uuid::Uuid;
 // Date and time
@@ -33,10 +34,13 @@ impl Default for Pipeline {
         Pipeline {
             uuid: Uuid::new_v4(),
             name: "default".to_owned(),
+            global: false,
             event: None,
             status: None,
             duration: None,
             triggers: None,
+            env: None,
+            params: None,
             options: None,
             steps,
             fallback: None,
@@ -67,6 +71,9 @@ impl Default for Parallel {
             status: None,
             duration: None,
             steps: vec![Step::default()],
+            condition: None,
+            timeout: None,
+            env: None,
             fallback: None,
         }
     }
@@ -92,6 +99,14 @@ impl Default for TriggerBranch {
         TriggerBranch {
             action: Some(Flag::Special(Special::Manual)),
             branch: None,
+            paths: None,
+            paths_ignore: None,
+            schedule: None,
+            catch_up: None,
+            after: None,
+            changed: None,
+            scheduled_at: None,
+            upstream: None,
             commit: None,
         }
     }
@@ -101,6 +116,14 @@ impl Default for TriggerTag {
         TriggerTag {
             action: Some(Flag::Special(Special::Manual)),
             tag: None,
+            paths: None,
+            paths_ignore: None,
+            schedule: None,
+            catch_up: None,
+            after: None,
+            changed: None,
+            scheduled_at: None,
+            upstream: None,
             commit: None,
         }
     }
@@ -114,6 +137,9 @@ impl Default for Step {
             status: None,
             duration: None,
             commands,
+            needs: None,
+            condition: None,
+            env: None,
             options: None,
             fallback: None,
         }
@@ -155,6 +181,8 @@ impl Default for Event {
             pid: Some(Pid::as_raw(Some(pid))),
             pgid: Some(Pid::as_raw(Some(pgid))),
             sid: Some(Pid::as_raw(Some(sid))),
+            params: None,
+            project: env::current_dir().ok().map(|e| e.display().to_string()),
         }
     }
 }
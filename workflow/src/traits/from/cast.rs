@@ -1,17 +1,22 @@
 use crate::pipeline::Filters;
+use crate::types::{CatchUp, Trigger, TriggerAfter, TriggerBranch, TriggerTag};
 use crate::types::{
-    Command, Config, ConfigOpts, Fallback, Mode, Parallel, Pipeline, PipelineOpts, Step, StepOpts,
-    StepOrParallel,
+    Command, Config, ConfigOpts, Fallback, Mode, Parallel, Param, ParamType, Pipeline,
+    PipelineOpts, RetryDelay, Status, Step, StepOpts, StepOrParallel,
 };
-use crate::types::{Trigger, TriggerBranch, TriggerTag};
 use exec::Process;
 use log::LevelFilter;
 
 use convert_case::{Case, Casing};
 
+use super::resolve::resolve_pipelines;
+
+use std::collections::{BTreeMap, HashMap};
 use std::convert::From;
+use std::env;
 use std::process::exit;
-use utils::git::Flag;
+use utils::dates::convert::{std_duration_to_iso8601, str_to_std_duration};
+use utils::git::{Flag, Special};
 use uuid::Uuid;
 
 // Logger
@@ -27,6 +32,9 @@ impl From<&cast::ConfigOpts> for ConfigOpts {
         if let Some(attach) = e.attach {
             options.attach = Some(attach);
         }
+        if let Some(debounce) = &e.watch_debounce {
+            options.watch_debounce = Some(duration_to_iso8601(debounce));
+        }
         options
     }
 }
@@ -38,12 +46,10 @@ impl From<&cast::Config> for Config {
         }
         let mut pipelines = None;
         if e.pipelines.is_some() {
-            let mut binding_pipelines = e
-                .clone()
-                .pipelines
-                .unwrap()
+            // Inheritance and templates are resolved before conversion
+            let mut binding_pipelines = resolve_pipelines(e)
                 .iter()
-                .map(Pipeline::from)
+                .flat_map(Pipeline::from_matrix)
                 .collect();
             binding_pipelines = Filters::dedup(binding_pipelines).unwrap();
             pipelines = Some(binding_pipelines);
@@ -66,6 +72,12 @@ impl From<&cast::PipelineOpts> for PipelineOpts {
         if let Some(attach) = e.attach {
             options.attach = Some(attach);
         }
+        if let Some(timeout) = &e.timeout {
+            options.timeout = Some(duration_to_iso8601(timeout));
+        }
+        if let Some(concurrency) = e.concurrency {
+            options.concurrency = Some(concurrency);
+        }
         options
     }
 }
@@ -73,11 +85,7 @@ impl From<&cast::PipelineOpts> for PipelineOpts {
 impl From<&cast::Pipeline> for Pipeline {
     fn from(e: &cast::Pipeline) -> Self {
         // Convert steps
-        let steps = &e
-            .steps
-            .iter()
-            .map(StepOrParallel::from)
-            .collect::<Vec<StepOrParallel>>();
+        let steps = &steps_from(&e.steps);
 
         // Convert fallback
         let mut fallback = None;
@@ -110,6 +118,11 @@ impl From<&cast::Pipeline> for Pipeline {
             name: e.name.to_owned(),
             steps: steps.to_owned(),
             triggers,
+            env: e.env.clone(),
+            params: e
+                .params
+                .as_ref()
+                .map(|params| params.iter().map(Param::from).collect()),
             fallback,
             options,
             ..Pipeline::default()
@@ -117,14 +130,205 @@ impl From<&cast::Pipeline> for Pipeline {
     }
 }
 
+impl Pipeline {
+    /**
+    Convert a pipeline into one pipeline per matrix combination.
+    */
+    pub fn from_matrix(e: &cast::Pipeline) -> Vec<Pipeline> {
+        match &e.matrix {
+            None => vec![Pipeline::from(e)],
+            Some(matrix) => matrix_combinations(matrix)
+                .iter()
+                .map(|combination| {
+                    Pipeline::from(&cast::Pipeline {
+                        name: matrix_name(&e.name, combination),
+                        env: matrix_env(&e.env, combination),
+                        matrix: None,
+                        ..e.clone()
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+impl Step {
+    /**
+    Convert a step into one step per matrix combination.
+    */
+    pub fn from_matrix(e: &cast::Step) -> Vec<Step> {
+        match &e.matrix {
+            None => vec![Step::from(e)],
+            Some(matrix) => matrix_combinations(matrix)
+                .iter()
+                .map(|combination| {
+                    Step::from(&cast::Step {
+                        name: matrix_name(&e.name, combination),
+                        env: matrix_env(&e.env, combination),
+                        matrix: None,
+                        ..e.clone()
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/**
+Convert a list of steps, expanding matrix steps.
+Needing a matrix step means needing every one of its combinations.
+*/
+fn steps_from(e: &[cast::StepOrParallel]) -> Vec<StepOrParallel> {
+    let mut expanded: HashMap<String, Vec<String>> = HashMap::new();
+    let mut steps: Vec<StepOrParallel> = vec![];
+    for step_or_parallel in e {
+        let cast_steps = match step_or_parallel {
+            cast::StepOrParallel::Step(step) => {
+                let res = Step::from_matrix(step);
+                steps.extend(res.into_iter().map(StepOrParallel::Step));
+                vec![step]
+            }
+            cast::StepOrParallel::Parallel(parallel) => {
+                steps.push(StepOrParallel::Parallel(Parallel::from(parallel)));
+                parallel.parallel.iter().collect()
+            }
+            cast::StepOrParallel::Use(step) => unresolved(step),
+        };
+        for step in cast_steps {
+            if let Some(matrix) = &step.matrix {
+                let names = matrix_combinations(matrix)
+                    .iter()
+                    .map(|combination| matrix_name(&step.name, combination))
+                    .collect();
+                expanded.insert(step.name.to_owned(), names);
+            }
+        }
+    }
+    for step in &mut steps {
+        if let StepOrParallel::Step(Step {
+            needs: Some(needs), ..
+        }) = step
+        {
+            *needs = needs
+                .iter()
+                .flat_map(|need| expanded.get(need).cloned().unwrap_or(vec![need.to_owned()]))
+                .collect();
+        }
+    }
+    steps
+}
+
+/**
+Returns every combination of the matrix axis values
+as lists of (axis, value) pairs.
+An axis without values has no combinations (reported by the config check).
+*/
+fn matrix_combinations(matrix: &BTreeMap<String, Vec<String>>) -> Vec<Vec<(String, String)>> {
+    let mut combinations: Vec<Vec<(String, String)>> = vec![vec![]];
+    for (axis, values) in matrix {
+        combinations = combinations
+            .iter()
+            .flat_map(|combination| {
+                values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push((axis.to_owned(), value.to_owned()));
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+/**
+Returns the expanded name, like "test-full-nightly",
+the name followed by the axis values ordered by axis.
+Values are slugified for the name to be safely used on the command line.
+*/
+fn matrix_name(name: &str, combination: &[(String, String)]) -> String {
+    let mut names = vec![name.to_owned()];
+    for (_, value) in combination {
+        let slug: String = value
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "._".contains(c) {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        names.push(slug);
+    }
+    names.join("-")
+}
+/**
+Add the axis values to the environment,
+the "toolchain" axis is available as the "TOOLCHAIN" variable.
+*/
+fn matrix_env(
+    env: &Option<HashMap<String, String>>,
+    combination: &[(String, String)],
+) -> Option<HashMap<String, String>> {
+    let mut env = env.clone().unwrap_or_default();
+    for (axis, value) in combination {
+        env.insert(axis.to_case(Case::UpperSnake), value.to_owned());
+    }
+    Some(env)
+}
+
+impl From<&cast::Param> for Param {
+    fn from(e: &cast::Param) -> Self {
+        let kind = match e.kind.as_deref() {
+            None | Some("string") => ParamType::String,
+            Some("number") => ParamType::Number,
+            Some("boolean") => ParamType::Boolean,
+            Some(kind) => {
+                let message = format!("The parameter type {} is not known", kind);
+                error!("{}", message);
+                exit(1);
+            }
+        };
+        Param {
+            name: e.name.to_owned(),
+            kind,
+            default: e.default.as_ref().map(param_value),
+            values: e
+                .values
+                .as_ref()
+                .map(|values| values.iter().map(param_value).collect()),
+            description: e.description.clone(),
+        }
+    }
+}
+/**
+Returns the parameter value as a string, "3" for 3 and "true" for true.
+*/
+fn param_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(string) => string.to_owned(),
+        value => value.to_string(),
+    }
+}
+
 impl From<&cast::StepOrParallel> for StepOrParallel {
     fn from(e: &cast::StepOrParallel) -> Self {
         match e {
             cast::StepOrParallel::Step(res) => StepOrParallel::Step(Step::from(res)),
             cast::StepOrParallel::Parallel(res) => StepOrParallel::Parallel(Parallel::from(res)),
+            cast::StepOrParallel::Use(res) => unresolved(res),
         }
     }
 }
+/**
+Template steps can only be converted from a whole config.
+*/
+fn unresolved(step: &cast::UseStep) -> ! {
+    let message = format!(
+        "The step template {} can't be resolved outside of a config",
+        step.template
+    );
+    error!("{}", message);
+    exit(1);
+}
 
 impl From<&cast::StepOpts> for StepOpts {
     fn from(e: &cast::StepOpts) -> Self {
@@ -132,13 +336,39 @@ impl From<&cast::StepOpts> for StepOpts {
         if let Some(mode) = &e.mode {
             options.mode = Some(Mode::from(mode));
         }
+        // Resolve the working directory relative to the teleported directory
+        if let Some(cwd) = &e.cwd {
+            let path = env::current_dir().unwrap().join(cwd);
+            options.cwd = Some(path.to_str().unwrap().to_owned());
+        }
+        if let Some(timeout) = &e.timeout {
+            options.timeout = Some(duration_to_iso8601(timeout));
+        }
+        options.retries = e.retries;
+        if let Some(retry_delay) = &e.retry_delay {
+            options.retry_delay = Some(RetryDelay::from(retry_delay));
+        }
         options
     }
 }
 
+impl From<&cast::RetryDelay> for RetryDelay {
+    fn from(e: &cast::RetryDelay) -> Self {
+        match e {
+            cast::RetryDelay::Fixed(delay)
+            | cast::RetryDelay::Strategy(cast::RetryStrategy::Fixed(delay)) => {
+                RetryDelay::Fixed(duration_to_iso8601(delay))
+            }
+            cast::RetryDelay::Strategy(cast::RetryStrategy::Exponential(delay)) => {
+                RetryDelay::Exponential(duration_to_iso8601(delay))
+            }
+        }
+    }
+}
+
 impl From<&cast::Step> for Step {
     fn from(e: &cast::Step) -> Self {
-        let commands = e
+        let mut commands = e
             .commands
             .iter()
             .map(Command::from)
@@ -155,10 +385,18 @@ impl From<&cast::Step> for Step {
         if e.options.is_some() {
             options = Some(StepOpts::from(e.options.as_ref().unwrap()));
         }
+        if let Some(StepOpts { cwd: Some(cwd), .. }) = &options {
+            for command in &mut commands {
+                command.process.cwd = Some(cwd.to_owned());
+            }
+        }
 
         Step {
             name: e.clone().name,
             commands,
+            needs: e.needs.clone(),
+            condition: e.condition.clone(),
+            env: e.env.clone(),
             fallback,
             options,
             ..Step::default()
@@ -173,12 +411,15 @@ impl From<&cast::Parallel> for Parallel {
             fallback = Some(Fallback::from(e.fallback.as_ref().unwrap()));
         }
         let mut res = Parallel {
+            condition: e.condition.clone(),
+            timeout: e.timeout.as_ref().map(duration_to_iso8601),
+            env: e.env.clone(),
             fallback,
             steps: vec![],
             ..Parallel::new()
         };
         for step in &e.parallel {
-            res.steps.push(Step::from(step));
+            res.steps.extend(Step::from_matrix(step));
         }
         res
     }
@@ -199,51 +440,38 @@ impl From<&cast::Fallback> for Fallback {
         let mut on_started = None;
         if e.on_started.is_some() {
             let binding = e.on_started.clone().unwrap();
-            on_started = Some(
-                binding
-                    .iter()
-                    .map(StepOrParallel::from)
-                    .collect::<Vec<StepOrParallel>>(),
-            );
+            on_started = Some(steps_from(&binding));
         }
         // Convert post-run steps
         let mut on_failure = None;
         if e.on_failure.is_some() {
             let binding = e.on_failure.clone().unwrap();
-            on_failure = Some(
-                binding
-                    .iter()
-                    .map(StepOrParallel::from)
-                    .collect::<Vec<StepOrParallel>>(),
-            );
+            on_failure = Some(steps_from(&binding));
         }
         // Convert post-run steps
         let mut on_success = None;
         if e.on_success.is_some() {
             let binding = e.on_success.clone().unwrap();
-            on_success = Some(
-                binding
-                    .iter()
-                    .map(StepOrParallel::from)
-                    .collect::<Vec<StepOrParallel>>(),
-            );
+            on_success = Some(steps_from(&binding));
         }
         // Convert post-run steps
         let mut on_abortion = None;
         if e.on_abortion.is_some() {
             let binding = e.on_abortion.clone().unwrap();
-            on_abortion = Some(
-                binding
-                    .iter()
-                    .map(StepOrParallel::from)
-                    .collect::<Vec<StepOrParallel>>(),
-            );
+            on_abortion = Some(steps_from(&binding));
+        }
+        // Convert post-run steps
+        let mut on_timeout = None;
+        if e.on_timeout.is_some() {
+            let binding = e.on_timeout.clone().unwrap();
+            on_timeout = Some(steps_from(&binding));
         }
         Fallback {
             on_started,
             on_failure,
             on_success,
             on_abortion,
+            on_timeout,
         }
     }
 }
@@ -252,30 +480,59 @@ impl Trigger {
         let mut tuplelist: Vec<Trigger> = vec![];
         match &e {
             cast::Trigger::TriggerBranch(res) => {
-                if res.branches.is_none() {
-                    for action in res.actions.clone().unwrap() {
+                let actions = implicit_actions(&res.actions, &res.schedule, &res.after);
+                // Only filtered by changed files
+                if res.branches.is_none() && actions.is_none() {
+                    tuplelist.push(Trigger::TriggerBranch(TriggerBranch {
+                        action: None,
+                        branch: None,
+                        paths: res.paths.clone(),
+                        paths_ignore: res.paths_ignore.clone(),
+                        schedule: res.schedule.clone(),
+                        catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                        after: res.after.as_ref().map(TriggerAfter::from),
+                        ..TriggerBranch::default()
+                    }))
+                }
+                if res.branches.is_none() && actions.is_some() {
+                    for action in actions.clone().unwrap() {
                         tuplelist.push(Trigger::TriggerBranch(TriggerBranch {
                             action: Some(Flag::from(&action)),
                             branch: None,
+                            paths: res.paths.clone(),
+                            paths_ignore: res.paths_ignore.clone(),
+                            schedule: res.schedule.clone(),
+                            catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                            after: res.after.as_ref().map(TriggerAfter::from),
                             ..TriggerBranch::default()
                         }))
                     }
                 }
-                if res.actions.is_none() {
+                if actions.is_none() && res.branches.is_some() {
                     for branch in res.branches.clone().unwrap() {
                         tuplelist.push(Trigger::TriggerBranch(TriggerBranch {
                             action: None,
                             branch: Some(branch.to_owned()),
+                            paths: res.paths.clone(),
+                            paths_ignore: res.paths_ignore.clone(),
+                            schedule: res.schedule.clone(),
+                            catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                            after: res.after.as_ref().map(TriggerAfter::from),
                             ..TriggerBranch::default()
                         }))
                     }
                 }
-                if res.branches.is_some() && res.actions.is_some() {
+                if res.branches.is_some() && actions.is_some() {
                     for branch in res.branches.clone().unwrap() {
-                        for action in res.actions.clone().unwrap() {
+                        for action in actions.clone().unwrap() {
                             tuplelist.push(Trigger::TriggerBranch(TriggerBranch {
                                 action: Some(Flag::from(&action)),
                                 branch: Some(branch.to_owned()),
+                                paths: res.paths.clone(),
+                                paths_ignore: res.paths_ignore.clone(),
+                                schedule: res.schedule.clone(),
+                                catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                                after: res.after.as_ref().map(TriggerAfter::from),
                                 ..TriggerBranch::default()
                             }))
                         }
@@ -283,30 +540,59 @@ impl Trigger {
                 }
             }
             cast::Trigger::TriggerTag(res) => {
-                if res.tags.is_none() {
-                    for action in res.actions.clone().unwrap() {
+                let actions = implicit_actions(&res.actions, &res.schedule, &res.after);
+                // Only filtered by changed files
+                if res.tags.is_none() && actions.is_none() {
+                    tuplelist.push(Trigger::TriggerTag(TriggerTag {
+                        action: None,
+                        tag: None,
+                        paths: res.paths.clone(),
+                        paths_ignore: res.paths_ignore.clone(),
+                        schedule: res.schedule.clone(),
+                        catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                        after: res.after.as_ref().map(TriggerAfter::from),
+                        ..TriggerTag::default()
+                    }))
+                }
+                if res.tags.is_none() && actions.is_some() {
+                    for action in actions.clone().unwrap() {
                         tuplelist.push(Trigger::TriggerTag(TriggerTag {
                             action: Some(Flag::from(&action)),
                             tag: None,
+                            paths: res.paths.clone(),
+                            paths_ignore: res.paths_ignore.clone(),
+                            schedule: res.schedule.clone(),
+                            catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                            after: res.after.as_ref().map(TriggerAfter::from),
                             ..TriggerTag::default()
                         }))
                     }
                 }
-                if res.actions.is_none() {
+                if actions.is_none() && res.tags.is_some() {
                     for tag in res.tags.clone().unwrap() {
                         tuplelist.push(Trigger::TriggerTag(TriggerTag {
                             action: None,
                             tag: Some(tag.to_owned()),
+                            paths: res.paths.clone(),
+                            paths_ignore: res.paths_ignore.clone(),
+                            schedule: res.schedule.clone(),
+                            catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                            after: res.after.as_ref().map(TriggerAfter::from),
                             ..TriggerTag::default()
                         }))
                     }
                 }
-                if res.tags.is_some() && res.actions.is_some() {
+                if res.tags.is_some() && actions.is_some() {
                     for tag in res.tags.clone().unwrap() {
-                        for action in res.actions.clone().unwrap() {
+                        for action in actions.clone().unwrap() {
                             tuplelist.push(Trigger::TriggerTag(TriggerTag {
                                 action: Some(Flag::from(&action)),
                                 tag: Some(tag.to_owned()),
+                                paths: res.paths.clone(),
+                                paths_ignore: res.paths_ignore.clone(),
+                                schedule: res.schedule.clone(),
+                                catch_up: res.catch_up.as_ref().map(CatchUp::from),
+                                after: res.after.as_ref().map(TriggerAfter::from),
                                 ..TriggerTag::default()
                             }))
                         }
@@ -317,6 +603,68 @@ impl Trigger {
         tuplelist
     }
 }
+/**
+A scheduled trigger is fired by the scheduler with the "schedule" action,
+a chained trigger by its upstream pipeline with the "after" action,
+in addition to the declared actions.
+*/
+fn implicit_actions(
+    actions: &Option<Vec<String>>,
+    schedule: &Option<String>,
+    after: &Option<cast::TriggerAfter>,
+) -> Option<Vec<String>> {
+    let mut actions = actions.clone();
+    let implicit = [
+        schedule.as_ref().map(|_| Special::Schedule),
+        after.as_ref().map(|_| Special::After),
+    ];
+    for special in implicit.into_iter().flatten() {
+        let action = String::from(&Flag::Special(special));
+        let list = actions.get_or_insert_with(Vec::new);
+        if !list.contains(&action) {
+            list.push(action);
+        }
+    }
+    actions
+}
+impl From<&cast::TriggerAfter> for TriggerAfter {
+    fn from(e: &cast::TriggerAfter) -> TriggerAfter {
+        let status = e.status.as_ref().map(|list| {
+            list.iter()
+                .map(|status| {
+                    let cased: &str = &status.to_case(Case::Kebab);
+                    match serde_plain::from_str::<Status>(cased) {
+                        Ok(status) => status,
+                        Err(_) => {
+                            let message = format!("The upstream status {} is not known", cased);
+                            error!("{}", message);
+                            exit(1);
+                        }
+                    }
+                })
+                .collect()
+        });
+        TriggerAfter {
+            pipeline: e.pipeline.clone(),
+            status,
+        }
+    }
+}
+impl From<&String> for CatchUp {
+    fn from(catch_up: &String) -> CatchUp {
+        let cased: &str = &catch_up.to_case(Case::Snake);
+        match cased {
+            "skip" => CatchUp::Skip,
+            "latest" => CatchUp::Latest,
+            "all" => CatchUp::All,
+            _ => {
+                let message = format!("The catch-up policy {} is not known", cased);
+                error!("{}", message);
+                exit(1);
+            }
+        }
+    }
+}
 impl From<&String> for Mode {
     fn from(mode: &String) -> Mode {
         let cased: &str = &mode.to_case(Case::Snake);
@@ -342,3 +690,17 @@ impl From<&Mode> for String {
         }
     }
 }
+
+/**
+Normalize a duration string (ISO8601 or human readable) into an ISO8601 duration.
+*/
+fn duration_to_iso8601(duration: &String) -> String {
+    match str_to_std_duration(duration).and_then(|e| std_duration_to_iso8601(&e)) {
+        Ok(res) => res,
+        Err(_) => {
+            let message = format!("The duration {} is not valid", duration);
+            error!("{}", message);
+            exit(1);
+        }
+    }
+}
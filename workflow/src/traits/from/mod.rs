@@ -1,2 +1,5 @@
 mod cast;
 mod node;
+mod resolve;
+// Tests
+mod test;
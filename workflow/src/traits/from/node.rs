@@ -8,6 +8,7 @@ use crate::types::{Command, Event, Node, Parallel, Pipeline, Step, StepOrParalle
 use exec::{Statuable, Status};
 use log::LevelFilter;
 use utils::git::Flag;
+use utils::secrets;
 // Globals
 use utils::globals::LOGGER;
 // Colorize
@@ -66,6 +67,20 @@ impl From<&Event> for String {
             string.push_str(&commit);
         }
 
+        // Set the upstream pipelines chain
+        if let Some(upstream) = e.trigger.get_upstream().unwrap() {
+            let header = "after: ";
+            let mut chain = upstream.chain.clone();
+            chain.push(upstream.pipeline.clone());
+            let after = format!(
+                "{}{} {}\n",
+                header.white(),
+                chain.join(" > ").white(),
+                format!("({})", upstream.uuid).white()
+            );
+            string.push_str(&after);
+        }
+
         string
     }
 }
@@ -127,6 +142,16 @@ impl From<&Pipeline> for Node {
                 };
                 children.push(node);
             }
+            if e.fallback.clone().unwrap().on_timeout.is_some() {
+                let on_timeout = e.fallback.clone().unwrap().on_timeout.unwrap();
+                let on_timeout_children = on_timeout.iter().map(Node::from).collect();
+                let node = Node {
+                    children: Some(on_timeout_children),
+                    value: Some("on_timeout".to_owned()),
+                    ..Node::default()
+                };
+                children.push(node);
+            }
         }
         Node {
             value: Some(head),
@@ -188,6 +213,16 @@ impl From<&Parallel> for Node {
                 };
                 children.push(node);
             }
+            if e.fallback.clone().unwrap().on_timeout.is_some() {
+                let on_timeout = e.fallback.clone().unwrap().on_timeout.unwrap();
+                let on_timeout_children = on_timeout.iter().map(Node::from).collect();
+                let node = Node {
+                    children: Some(on_timeout_children),
+                    value: Some("on_timeout".to_owned()),
+                    ..Node::default()
+                };
+                children.push(node);
+            }
         }
         Node {
             value: Some("parallel".to_owned()),
@@ -201,7 +236,16 @@ impl From<&Parallel> for Node {
 impl From<&Step> for Node {
     fn from(e: &Step) -> Self {
         let head = format!("step: {}", e.name.clone());
-        let mut children: Vec<Node> = e.commands.iter().map(Node::from).collect();
+        let mut children: Vec<Node> = vec![];
+        // Working directory
+        if let Some(cwd) = e.get_cwd() {
+            children.push(Node {
+                value: Some(format!("cwd: {}", cwd)),
+                level: LevelFilter::Debug,
+                ..Node::new()
+            });
+        }
+        children.extend(e.commands.iter().map(Node::from));
 
         // Duration
         let mut duration: Option<String> = None;
@@ -242,6 +286,16 @@ impl From<&Step> for Node {
                 };
                 children.push(node);
             }
+            if e.fallback.clone().unwrap().on_timeout.is_some() {
+                let on_timeout = e.fallback.clone().unwrap().on_timeout.unwrap();
+                let on_timeout_children = on_timeout.iter().map(Node::from).collect();
+                let node = Node {
+                    children: Some(on_timeout_children),
+                    value: Some("on_timeout".to_owned()),
+                    ..Node::default()
+                };
+                children.push(node);
+            }
         }
         Node {
             value: Some(head),
@@ -269,12 +323,16 @@ impl From<&Command> for Node {
         if e.process.io.stdout.is_some() | e.process.io.stderr.is_some() {
             let stdout = format!("stdout: {}", e.process.io.stdout.clone().unwrap());
             let stderr = format!("stderr: {}", e.process.io.stderr.clone().unwrap());
+            let stdout = secrets::redact(&stdout);
+            let stderr = secrets::redact(&stderr);
 
             let out = match e.get_status() {
-                Some(Status::Succeeded) => e.process.io.stdout.clone(),
-                Some(Status::Failed) => e.process.io.stderr.clone(),
+                Some(Status::Succeeded) => e.process.io.stdout.as_deref().map(secrets::redact),
+                Some(Status::Failed) => e.process.io.stderr.as_deref().map(secrets::redact),
+                Some(Status::TimedOut) => e.process.io.stderr.as_deref().map(secrets::redact),
                 Some(Status::Started) => None,
                 Some(Status::Aborted) => None,
+                Some(Status::Skipped) => None,
                 Some(Status::Running) => None,
                 None => None,
             };
@@ -305,7 +363,10 @@ impl From<&Command> for Node {
                 node.children = Some(vec![stdout, stderr]);
             }
         }
-        node.value = e.process.io.stdin.clone();
+        node.value = e.process.io.stdin.as_deref().map(secrets::redact);
+        if let (Some(value), Some(attempt)) = (&mut node.value, e.attempt) {
+            value.push_str(&format!(" (attempt {})", attempt));
+        }
         node.status = e.get_status();
         node
     }
@@ -0,0 +1,243 @@
+// Structs
+use std::collections::{BTreeMap, HashMap};
+// Traits
+use convert_case::{Case, Casing};
+// Error Handling
+use log::error;
+
+/**
+Resolve pipeline inheritance and step templates,
+so that every converted pipeline is concrete.
+*/
+pub(super) fn resolve_pipelines(config: &cast::Config) -> Vec<cast::Pipeline> {
+    let pipelines = config.pipelines.clone().unwrap_or_default();
+    let templates: HashMap<&str, &cast::Step> = config
+        .templates
+        .iter()
+        .flatten()
+        .map(|e| (e.name.as_str(), e))
+        .collect();
+    pipelines
+        .iter()
+        .map(|pipeline| {
+            let mut pipeline = extend(pipeline, &pipelines, &mut vec![]);
+            pipeline.steps = use_templates(&pipeline.steps, &templates, &mut vec![]);
+            pipeline.fallback = pipeline
+                .fallback
+                .map(|e| fallback_templates(&e, &templates, &mut vec![]));
+            pipeline
+        })
+        .collect()
+}
+
+/**
+Merge the pipeline with the pipelines it extends.
+Triggers, params, env, options and fallbacks are inherited.
+Fields defined by the pipeline override the inherited ones.
+*/
+fn extend(
+    pipeline: &cast::Pipeline,
+    pipelines: &[cast::Pipeline],
+    chain: &mut Vec<String>,
+) -> cast::Pipeline {
+    let Some(name) = &pipeline.extends else {
+        return pipeline.clone();
+    };
+    chain.push(pipeline.name.to_owned());
+    if chain.contains(name) {
+        let message = format!(
+            "The pipeline inheritance loops: {} -> {}",
+            chain.join(" -> "),
+            name
+        );
+        error!("{}", message);
+        return unextended(pipeline);
+    }
+    let Some(parent) = pipelines.iter().find(|e| &e.name == name) else {
+        let message = format!(
+            "The pipeline {} extends the unknown pipeline {}",
+            pipeline.name, name
+        );
+        error!("{}", message);
+        return unextended(pipeline);
+    };
+    let parent = extend(parent, pipelines, chain);
+
+    let env = match (parent.env, &pipeline.env) {
+        (Some(mut env), Some(own)) => {
+            env.extend(own.clone());
+            Some(env)
+        }
+        (env, own) => own.clone().or(env),
+    };
+    let options = match (parent.options, &pipeline.options) {
+        (Some(inherited), Some(own)) => Some(cast::PipelineOpts {
+            attach: own.attach.or(inherited.attach),
+            log_level: own.log_level.clone().or(inherited.log_level),
+            timeout: own.timeout.clone().or(inherited.timeout),
+            concurrency: own.concurrency.or(inherited.concurrency),
+        }),
+        (options, own) => own.clone().or(options),
+    };
+    let fallback = match (parent.fallback, &pipeline.fallback) {
+        (Some(inherited), Some(own)) => Some(cast::Fallback {
+            on_started: own.on_started.clone().or(inherited.on_started),
+            on_failure: own.on_failure.clone().or(inherited.on_failure),
+            on_success: own.on_success.clone().or(inherited.on_success),
+            on_abortion: own.on_abortion.clone().or(inherited.on_abortion),
+            on_timeout: own.on_timeout.clone().or(inherited.on_timeout),
+        }),
+        (fallback, own) => own.clone().or(fallback),
+    };
+    cast::Pipeline {
+        extends: None,
+        triggers: pipeline.triggers.clone().or(parent.triggers),
+        params: pipeline.params.clone().or(parent.params),
+        env,
+        options,
+        fallback,
+        ..pipeline.clone()
+    }
+}
+
+/**
+Returns the pipeline on its own, when its inheritance can't be resolved.
+Such configs are reported by the config check.
+*/
+fn unextended(pipeline: &cast::Pipeline) -> cast::Pipeline {
+    cast::Pipeline {
+        extends: None,
+        ..pipeline.clone()
+    }
+}
+
+/**
+Replace the template references with the template steps.
+*/
+fn use_templates(
+    steps: &[cast::StepOrParallel],
+    templates: &HashMap<&str, &cast::Step>,
+    chain: &mut Vec<String>,
+) -> Vec<cast::StepOrParallel> {
+    steps
+        .iter()
+        .map(|step_or_parallel| match step_or_parallel {
+            cast::StepOrParallel::Step(step) => {
+                cast::StepOrParallel::Step(step_templates(step, templates, chain))
+            }
+            cast::StepOrParallel::Parallel(parallel) => {
+                cast::StepOrParallel::Parallel(cast::Parallel {
+                    parallel: parallel
+                        .parallel
+                        .iter()
+                        .map(|e| step_templates(e, templates, chain))
+                        .collect(),
+                    fallback: parallel
+                        .fallback
+                        .as_ref()
+                        .map(|e| fallback_templates(e, templates, chain)),
+                    ..parallel.clone()
+                })
+            }
+            cast::StepOrParallel::Use(step) => {
+                cast::StepOrParallel::Step(template_step(step, templates, chain))
+            }
+        })
+        .collect()
+}
+fn step_templates(
+    step: &cast::Step,
+    templates: &HashMap<&str, &cast::Step>,
+    chain: &mut Vec<String>,
+) -> cast::Step {
+    cast::Step {
+        fallback: step
+            .fallback
+            .as_ref()
+            .map(|e| fallback_templates(e, templates, chain)),
+        ..step.clone()
+    }
+}
+fn fallback_templates(
+    fallback: &cast::Fallback,
+    templates: &HashMap<&str, &cast::Step>,
+    chain: &mut Vec<String>,
+) -> cast::Fallback {
+    let mut convert = |steps: &Option<Vec<cast::StepOrParallel>>| {
+        steps.as_ref().map(|e| use_templates(e, templates, chain))
+    };
+    cast::Fallback {
+        on_started: convert(&fallback.on_started),
+        on_failure: convert(&fallback.on_failure),
+        on_success: convert(&fallback.on_success),
+        on_abortion: convert(&fallback.on_abortion),
+        on_timeout: convert(&fallback.on_timeout),
+    }
+}
+
+/**
+Returns the template step, renamed and with the parameters in its environment.
+*/
+fn template_step(
+    step: &cast::UseStep,
+    templates: &HashMap<&str, &cast::Step>,
+    chain: &mut Vec<String>,
+) -> cast::Step {
+    let Some(template) = templates.get(step.template.as_str()) else {
+        let message = format!("The step template {} is not known", step.template);
+        error!("{}", message);
+        return unresolved_step(step);
+    };
+    // A template fallback may use another template
+    if chain.contains(&step.template) {
+        let message = format!(
+            "The step template {} uses itself: {} -> {}",
+            step.template,
+            chain.join(" -> "),
+            step.template
+        );
+        error!("{}", message);
+        return unresolved_step(step);
+    }
+    chain.push(step.template.to_owned());
+    let mut res = step_templates(template, templates, chain);
+    chain.pop();
+
+    if let Some(name) = &step.name {
+        res.name = name.to_owned();
+    }
+    if let Some(with) = &step.with {
+        res.env = params_env(&res.env, with);
+    }
+    res
+}
+/**
+Returns a step without commands, when its template can't be resolved.
+Such configs are reported by the config check.
+*/
+fn unresolved_step(step: &cast::UseStep) -> cast::Step {
+    cast::Step {
+        name: step.name.clone().unwrap_or(step.template.to_owned()),
+        commands: vec![],
+        needs: None,
+        condition: None,
+        env: None,
+        matrix: None,
+        options: None,
+        fallback: None,
+    }
+}
+/**
+Add the parameters to the environment,
+the "toolchain" parameter is available as the "TOOLCHAIN" variable.
+*/
+fn params_env(
+    env: &Option<HashMap<String, String>>,
+    params: &BTreeMap<String, String>,
+) -> Option<HashMap<String, String>> {
+    let mut env = env.clone().unwrap_or_default();
+    for (key, value) in params {
+        env.insert(key.to_case(Case::UpperSnake), value.to_owned());
+    }
+    Some(env)
+}
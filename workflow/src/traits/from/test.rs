@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod cast {
+    use crate::types::{Config, ParamType, StepOrParallel, Trigger};
+
+    #[test]
+    fn matrix_expansion() {
+        let json = r#"
+        {
+            "pipelines": [{
+                "name": "ci",
+                "matrix": { "os": ["linux", "macos/arm64"] },
+                "steps": [
+                    {
+                        "name": "test",
+                        "commands": ["cargo test"],
+                        "matrix": { "features": ["default", "full"], "toolchain": ["stable", "nightly"] }
+                    },
+                    {
+                        "name": "deploy",
+                        "commands": ["echo deploy"],
+                        "needs": ["test"]
+                    }
+                ]
+            }]
+        }
+        "#;
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let pipelines = config.pipelines.unwrap();
+        let names: Vec<String> = pipelines.iter().map(|e| e.name.clone()).collect();
+        // Names are safe to pass on the command line
+        assert_eq!(names, vec!["ci-linux", "ci-macos-arm64"]);
+
+        let steps = &pipelines[0].steps;
+        assert_eq!(steps.len(), 5);
+        match (&steps[3], &steps[4]) {
+            (StepOrParallel::Step(step), StepOrParallel::Step(deploy)) => {
+                assert_eq!(step.name, "test-full-nightly");
+                let env = step.env.clone().unwrap();
+                assert_eq!(env.get("FEATURES").map(|e| e.as_str()), Some("full"));
+                assert_eq!(env.get("TOOLCHAIN").map(|e| e.as_str()), Some("nightly"));
+                assert_eq!(deploy.needs.clone().unwrap().len(), 4);
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn step_templates() {
+        let json = r#"
+        {
+            "templates": [
+                { "name": "setup", "commands": ["rustup default ${{ env.TOOLCHAIN }}"] }
+            ],
+            "pipelines": [{
+                "name": "ci",
+                "steps": [
+                    { "use": "setup", "name": "setup nightly", "with": { "toolchain": "nightly" } },
+                    { "name": "test", "commands": ["cargo test"] }
+                ],
+                "on_failure": [{ "use": "setup" }]
+            }]
+        }
+        "#;
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let pipeline = &config.pipelines.unwrap()[0];
+        match &pipeline.steps[0] {
+            StepOrParallel::Step(step) => {
+                assert_eq!(step.name, "setup nightly");
+                let env = step.env.clone().unwrap();
+                assert_eq!(env.get("TOOLCHAIN").map(|e| e.as_str()), Some("nightly"));
+            }
+            _ => panic!(),
+        }
+        let on_failure = pipeline.fallback.clone().unwrap().on_failure.unwrap();
+        match &on_failure[0] {
+            StepOrParallel::Step(step) => assert_eq!(step.name, "setup"),
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn unresolved_templates() {
+        let json = r#"
+        {
+            "pipelines": [
+                { "name": "a", "extends": "b", "steps": [{ "use": "setup" }] },
+                { "name": "b", "extends": "a", "steps": [] }
+            ]
+        }
+        "#;
+        // Unresolvable configs are converted as is, for the check to report them
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let pipeline = &config.pipelines.unwrap()[0];
+        match &pipeline.steps[0] {
+            StepOrParallel::Step(step) => {
+                assert_eq!(step.name, "setup");
+                assert!(step.commands.is_empty());
+            }
+            _ => panic!(),
+        }
+    }
+    #[test]
+    fn pipeline_extends() {
+        let json = r#"
+        {
+            "pipelines": [
+                {
+                    "name": "base",
+                    "triggers": [{ "branches": ["main"], "actions": ["pre-push"] }],
+                    "env": { "A": "base", "B": "base" },
+                    "options": { "attach": true, "log_level": "warn" },
+                    "steps": [],
+                    "on_failure": [{ "name": "notify", "commands": ["echo failed"] }]
+                },
+                {
+                    "name": "child",
+                    "extends": "base",
+                    "env": { "B": "child" },
+                    "options": { "log_level": "debug" },
+                    "steps": [{ "name": "build", "commands": ["cargo build"] }]
+                },
+                {
+                    "name": "grandchild",
+                    "extends": "child",
+                    "triggers": [{ "actions": ["manual"] }],
+                    "steps": []
+                }
+            ]
+        }
+        "#;
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let pipelines = config.pipelines.unwrap();
+
+        let child = &pipelines[1];
+        let env = child.env.clone().unwrap();
+        assert_eq!(env.get("A").map(|e| e.as_str()), Some("base"));
+        assert_eq!(env.get("B").map(|e| e.as_str()), Some("child"));
+        let options = child.options.clone().unwrap();
+        assert_eq!(options.attach, Some(true));
+        assert_eq!(options.log_level, Some(log::LevelFilter::Debug));
+        assert_eq!(child.triggers.clone().unwrap().len(), 1);
+        assert!(child.fallback.clone().unwrap().on_failure.is_some());
+        assert_eq!(child.steps.len(), 1);
+
+        // Own fields override inherited ones through the whole chain
+        let grandchild = &pipelines[2];
+        assert_eq!(grandchild.steps.len(), 0);
+        assert_eq!(grandchild.triggers.clone().unwrap().len(), 1);
+        assert!(grandchild.fallback.clone().unwrap().on_failure.is_some());
+    }
+    #[test]
+    fn typed_params() {
+        let json = r#"
+        {
+            "pipelines": [{
+                "name": "deploy",
+                "params": [
+                    { "name": "env", "values": ["staging", "production"], "description": "Target" },
+                    { "name": "replicas", "type": "number", "default": 2, "values": [1, 2, 3] },
+                    { "name": "dry_run", "type": "boolean", "default": false }
+                ],
+                "steps": []
+            }]
+        }
+        "#;
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let params = config.pipelines.unwrap()[0].params.clone().unwrap();
+        assert_eq!(params[0].kind, ParamType::String);
+        assert_eq!(params[0].default, None);
+        assert_eq!(params[1].kind, ParamType::Number);
+        assert_eq!(params[1].default.as_deref(), Some("2"));
+        assert_eq!(params[1].values.clone().unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(params[2].default.as_deref(), Some("false"));
+    }
+    #[test]
+    fn path_filtered_triggers() {
+        let json = r#"
+        {
+            "pipelines": [{
+                "name": "build",
+                "triggers": [
+                    { "branches": ["main", "dev"], "paths_ignore": ["docs/**"] },
+                    { "paths": ["src/**"] }
+                ],
+                "steps": []
+            }]
+        }
+        "#;
+        let config = Config::from(&serde_json::from_str::<cast::Config>(json).unwrap());
+        let triggers = config.pipelines.unwrap()[0].triggers.clone().unwrap();
+        // Every flattened trigger keeps the paths filter
+        assert_eq!(triggers.len(), 3);
+        match (&triggers[1], &triggers[2]) {
+            (Trigger::TriggerBranch(dev), Trigger::TriggerBranch(paths)) => {
+                assert_eq!(dev.branch.as_deref(), Some("dev"));
+                assert_eq!(dev.paths_ignore.clone().unwrap(), vec!["docs/**"]);
+                assert_eq!(paths.action, None);
+                assert_eq!(paths.branch, None);
+                assert_eq!(paths.paths.clone().unwrap(), vec!["src/**"]);
+            }
+            _ => panic!(),
+        }
+    }
+}
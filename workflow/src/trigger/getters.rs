@@ -1,5 +1,5 @@
 // Struct
-use crate::types::Trigger;
+use crate::types::{Trigger, Upstream};
 use utils::git::Flag;
 // Error Handling
 use miette::Result;
@@ -11,10 +11,42 @@ impl Trigger {
             Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.action.clone()),
         }
     }
+    pub fn get_branch(&self) -> Result<Option<String>> {
+        match self {
+            Trigger::TriggerBranch(self_trigger_branch) => Ok(self_trigger_branch.branch.clone()),
+            Trigger::TriggerTag(_) => Ok(None),
+        }
+    }
+    pub fn get_tag(&self) -> Result<Option<String>> {
+        match self {
+            Trigger::TriggerBranch(_) => Ok(None),
+            Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.tag.clone()),
+        }
+    }
     pub fn get_commit(&self) -> Result<Option<String>> {
         match self {
             Trigger::TriggerBranch(self_trigger_branch) => Ok(self_trigger_branch.commit.clone()),
             Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.commit.clone()),
         }
     }
+    pub fn get_changed(&self) -> Result<Option<Vec<String>>> {
+        match self {
+            Trigger::TriggerBranch(self_trigger_branch) => Ok(self_trigger_branch.changed.clone()),
+            Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.changed.clone()),
+        }
+    }
+    pub fn get_scheduled_at(&self) -> Result<Option<String>> {
+        match self {
+            Trigger::TriggerBranch(self_trigger_branch) => {
+                Ok(self_trigger_branch.scheduled_at.clone())
+            }
+            Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.scheduled_at.clone()),
+        }
+    }
+    pub fn get_upstream(&self) -> Result<Option<Upstream>> {
+        match self {
+            Trigger::TriggerBranch(self_trigger_branch) => Ok(self_trigger_branch.upstream.clone()),
+            Trigger::TriggerTag(self_trigger_tag) => Ok(self_trigger_tag.upstream.clone()),
+        }
+    }
 }
@@ -1,3 +1,100 @@
 // Matching algorithm
 pub mod permissive;
 pub mod strict;
+
+// Structs
+use crate::types::{TriggerAfter, Upstream};
+// Globbing
+use glob::Pattern;
+// Dates
+use chrono::{DateTime, Local};
+use utils::dates::Schedule;
+// Error Handling
+use miette::{IntoDiagnostic, Result};
+
+/**
+Return success if at least one changed file matches the paths globs
+and is not ignored by the paths_ignore globs.
+
+If the changed files are unknown (not a git repo, manual action...)
+OR
+If the pipeline has no defined paths filter
+then there is no restriction.
+*/
+pub(crate) fn paths_match(
+    changed: &Option<Vec<String>>,
+    paths: &Option<Vec<String>>,
+    paths_ignore: &Option<Vec<String>>,
+) -> Result<bool> {
+    if changed.is_none() || (paths.is_none() && paths_ignore.is_none()) {
+        return Ok(true);
+    }
+    let globs = |list: &Option<Vec<String>>| -> Result<Vec<Pattern>> {
+        list.iter()
+            .flatten()
+            .map(|e| Pattern::new(e).into_diagnostic())
+            .collect()
+    };
+    let paths = paths.as_ref().map(|_| globs(paths)).transpose()?;
+    let paths_ignore = globs(paths_ignore)?;
+    for file in changed.iter().flatten() {
+        let is_included = match &paths {
+            Some(paths) => paths.iter().any(|e| e.matches(file)),
+            None => true,
+        };
+        let is_ignored = paths_ignore.iter().any(|e| e.matches(file));
+        if is_included && !is_ignored {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/**
+Return success if the date the scheduler fired the trigger at
+matches the trigger schedule.
+
+If the trigger wasn't fired by the scheduler
+OR
+If the pipeline has no defined schedule
+then there is no restriction.
+*/
+pub(crate) fn schedule_match(
+    scheduled_at: &Option<String>,
+    schedule: &Option<String>,
+) -> Result<bool> {
+    if let (Some(scheduled_at), Some(schedule)) = (scheduled_at, schedule) {
+        let date = scheduled_at.parse::<DateTime<Local>>().into_diagnostic()?;
+        Ok(Schedule::new(schedule)?.is_match(&date))
+    } else {
+        Ok(true)
+    }
+}
+
+/**
+Return success if the upstream pipeline run that fired the trigger
+matches the trigger upstream pipeline name and statuses.
+
+If the trigger wasn't fired by an upstream pipeline
+OR
+If the pipeline has no defined upstream condition
+then there is no restriction.
+*/
+pub(crate) fn after_match(upstream: &Option<Upstream>, after: &Option<TriggerAfter>) -> bool {
+    if let (Some(upstream), Some(after)) = (upstream, after) {
+        let is_pipeline_match = match &after.pipeline {
+            Some(name) => name == &upstream.pipeline,
+            None => true,
+        };
+        let is_status_match = match &after.status {
+            Some(statuses) => upstream
+                .status
+                .as_ref()
+                .map_or(false, |status| statuses.contains(status)),
+            None => true,
+        };
+        is_pipeline_match && is_status_match
+    } else {
+        true
+    }
+}
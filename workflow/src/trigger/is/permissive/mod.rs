@@ -2,6 +2,8 @@
 mod test;
 // Struct
 use crate::types::{Trigger, TriggerBranch, TriggerTag};
+// Changed files filter
+use crate::trigger::is::{after_match, paths_match, schedule_match};
 // Globbing
 use glob::Pattern;
 // Error Handling
@@ -43,7 +45,11 @@ impl Trigger {
 
 impl TriggerBranch {
     pub fn is_match(&self, trigger: &Self) -> Result<bool> {
-        Ok(self.is_action_match(trigger)? && self.is_branch_match(trigger)?)
+        Ok(self.is_action_match(trigger)?
+            && self.is_branch_match(trigger)?
+            && self.is_paths_match(trigger)?
+            && self.is_schedule_match(trigger)?
+            && self.is_after_match(trigger))
     }
     /**
     Return success if trigger has same action or None
@@ -55,6 +61,24 @@ impl TriggerBranch {
             Ok(false)
         }
     }
+    /**
+    Return success if the changed files pass the trigger paths filter
+    */
+    pub fn is_paths_match(&self, trigger: &Self) -> Result<bool> {
+        paths_match(&self.changed, &trigger.paths, &trigger.paths_ignore)
+    }
+    /**
+    Return success if the scheduled date matches the trigger schedule
+    */
+    pub fn is_schedule_match(&self, trigger: &Self) -> Result<bool> {
+        schedule_match(&self.scheduled_at, &trigger.schedule)
+    }
+    /**
+    Return success if the upstream run matches the trigger upstream condition
+    */
+    pub fn is_after_match(&self, trigger: &Self) -> bool {
+        after_match(&self.upstream, &trigger.after)
+    }
     pub fn is_branch_match(&self, trigger: &Self) -> Result<bool> {
         // If the project is not a git repo
         // OR
@@ -74,7 +98,11 @@ impl TriggerBranch {
 }
 impl TriggerTag {
     pub fn is_match(&self, trigger: &Self) -> Result<bool> {
-        Ok(self.is_action_match(trigger)? && self.is_tag_match(trigger)?)
+        Ok(self.is_action_match(trigger)?
+            && self.is_tag_match(trigger)?
+            && self.is_paths_match(trigger)?
+            && self.is_schedule_match(trigger)?
+            && self.is_after_match(trigger))
     }
     /**
     Return success if trigger has same action or None
@@ -86,6 +114,24 @@ impl TriggerTag {
             Ok(false)
         }
     }
+    /**
+    Return success if the changed files pass the trigger paths filter
+    */
+    pub fn is_paths_match(&self, trigger: &Self) -> Result<bool> {
+        paths_match(&self.changed, &trigger.paths, &trigger.paths_ignore)
+    }
+    /**
+    Return success if the scheduled date matches the trigger schedule
+    */
+    pub fn is_schedule_match(&self, trigger: &Self) -> Result<bool> {
+        schedule_match(&self.scheduled_at, &trigger.schedule)
+    }
+    /**
+    Return success if the upstream run matches the trigger upstream condition
+    */
+    pub fn is_after_match(&self, trigger: &Self) -> bool {
+        after_match(&self.upstream, &trigger.after)
+    }
     pub fn is_tag_match(&self, trigger: &Self) -> Result<bool> {
         // If the project is not a git repo
         // OR
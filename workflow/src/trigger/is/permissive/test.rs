@@ -156,3 +156,70 @@ mod trigger_match_git {
         assert_eq!(env.has_match(triggers).unwrap(), false);
     }
 }
+#[cfg(test)]
+mod trigger_match_paths {
+    use crate::{Trigger, TriggerBranch};
+    use utils::git::{Flag, Hook};
+
+    fn changed_env(changed: &[&str]) -> Trigger {
+        Trigger::TriggerBranch(TriggerBranch {
+            branch: Some("master".to_owned()),
+            action: Some(Flag::Hook(Hook::PrePush)),
+            changed: Some(changed.iter().map(|e| e.to_string()).collect()),
+            ..TriggerBranch::default()
+        })
+    }
+    fn filtered_triggers(paths: Option<&[&str]>, paths_ignore: Option<&[&str]>) -> Vec<Trigger> {
+        let globs = |list: &[&str]| list.iter().map(|e| e.to_string()).collect();
+        vec![Trigger::TriggerBranch(TriggerBranch {
+            branch: Some("master".to_owned()),
+            action: None,
+            paths: paths.map(globs),
+            paths_ignore: paths_ignore.map(globs),
+            ..TriggerBranch::default()
+        })]
+    }
+
+    /// match when a changed file matches the paths
+    #[test]
+    fn try_match_paths() {
+        let env = changed_env(&["docs/index.md", "src/main.rs"]);
+        let triggers = filtered_triggers(Some(&["src/**"]), None);
+        assert!(env.has_match(triggers).unwrap());
+    }
+    /// unmatch when no changed file matches the paths
+    #[test]
+    fn try_unmatch_paths() {
+        let env = changed_env(&["docs/index.md", "docs/guide/start.md"]);
+        let triggers = filtered_triggers(Some(&["src/**"]), None);
+        assert!(!env.has_match(triggers).unwrap());
+    }
+    /// unmatch when every changed file is ignored
+    #[test]
+    fn try_unmatch_paths_ignore() {
+        let env = changed_env(&["docs/index.md", "docs/guide/start.md"]);
+        let triggers = filtered_triggers(None, Some(&["docs/**"]));
+        assert!(!env.has_match(triggers).unwrap());
+    }
+    /// match when a changed file is not ignored
+    #[test]
+    fn try_match_paths_ignore() {
+        let env = changed_env(&["docs/index.md", "Cargo.toml"]);
+        let triggers = filtered_triggers(None, Some(&["docs/**"]));
+        assert!(env.has_match(triggers).unwrap());
+        // Ignored files are excluded from the matching paths
+        let triggers = filtered_triggers(Some(&["docs/**"]), Some(&["*.md"]));
+        assert!(!env.has_match(triggers).unwrap());
+    }
+    /// match when the changed files are unknown
+    #[test]
+    fn try_match_paths_unknown_changes() {
+        let env = Trigger::TriggerBranch(TriggerBranch {
+            branch: Some("master".to_owned()),
+            action: Some(Flag::Hook(Hook::PrePush)),
+            ..TriggerBranch::default()
+        });
+        let triggers = filtered_triggers(Some(&["src/**"]), None);
+        assert!(env.has_match(triggers).unwrap());
+    }
+}
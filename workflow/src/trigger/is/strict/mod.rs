@@ -43,7 +43,11 @@ impl Trigger {
 }
 impl TriggerBranch {
     pub fn is_match_strict(&self, trigger: &Self) -> Result<bool> {
-        Ok(self.is_action_match_strict(trigger)? && self.is_branch_match(trigger)?)
+        Ok(self.is_action_match_strict(trigger)?
+            && self.is_branch_match(trigger)?
+            && self.is_paths_match(trigger)?
+            && self.is_schedule_match(trigger)?
+            && self.is_after_match(trigger))
     }
     fn is_action_match_strict(&self, trigger: &Self) -> Result<bool> {
         if trigger.action.is_some() && self.action.is_some() && trigger.action == self.action {
@@ -55,7 +59,11 @@ impl TriggerBranch {
 }
 impl TriggerTag {
     pub fn is_match_strict(&self, trigger: &Self) -> Result<bool> {
-        Ok(self.is_action_match_strict(trigger)? && self.is_tag_match(trigger)?)
+        Ok(self.is_action_match_strict(trigger)?
+            && self.is_tag_match(trigger)?
+            && self.is_paths_match(trigger)?
+            && self.is_schedule_match(trigger)?
+            && self.is_after_match(trigger))
     }
     /**
     Return success if trigger has same action or None
@@ -156,3 +156,51 @@ mod trigger_match_git {
         assert_eq!(env.has_match_strict(triggers).unwrap(), false);
     }
 }
+#[cfg(test)]
+mod trigger_match_filters {
+    use crate::{Trigger, TriggerTag};
+    use chrono::{Local, TimeZone};
+    use utils::git::{Flag, Hook, Special};
+
+    #[test]
+    fn try_match_paths() {
+        let env = Trigger::TriggerTag(TriggerTag {
+            tag: Some("v0.5".to_owned()),
+            action: Some(Flag::Hook(Hook::PrePush)),
+            changed: Some(vec!["docs/index.md".to_owned()]),
+            ..TriggerTag::default()
+        });
+        let triggers = vec![Trigger::TriggerTag(TriggerTag {
+            tag: None,
+            action: Some(Flag::Hook(Hook::PrePush)),
+            paths_ignore: Some(vec!["docs/**".to_owned()]),
+            ..TriggerTag::default()
+        })];
+        assert!(!env.has_match_strict(triggers).unwrap());
+        let triggers = vec![Trigger::TriggerTag(TriggerTag {
+            tag: None,
+            action: Some(Flag::Hook(Hook::PrePush)),
+            paths: Some(vec!["docs/**".to_owned()]),
+            ..TriggerTag::default()
+        })];
+        assert!(env.has_match_strict(triggers).unwrap());
+    }
+    #[test]
+    fn try_match_schedule() {
+        let env = |hour: u32| {
+            let date = Local.with_ymd_and_hms(2023, 10, 12, hour, 0, 0).unwrap();
+            Trigger::TriggerTag(TriggerTag {
+                action: Some(Flag::Special(Special::Schedule)),
+                scheduled_at: Some(date.to_string()),
+                ..TriggerTag::default()
+            })
+        };
+        let triggers = vec![Trigger::TriggerTag(TriggerTag {
+            action: Some(Flag::Special(Special::Schedule)),
+            schedule: Some("0 3 * * *".to_owned()),
+            ..TriggerTag::default()
+        })];
+        assert!(env(3).has_match_strict(triggers.clone()).unwrap());
+        assert!(!env(4).has_match_strict(triggers).unwrap());
+    }
+}
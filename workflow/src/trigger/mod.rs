@@ -1,12 +1,15 @@
 // Tests
 mod test;
 // Structs
-use crate::types::{Trigger, TriggerBranch, TriggerTag};
-use utils::git::{Flag, Git};
+use crate::types::{Logs, Trigger, TriggerBranch, TriggerTag, Upstream};
+use utils::git::{Flag, Git, Hook};
+use uuid::Uuid;
+// Standard input
+use std::io::{self, IsTerminal, Read};
 // Global var
 use crate::globals::TRIGGER_ENV;
 // Error Handling
-use miette::Result;
+use miette::{IntoDiagnostic, Result};
 
 mod display;
 mod getters;
@@ -20,6 +23,13 @@ mod is;
 // When you trigger a pipeline, informations about the triggerring environment are gathered into a Trigger.
 // This Trigger is then matched against the ones declared in your pipeline definition.
 //
+// The files changed by the triggering action (pushed commits, staged files,
+// latest commit or watched paths) are matched against the pipeline trigger
+// "paths" and "paths_ignore" globs.
+//
+// Chained triggers are fired with the "after" action when an upstream pipeline run ends.
+// The upstream run is matched against the trigger "after" pipeline name and statuses.
+//
 // Permissive matching Algorithm: **None -> Everything**
 //
 // Used on "pipelight run".
@@ -60,22 +70,24 @@ impl Trigger {
         let env: Trigger = TRIGGER_ENV.lock().unwrap().clone();
         Ok(env)
     }
-    pub fn set(flag: Option<Flag>) -> Result<Trigger> {
+    /**
+    Compute the triggering environment and store it in the global trigger.
+    The changed files are taken from the given paths (watch, webhook),
+    absolute or relative to the working directory,
+    or computed from the git repository depending on the action.
+    They are made relative to the repository root, like the trigger paths globs.
+    */
+    pub fn set(flag: Option<Flag>, paths: Option<Vec<String>>) -> Result<Trigger> {
         // Get the global
         let env: Trigger;
 
         let mut branch = None;
         let mut tag = None;
         let mut action = None;
+        let mut changed = paths;
         // Storage value
         let mut commit = None;
 
-        // Get git info
-        if Git::new().exists() {
-            branch = Git::new().get_branch().ok();
-            tag = Git::new().get_tag().ok();
-            commit = Git::new().get_commit().ok();
-        }
         // Set env action to flag
         if flag.is_some() {
             action = flag;
@@ -85,23 +97,131 @@ impl Trigger {
             action = Some(Flag::default());
         }
 
+        // Get git info
+        if Git::new().exists() {
+            branch = Git::new().get_branch().ok();
+            tag = Git::new().get_tag().ok();
+            commit = Git::new().get_commit().ok();
+            changed = match changed {
+                Some(paths) => Some(
+                    paths
+                        .iter()
+                        .map(|e| Git::new().get_root_relative(e))
+                        .collect(),
+                ),
+                None => Trigger::get_changed_files(&action),
+            };
+        }
+
         // Set the global trigger
         if tag.is_some() {
             env = Trigger::TriggerTag(TriggerTag {
                 tag,
                 action,
+                changed,
                 commit,
+                ..TriggerTag::default()
             });
             *TRIGGER_ENV.lock().unwrap() = env.clone();
         } else {
             env = Trigger::TriggerBranch(TriggerBranch {
                 branch,
                 action,
+                changed,
                 commit,
+                ..TriggerBranch::default()
             });
             *TRIGGER_ENV.lock().unwrap() = env.clone();
         }
 
         Ok(env)
     }
+    /**
+    Set the date the scheduler fired the trigger at.
+    */
+    pub fn set_scheduled_at(date: &str) -> Result<Trigger> {
+        let mut env = TRIGGER_ENV.lock().unwrap();
+        match &mut *env {
+            Trigger::TriggerBranch(e) => e.scheduled_at = Some(date.to_owned()),
+            Trigger::TriggerTag(e) => e.scheduled_at = Some(date.to_owned()),
+        };
+        Ok(env.clone())
+    }
+    /**
+    Set the upstream run that fired the trigger, read from the logs.
+    */
+    pub fn set_upstream(uuid: &str) -> Result<Trigger> {
+        let uuid = Uuid::parse_str(uuid).into_diagnostic()?;
+        let upstream = Upstream::from(&Logs::get_by_uuid(&uuid)?);
+        let mut env = TRIGGER_ENV.lock().unwrap();
+        match &mut *env {
+            Trigger::TriggerBranch(e) => e.upstream = Some(upstream),
+            Trigger::TriggerTag(e) => e.upstream = Some(upstream),
+        };
+        Ok(env.clone())
+    }
+    /**
+    Replace the git ref and commit of the triggering env
+    with the ones received from a remote (webhook).
+    The env becomes a tag trigger if a tag is given.
+    */
+    pub fn set_ref(
+        branch: Option<String>,
+        tag: Option<String>,
+        commit: Option<String>,
+    ) -> Result<Trigger> {
+        let mut env = TRIGGER_ENV.lock().unwrap();
+        let action = env.get_action()?;
+        let changed = env.get_changed()?;
+        let scheduled_at = env.get_scheduled_at()?;
+        let upstream = env.get_upstream()?;
+        if tag.is_some() {
+            *env = Trigger::TriggerTag(TriggerTag {
+                tag,
+                action,
+                changed,
+                scheduled_at,
+                upstream,
+                commit,
+                ..TriggerTag::default()
+            });
+        } else {
+            *env = Trigger::TriggerBranch(TriggerBranch {
+                branch,
+                action,
+                changed,
+                scheduled_at,
+                upstream,
+                commit,
+                ..TriggerBranch::default()
+            });
+        }
+        Ok(env.clone())
+    }
+    /**
+    Returns the files changed by the triggering git action.
+    None if the action doesn't change files in a known way.
+    */
+    fn get_changed_files(action: &Option<Flag>) -> Option<Vec<String>> {
+        match action {
+            Some(Flag::Hook(Hook::PrePush)) => Git::new().get_pushed_files(&read_pushed_refs()).ok(),
+            Some(Flag::Hook(Hook::PreCommit)) => Git::new().get_staged_files().ok(),
+            Some(Flag::Hook(Hook::PostCommit)) => Git::new().get_commit_files().ok(),
+            _ => None,
+        }
+    }
+}
+
+/**
+Read the pushed refs the pre-push hook receives on its standard input.
+Only the first pipelight process can read them, the runs get the changed files as paths.
+*/
+fn read_pushed_refs() -> String {
+    let mut stdin = io::stdin();
+    let mut updates = String::new();
+    // Nothing to read when run by hand
+    if !stdin.is_terminal() {
+        stdin.read_to_string(&mut updates).ok();
+    }
+    updates
 }
@@ -1,6 +1,7 @@
 // Standard libs
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 pub use utils::dates::Duration;
 use uuid::Uuid;
 
@@ -21,6 +22,8 @@ pub struct ConfigOpts {
     // when triggered by a git hook.
     pub attach: Option<bool>,
     pub log_level: Option<LevelFilter>,
+    // ISO8601 quiet period that coalesces file changes into one watch trigger
+    pub watch_debounce: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -47,26 +50,80 @@ pub struct PipelineOpts {
     // when triggered by a git hook.
     pub attach: Option<bool>,
     pub log_level: Option<LevelFilter>,
+    // ISO8601 maximum pipeline duration
+    pub timeout: Option<String>,
+    // Maximum number of steps running at once when steps have "needs"
+    pub concurrency: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct Pipeline {
     pub uuid: Uuid,
     pub name: String,
+    // Whether the pipeline comes from the user global config
+    #[serde(default)]
+    pub global: bool,
     pub event: Option<Event>,
     pub status: Option<Status>,
     pub duration: Option<Duration>,
     pub triggers: Option<Vec<Trigger>>,
+    // Environment variables passed to every step
+    #[serde(serialize_with = "exec::env::serialize_masked")]
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    // Parameters declared for manual runs
+    #[serde(default)]
+    pub params: Option<Vec<Param>>,
     pub fallback: Option<Fallback>,
     pub steps: Vec<StepOrParallel>,
     pub options: Option<PipelineOpts>,
 }
 
+/**
+A typed pipeline parameter.
+Values are kept as strings and checked against the type.
+*/
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ParamType,
+    pub default: Option<String>,
+    // Allowed values
+    pub values: Option<Vec<String>>,
+    pub description: Option<String>,
+}
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+}
+
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct StepOpts {
     // The step's command execution behavior
     // Failure Handling mode
     pub mode: Option<Mode>,
+    // Absolute path to the commands working directory
+    pub cwd: Option<String>,
+    // ISO8601 maximum step duration
+    pub timeout: Option<String>,
+    // Failed commands retries
+    pub retries: Option<u32>,
+    pub retry_delay: Option<RetryDelay>,
+}
+
+/**
+The delay between command retries as an ISO8601 duration.
+*/
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryDelay {
+    Fixed(String),
+    Exponential(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -80,6 +137,16 @@ pub struct Parallel {
     pub status: Option<Status>,
     pub duration: Option<Duration>,
     pub steps: Vec<Step>,
+    // Condition expression, the parallel group is skipped when false
+    #[serde(rename = "if")]
+    #[serde(default)]
+    pub condition: Option<String>,
+    // ISO8601 maximum parallel group duration
+    pub timeout: Option<String>,
+    // Environment variables passed to every parallel step
+    #[serde(serialize_with = "exec::env::serialize_masked")]
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
     // Fallback Hooks
     pub fallback: Option<Fallback>,
 }
@@ -90,6 +157,17 @@ pub struct Step {
     pub status: Option<Status>,
     pub duration: Option<Duration>,
     pub commands: Vec<Command>,
+    // Names of the steps that must succeed before this one starts
+    #[serde(default)]
+    pub needs: Option<Vec<String>>,
+    // Condition expression, the step is skipped when false
+    #[serde(rename = "if")]
+    #[serde(default)]
+    pub condition: Option<String>,
+    // Environment variables, override the pipeline ones
+    #[serde(serialize_with = "exec::env::serialize_masked")]
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
     // Failure Handling mode
     pub options: Option<StepOpts>,
     // Fallback Hooks
@@ -101,6 +179,7 @@ pub struct Fallback {
     pub on_failure: Option<Vec<StepOrParallel>>,
     pub on_success: Option<Vec<StepOrParallel>>,
     pub on_abortion: Option<Vec<StepOrParallel>>,
+    pub on_timeout: Option<Vec<StepOrParallel>>,
 }
 
 #[derive(Debug, EnumIter, Serialize, Deserialize, Clone, PartialEq, PartialOrd, Eq, Ord)]
@@ -115,6 +194,13 @@ pub enum Mode {
 pub struct Command {
     // Pretty computatoin (Time, duration...)
     pub duration: Option<Duration>,
+    // Attempt number when the command has been retried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub attempt: Option<u32>,
+    // Command text before its placeholders are interpolated
+    #[serde(skip)]
+    pub original_stdin: Option<String>,
     // Things relevant to unix process
     pub process: Process,
 }
@@ -129,6 +215,36 @@ pub enum Trigger {
 pub struct TriggerBranch {
     pub action: Option<Flag>,
     pub branch: Option<String>,
+    // Changed files globs filtering
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub paths_ignore: Option<Vec<String>>,
+    // Cron expression fired by the scheduler
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub catch_up: Option<CatchUp>,
+    // The files changed by the triggering action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub changed: Option<Vec<String>>,
+    // Fired on the completion of another pipeline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub after: Option<TriggerAfter>,
+    // The date the scheduler fired the trigger at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub scheduled_at: Option<String>,
+    // The pipeline run that fired the trigger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub upstream: Option<Upstream>,
     // Storage value. Not used in any computation
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -138,12 +254,79 @@ pub struct TriggerBranch {
 pub struct TriggerTag {
     pub action: Option<Flag>,
     pub tag: Option<String>,
+    // Changed files globs filtering
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub paths_ignore: Option<Vec<String>>,
+    // Cron expression fired by the scheduler
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub catch_up: Option<CatchUp>,
+    // The files changed by the triggering action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub changed: Option<Vec<String>>,
+    // Fired on the completion of another pipeline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub after: Option<TriggerAfter>,
+    // The date the scheduler fired the trigger at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub scheduled_at: Option<String>,
+    // The pipeline run that fired the trigger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub upstream: Option<Upstream>,
     // Storage value. Not used in any computation
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub commit: Option<String>,
 }
 /**
+What to do with the scheduled runs missed while the scheduler was down.
+- skip: forget about them,
+- latest: run once for the latest missed date,
+- all: run once for every missed date.
+*/
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUp {
+    #[default]
+    Skip,
+    Latest,
+    All,
+}
+/**
+The upstream pipeline run condition of a chained trigger.
+- pipeline: the upstream pipeline name, any pipeline if None,
+- status: the upstream final statuses, any final status if None.
+*/
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub struct TriggerAfter {
+    pub pipeline: Option<String>,
+    pub status: Option<Vec<Status>>,
+}
+/**
+The pipeline run that triggered a downstream pipeline.
+The chain lists the upstream pipelines of this run, the eldest first.
+*/
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Upstream {
+    pub pipeline: String,
+    pub uuid: Uuid,
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub chain: Vec<String>,
+}
+/**
 The event/environment that triggered the piepline execution.
 */
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -154,6 +337,12 @@ pub struct Event {
     pub pid: Option<i32>,
     pub pgid: Option<i32>,
     pub sid: Option<i32>,
+    // Values of the pipeline parameters
+    #[serde(default)]
+    pub params: Option<BTreeMap<String, String>>,
+    // The project directory the pipeline was run from
+    #[serde(default)]
+    pub project: Option<String>,
 }
 
 /**